@@ -1,9 +1,9 @@
 use std::mem::transmute;
 
-use ffi::{IndexedMeshBuffers, Vec3};
+use ffi::{IndexedMeshBuffers, Vec2, Vec3, Vec4};
 use oneiroi::asset::Asset;
 
-use oneiroi::nodes::{ContextProvider, Node};
+use oneiroi::nodes::{ComputeError, ContextProvider, Node};
 use oneiroi::type_system::Reference as InternalReference;
 use oneiroi::type_system::data_types::{
     Collection as InternalCollection, CubicBezier as InternalCubicBezier,
@@ -15,8 +15,12 @@ use oneiroi::type_system::{
     OwnedDataType as InternalDataTypeValue, TypeRef as InternalDataTypeRef,
 };
 use oneiroi::{
-    asset::instance::AssetInstance, nodes::PropertyInterface,
-    serialization::deserialize_asset_v1 as internal_deserialize_asset_v1,
+    asset::instance::AssetInstance,
+    nodes::PropertyInterface,
+    serialization::{
+        AssetLoadError, deserialize_asset_binary_migrated, deserialize_asset_migrated,
+        migration::{MigrationRegistry, MigrationReport},
+    },
 };
 
 mod cache;
@@ -41,11 +45,34 @@ impl From<Reference> for InternalReference {
 pub struct Mesh(InternalMesh);
 
 impl Mesh {
-    fn get_mesh_buffers(&self) -> IndexedMeshBuffers {
+    fn get_mesh_buffers(&self, tangents: bool) -> IndexedMeshBuffers {
+        // Custom attributes can't cross the bridge yet so only the render buffers are passed.
+        let InternalIndexedMeshBuffers {
+            positions,
+            normals,
+            indices,
+            uvs,
+            tangents,
+            ..
+        } = self.0.get_index_mesh_buffers(tangents);
+        // glam's Vec4 is 16 byte aligned so it can't be transmuted like the others.
+        let tangents = tangents
+            .into_iter()
+            .map(|tangent| Vec4 {
+                x: tangent.x,
+                y: tangent.y,
+                z: tangent.z,
+                w: tangent.w,
+            })
+            .collect();
         unsafe {
-            transmute::<InternalIndexedMeshBuffers, IndexedMeshBuffers>(
-                self.0.get_index_mesh_buffers(),
-            )
+            IndexedMeshBuffers {
+                positions: transmute::<Vec<InternalVec3>, Vec<Vec3>>(positions),
+                normals: transmute::<Vec<InternalVec3>, Vec<Vec3>>(normals),
+                indices,
+                uvs: transmute::<_, Vec<Vec2>>(uvs),
+                tangents,
+            }
         }
     }
 
@@ -219,12 +246,18 @@ impl OneiroiInstance {
             .try_set_property(name, InternalDataTypeValue::Float(value));
     }
 
-    fn compute(&self, cache: &AssetCache) -> Vec<DataTypeValue> {
-        self.0
-            .compute(Some(cache.get_input_refs()), cache)
+    /// Errors are thrown as `rust::Error` on the C++ side with the message of the ComputeError.
+    fn compute(&self, cache: &AssetCache) -> Result<Vec<DataTypeValue>, ComputeError> {
+        Ok(self
+            .0
+            .compute(Some(cache.get_input_refs()), cache)?
             .into_iter()
             .map(|d| unsafe { transmute::<InternalDataTypeValue, DataTypeValue>(d.unwrap()) })
-            .collect::<Vec<_>>()
+            .collect::<Vec<_>>())
+    }
+
+    fn get_changed_outputs(&self) -> Vec<bool> {
+        self.0.get_changed_outputs().into_vec()
     }
 
     fn get_reference(&self, reference: Reference) -> Box<DataTypeRef> {
@@ -242,8 +275,28 @@ impl OneiroiAsset {
     }
 }
 
-fn deserialize_asset_v1(file_as_string: &str) -> Box<OneiroiAsset> {
-    Box::new(OneiroiAsset(internal_deserialize_asset_v1(file_as_string)))
+/// Errors are thrown as `rust::Error` on the C++ side with the message of the AssetLoadError.
+fn deserialize_asset_v1(file_as_string: &str) -> Result<Box<OneiroiAsset>, AssetLoadError> {
+    let (asset, report) =
+        deserialize_asset_migrated(file_as_string, &MigrationRegistry::default())?;
+    log_migration(&report);
+    Ok(Box::new(OneiroiAsset(asset)))
+}
+
+fn deserialize_asset_binary(bytes: &[u8]) -> Result<Box<OneiroiAsset>, AssetLoadError> {
+    let (asset, report) = deserialize_asset_binary_migrated(bytes, &MigrationRegistry::default())?;
+    log_migration(&report);
+    Ok(Box::new(OneiroiAsset(asset)))
+}
+
+/// Upgraded files should be saved again, otherwise they are migrated on every load.
+fn log_migration(report: &MigrationReport) {
+    if !report.is_empty() {
+        eprintln!(
+            "Migrated an Asset from format version {}: {:?}",
+            report.from_version, report.nodes
+        );
+    }
 }
 
 /* fn export_asset_v1(asset: &OneiroiAsset) -> String {
@@ -263,6 +316,18 @@ mod ffi {
         repr_u32: u32,
     }
 
+    struct Vec2 {
+        x: f32,
+        y: f32,
+    }
+
+    struct Vec4 {
+        x: f32,
+        y: f32,
+        z: f32,
+        w: f32,
+    }
+
     struct Vec3 {
         x: f32,
         y: f32,
@@ -316,6 +381,13 @@ mod ffi {
         normals: Vec<Vec3>,
 
         indices: Vec<u32>,
+
+        /// Empty if the mesh has no UVs, otherwise of same length as vertices
+        uvs: Vec<Vec2>,
+
+        /// Empty if the mesh has no UVs or they weren't requested, otherwise of same length as vertices
+        /// w is the sign of the bitangent which is cross(normal, tangent) * w
+        tangents: Vec<Vec4>,
     }
 
     /// 4x3 Colum Major Affine Transformation Matrix
@@ -338,7 +410,8 @@ mod ffi {
     extern "Rust" {
         type OneiroiAsset;
 
-        fn deserialize_asset_v1(file_as_string: &str) -> Box<OneiroiAsset>;
+        fn deserialize_asset_v1(file_as_string: &str) -> Result<Box<OneiroiAsset>>;
+        fn deserialize_asset_binary(bytes: &[u8]) -> Result<Box<OneiroiAsset>>;
 
         fn get_instance(self: &OneiroiAsset) -> Box<OneiroiInstance>;
     }
@@ -350,7 +423,9 @@ mod ffi {
         fn set_input_index(self: &mut AssetCache, idx: u8, value: &DataTypeValue);
 
         type OneiroiInstance;
-        fn compute(self: &OneiroiInstance, cache: &AssetCache) -> Vec<DataTypeValue>;
+        fn compute(self: &OneiroiInstance, cache: &AssetCache) -> Result<Vec<DataTypeValue>>;
+        /// For each output of the last compute whether it changed since the previous one.
+        fn get_changed_outputs(self: &OneiroiInstance) -> Vec<bool>;
         unsafe fn get_reference<'a>(
             self: &'a OneiroiInstance,
             reference: Reference,
@@ -387,7 +462,8 @@ mod ffi {
         fn get_transform(self: &Instance) -> Transform;
 
         type Mesh;
-        fn get_mesh_buffers(self: &Mesh) -> IndexedMeshBuffers;
+        /// Tangents are only calculated if requested since only normal mapping needs them.
+        fn get_mesh_buffers(self: &Mesh, tangents: bool) -> IndexedMeshBuffers;
         fn has_material_ref(self: &Mesh) -> bool;
         fn get_material_ref(self: &Mesh) -> Reference;
 
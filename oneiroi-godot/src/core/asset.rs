@@ -16,6 +16,43 @@ impl OneiroiAsset {
     pub fn init_from_inner(asset: Asset) -> Gd<Self> {
         Gd::from_init_fn(|base| OneiroiAsset { base, asset })
     }
+
+    /// Reverts the last edit of the graph. Returns false if there was nothing to undo.
+    #[func]
+    fn undo(&mut self) -> bool {
+        let undone = self.asset.get_edit_mut().undo().unwrap_or_else(|err| {
+            godot_error!("{err}");
+            // Part of the step might have been applied before it failed.
+            true
+        });
+        if undone {
+            self.base_mut().emit_changed();
+        }
+        undone
+    }
+
+    #[func]
+    fn redo(&mut self) -> bool {
+        let redone = self.asset.get_edit_mut().redo().unwrap_or_else(|err| {
+            godot_error!("{err}");
+            // Part of the step might have been applied before it failed.
+            true
+        });
+        if redone {
+            self.base_mut().emit_changed();
+        }
+        redone
+    }
+
+    #[func]
+    fn can_undo(&self) -> bool {
+        matches!(&self.asset, Asset::Editable(asset) if asset.can_undo())
+    }
+
+    #[func]
+    fn can_redo(&self) -> bool {
+        matches!(&self.asset, Asset::Editable(asset) if asset.can_redo())
+    }
 }
 
 impl Deref for OneiroiAsset {
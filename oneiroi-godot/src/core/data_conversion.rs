@@ -93,7 +93,8 @@ impl TypeConvert for Mesh {
     type Target = Gd<ArrayMesh>;
 
     fn convert(self) -> Self::Target {
-        let buffers = self.get_index_mesh_buffers();
+        // Materials with normal maps need the tangents.
+        let buffers = self.get_index_mesh_buffers(true);
 
         let mut mesh = ArrayMesh::new_gd();
 
@@ -115,6 +116,31 @@ impl TypeConvert for Mesh {
             .to_variant(),
         );
 
+        //Godot has the origin of UVs in the top left corner so V is flipped
+        if !buffers.uvs.is_empty() {
+            surface.set(
+                ArrayType::TEX_UV.ord().try_into().unwrap(),
+                &PackedVector2Array::from_iter(
+                    buffers.uvs.iter().map(|uv| Vector2::new(uv.x, 1.0 - uv.y)),
+                )
+                .to_variant(),
+            );
+        }
+
+        //Flipping V also flips the bitangent so its sign is negated
+        if !buffers.tangents.is_empty() {
+            surface.set(
+                ArrayType::TANGENT.ord().try_into().unwrap(),
+                &PackedFloat32Array::from_iter(
+                    buffers
+                        .tangents
+                        .iter()
+                        .flat_map(|tangent| [tangent.x, tangent.y, tangent.z, -tangent.w]),
+                )
+                .to_variant(),
+            );
+        }
+
         //Since Godot uses clockwise winding order we reverse the iterator
         surface.set(
             ArrayType::INDEX.ord().try_into().unwrap(),
@@ -217,12 +243,11 @@ impl TypeConvert for Curve {
     }
 }
 
-///TODO
 impl TypeConvert for Selection {
-    type Target = ();
+    type Target = GString;
 
     fn convert(self) -> Self::Target {
-        todo!()
+        self.get_literal().into()
     }
 }
 
@@ -288,7 +313,7 @@ impl DataTypeConversion for DataTypeValue {
             DataTypeValue::Vec3(vec3) => Vector3::new(vec3.x, vec3.y, vec3.z).to_variant(),
             DataTypeValue::Float(float) => float.to_variant(),
             DataTypeValue::Mesh(mesh) => {
-                let buffers = mesh.get_index_mesh_buffers();
+                let buffers = mesh.get_index_mesh_buffers(true);
 
                 let mut mesh = ArrayMesh::new_gd();
 
@@ -373,7 +398,7 @@ impl DataTypeConversion for DataTypeValue {
             DataTypeRef::Vec3(vec3) => Vector3::new(vec3.x, vec3.y, vec3.z).to_variant(),
             DataTypeRef::Float(float) => float.to_variant(),
             DataTypeRef::Mesh(mesh) => {
-                let buffers = mesh.get_index_mesh_buffers();
+                let buffers = mesh.get_index_mesh_buffers(true);
 
                 let mut mesh = ArrayMesh::new_gd();
 
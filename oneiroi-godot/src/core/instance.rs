@@ -9,7 +9,7 @@ use godot::{
     register::info::{PropertyHintInfo, PropertyInfo, PropertyUsageFlags},
 };
 use oneiroi::{
-    asset::{NodeIndex, instance::AssetInstance},
+    asset::{Asset, NodeIndex, instance::AssetInstance},
     nodes::{ContextProvider, Node, PropertyInterface, SocketInterface},
     type_system::{
         OwnedDataType, Reference, TypeRef,
@@ -131,6 +131,13 @@ impl INode3D for OneiroiInstance {
                 "Please select the OneiroiAsset resource this OneiroiInstance is going to use.",
             );
         }
+        if let Some(asset) = &self.asset
+            && let Asset::Editable(asset) = &**asset.bind()
+        {
+            for diagnostic in asset.validate() {
+                array.push(&diagnostic.to_string());
+            }
+        }
         array
     }
 
@@ -180,6 +187,20 @@ impl OneiroiInstance {
         self.base_mut().notify_property_list_changed();
     }
 
+    /// Converts the changed input again and recomputes the Asset with it.
+    #[func]
+    fn on_input_changed(&mut self, socket: u8) {
+        let reference = Reference::Standard {
+            node: 0.into(),
+            socket,
+        };
+        if let Some((variant, changed, value)) = self.input_sockets.get_mut(&reference) {
+            *value = variant.clone().convert();
+            *changed = true;
+        }
+        self.compute();
+    }
+
     #[func]
     fn compute(&mut self) {
         let computation_instant = Instant::now();
@@ -191,11 +212,24 @@ impl OneiroiInstance {
         }]);
 
         //TODO inject the child node data types once that system is complete
-        let outputs = self
+        let outputs = match self
             .asset_instance
             .as_ref()
             .unwrap()
-            .compute(Some(&inputs), self);
+            .compute(Some(&inputs), self)
+        {
+            Ok(outputs) => outputs,
+            Err(error) => {
+                // The previous outputs stay bound until the Asset computes again.
+                godot_error!("{error}");
+                return;
+            }
+        };
+        // The inputs are consumed, so they only count as changed again once Godot changes them.
+        for (_, changed, _) in self.input_sockets.values_mut() {
+            *changed = false;
+        }
+        let changed_outputs = self.asset_instance.as_ref().unwrap().get_changed_outputs();
         let mut rs = RenderingServer::singleton();
         for (index, output) in outputs.into_iter().enumerate() {
             if output.is_none() {
@@ -206,6 +240,8 @@ impl OneiroiInstance {
                 continue;
             }
             match output {
+                // Unchanged meshes are still bound from the previous computation.
+                OwnedDataType::Mesh(_) if !changed_outputs[index] => {}
                 OwnedDataType::Mesh(_) => {
                     let mesh: &Mesh = output.to_ref().dispatch_ref().unwrap();
                     let material_ref = mesh.get_material_ref();
@@ -370,7 +406,10 @@ impl OneiroiInstance {
                                 },
                                 (gd_curve.to_variant(), true, godot_variant.convert()),
                             );
-                            let callable = self.base_mut().callable("compute");
+                            let callable = self
+                                .base()
+                                .callable("on_input_changed")
+                                .bind(&[(index as u8).to_variant()]);
                             path.connect("curve_changed", &callable);
                             continue;
                         }
@@ -423,4 +462,10 @@ impl ContextProvider for OneiroiInstance {
         println!("{:#?} , index: {:?}", self.input_sockets, index);
         (&self.input_sockets.get(&index).unwrap().2).into()
     }
+
+    fn reference_changed(&self, index: Reference) -> bool {
+        self.input_sockets
+            .get(&index)
+            .is_none_or(|(_, changed, _)| *changed)
+    }
 }
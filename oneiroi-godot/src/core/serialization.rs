@@ -1,14 +1,26 @@
+use std::cell::RefCell;
+
 use godot::{
     classes::{
         Engine, FileAccess, IResourceFormatLoader, IResourceFormatSaver, ResourceFormatLoader,
-        ResourceFormatSaver, ResourceLoader, ResourceSaver, file_access::ModeFlags,
+        ResourceFormatSaver, ResourceLoader, ResourceSaver, ResourceUid, file_access::ModeFlags,
+        resource_loader::CacheMode,
     },
     prelude::*,
 };
-use oneiroi::serialization::{deserialize_asset_v1, serialize_asset_v1};
+use oneiroi::asset::AssetReference;
+use oneiroi::asset::server::{AssetResolver, AssetServer, ResolveError};
+use oneiroi::serialization::migration::MigrationRegistry;
+use oneiroi::serialization::{
+    AssetLoadError, deserialize_asset_binary_migrated, deserialize_asset_migrated, is_binary_asset,
+    serialize_asset_binary, serialize_asset_v1,
+};
 
 use super::asset::{OneiroiAsset /* OneiroiAssetInstance */};
 
+/// Assets saved with this extension are stored in the compact binary form.
+const BINARY_EXTENSION: &str = "onib";
+
 pub fn register_input_output() {
     Engine::singleton().register_singleton(
         "OneiroiInputOutputSingleton",
@@ -58,6 +70,7 @@ impl IResourceFormatSaver for OneiroiAssetSaver {
         let mut arr = PackedStringArray::new();
         if res.unwrap().is_class("OneiroiAsset") {
             arr.push("oni");
+            arr.push(BINARY_EXTENSION);
         }
 
         arr
@@ -78,21 +91,47 @@ impl IResourceFormatSaver for OneiroiAssetSaver {
             .try_cast::<OneiroiAsset>()
             .expect("Godot called this function with the wrong resource type");
 
-        let string = serialize_asset_v1(&res.bind());
-
         let mut file =
             FileAccess::open(&path, ModeFlags::WRITE).expect("Cant obtain write access to path");
 
-        file.store_string(&string);
+        if path.get_extension().to_lower() == BINARY_EXTENSION {
+            let bytes = serialize_asset_binary(&res.bind());
+            file.store_buffer(&PackedByteArray::from(bytes.as_slice()));
+        } else {
+            file.store_string(&serialize_asset_v1(&res.bind()));
+        }
 
         godot::global::Error::OK
     }
 }
 
+/// Reads referenced Assets through Godot so both res:// paths and uid:// references work.
+struct GodotAssetResolver;
+
+impl AssetResolver for GodotAssetResolver {
+    fn load(&self, reference: &AssetReference) -> Result<Vec<u8>, ResolveError> {
+        let mut path = GString::from(reference.get_path());
+        if let Some(uid) = reference.get_uid() {
+            let uids = ResourceUid::singleton();
+            let id = uids.text_to_id(&GString::from(uid));
+            if uids.has_id(id) {
+                path = uids.get_id_path(id);
+            }
+        }
+
+        let file = FileAccess::open(&path, ModeFlags::READ)
+            .ok_or_else(|| ResolveError::NotFound(reference.clone()))?;
+        Ok(file.get_buffer(file.get_length() as i64).to_vec())
+    }
+}
+
 #[derive(GodotClass)]
 #[class(init,tool, base=ResourceFormatLoader)]
 pub struct OneiroiAssetLoader {
     base: Base<ResourceFormatLoader>,
+    // Shared between all loads so Assets referencing the same file share its template.
+    #[init(val=RefCell::new(AssetServer::new(GodotAssetResolver)))]
+    server: RefCell<AssetServer>,
 }
 
 #[godot_api]
@@ -100,6 +139,7 @@ impl IResourceFormatLoader for OneiroiAssetLoader {
     fn get_recognized_extensions(&self) -> PackedStringArray {
         let mut arr = PackedStringArray::new();
         arr.push("oni");
+        arr.push(BINARY_EXTENSION);
         arr
     }
 
@@ -108,7 +148,8 @@ impl IResourceFormatLoader for OneiroiAssetLoader {
     }
 
     fn get_resource_type(&self, path: GString) -> GString {
-        if path.get_extension().to_lower() == ".oni" {
+        let extension = path.get_extension().to_lower();
+        if extension == "oni" || extension == BINARY_EXTENSION {
             "OneiroiAsset".into()
         } else {
             "".into()
@@ -122,11 +163,64 @@ impl IResourceFormatLoader for OneiroiAssetLoader {
         use_sub_threads: bool,
         cache_mode: i32,
     ) -> Variant {
-        let file =
-            FileAccess::open(&path, ModeFlags::READ).expect("Cant obtain read access to path");
+        // Godot expects the error code as the result when a resource can't be loaded.
+        let Some(file) = FileAccess::open(&path, ModeFlags::READ) else {
+            godot_error!("Can't obtain read access to {path}");
+            return godot::global::Error::ERR_FILE_CANT_OPEN.to_variant();
+        };
+
+        let file_content = file.get_buffer(file.get_length() as i64).to_vec();
+        let registry = MigrationRegistry::default();
+        let loaded = if is_binary_asset(&file_content) {
+            deserialize_asset_binary_migrated(&file_content, &registry)
+        } else {
+            String::from_utf8(file_content)
+                .map_err(AssetLoadError::Encoding)
+                .and_then(|content| deserialize_asset_migrated(&content, &registry))
+        };
+        let (mut raw_asset, report) = match loaded {
+            Ok(loaded) => loaded,
+            Err(err) => {
+                godot_error!("Couldn't load {path}: {err}");
+                return godot::global::Error::ERR_FILE_CORRUPT.to_variant();
+            }
+        };
+        if !report.is_empty() {
+            godot_print!(
+                "Migrated {path} from format version {}: {:?}",
+                report.from_version,
+                report.nodes
+            );
+        }
 
-        let file_content: String = file.get_as_text().to_string();
-        let raw_asset = deserialize_asset_v1(&file_content);
+        // The file might have changed since it was last loaded so drop everything built on top of it.
+        // Templates are cached under the uid if the file has one, references without it use the path.
+        let mut server = self.server.borrow_mut();
+        let mut invalidated = server
+            .invalidate(&AssetReference::new(path.to_string(), None))
+            .into_vec();
+        let id = ResourceLoader::singleton().get_resource_uid(&path);
+        if id != ResourceUid::INVALID_ID {
+            let uid = ResourceUid::singleton().id_to_text(id).to_string();
+            let reference = AssetReference::new(path.to_string(), Some(uid));
+            invalidated.extend(server.invalidate(&reference));
+        }
+        if let Err(err) = server.resolve(&mut raw_asset) {
+            godot_error!("Couldn't resolve the Assets referenced by {path}: {err}");
+        }
+        // Loading the dependents goes through this loader again.
+        drop(server);
+
+        // Loaded Assets embedding this file still hold its old content, so they are loaded again.
+        for dependent in invalidated {
+            let dependent_path = GString::from(dependent.get_path());
+            if dependent_path != path && ResourceLoader::singleton().has_cached(&dependent_path) {
+                ResourceLoader::singleton()
+                    .load_ex(&dependent_path)
+                    .cache_mode(CacheMode::REPLACE)
+                    .done();
+            }
+        }
         OneiroiAsset::init_from_inner(raw_asset).to_variant()
     }
 }
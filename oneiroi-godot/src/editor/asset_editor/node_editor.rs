@@ -187,19 +187,29 @@ impl OneiroiNodeEditor {
     fn on_node_instantiated(&mut self, alias: GString, position: Vector2) {
         self.node_adder.set_visible(false);
 
-        let index = self
-            .asset
-            .bind_mut()
-            .get_edit_mut()
-            .add_node(&alias.to_string());
-
-        self.asset.bind_mut().get_edit_mut().try_set_node_position(
-            index,
-            oneiroi::asset::Vec2 {
-                x: position.x,
-                y: position.y,
-            },
-        );
+        let index = {
+            let mut asset = self.asset.bind_mut();
+            let asset = asset.get_edit_mut();
+            // Adding and placing the node is a single step for undo.
+            asset.begin_group();
+            let index = match asset.add_node(&alias.to_string()) {
+                Ok(index) => index,
+                Err(err) => {
+                    asset.end_group();
+                    godot_error!("Couldn't add the node {alias}: {err:?}");
+                    return;
+                }
+            };
+            asset.try_set_node_position(
+                index,
+                oneiroi::asset::Vec2 {
+                    x: position.x,
+                    y: position.y,
+                },
+            );
+            asset.end_group();
+            index
+        };
 
         //add and register in graph
         /* let mut node: Gd<OneiroiGraphNode> = */
@@ -57,6 +57,36 @@ impl ITree for AddNodeTree {
             .create_item()
             .expect("Couldnt generate new Tree Item");
         item.set_text(0, "Cylinder");
+        let mut item = self
+            .base_mut()
+            .create_item()
+            .expect("Couldnt generate new Tree Item");
+        item.set_text(0, "Sphere");
+        let mut item = self
+            .base_mut()
+            .create_item()
+            .expect("Couldnt generate new Tree Item");
+        item.set_text(0, "Icosphere");
+        let mut item = self
+            .base_mut()
+            .create_item()
+            .expect("Couldnt generate new Tree Item");
+        item.set_text(0, "Torus");
+        let mut item = self
+            .base_mut()
+            .create_item()
+            .expect("Couldnt generate new Tree Item");
+        item.set_text(0, "Cone");
+        let mut item = self
+            .base_mut()
+            .create_item()
+            .expect("Couldnt generate new Tree Item");
+        item.set_text(0, "Plane");
+        let mut item = self
+            .base_mut()
+            .create_item()
+            .expect("Couldnt generate new Tree Item");
+        item.set_text(0, "Grid");
         let mut item = self
             .base_mut()
             .create_item()
@@ -122,6 +152,51 @@ impl ITree for AddNodeTree {
             .create_item()
             .expect("Couldnt generate new Tree Item");
         item.set_text(0, "Bevel");
+        let mut item = self
+            .base_mut()
+            .create_item()
+            .expect("Couldnt generate new Tree Item");
+        item.set_text(0, "Boolean");
+        let mut item = self
+            .base_mut()
+            .create_item()
+            .expect("Couldnt generate new Tree Item");
+        item.set_text(0, "JoinMeshes");
+        let mut item = self
+            .base_mut()
+            .create_item()
+            .expect("Couldnt generate new Tree Item");
+        item.set_text(0, "Subdivide");
+        let mut item = self
+            .base_mut()
+            .create_item()
+            .expect("Couldnt generate new Tree Item");
+        item.set_text(0, "Transform");
+        let mut item = self
+            .base_mut()
+            .create_item()
+            .expect("Couldnt generate new Tree Item");
+        item.set_text(0, "Array");
+        let mut item = self
+            .base_mut()
+            .create_item()
+            .expect("Couldnt generate new Tree Item");
+        item.set_text(0, "Mirror");
+        let mut item = self
+            .base_mut()
+            .create_item()
+            .expect("Couldnt generate new Tree Item");
+        item.set_text(0, "UvProject");
+        let mut item = self
+            .base_mut()
+            .create_item()
+            .expect("Couldnt generate new Tree Item");
+        item.set_text(0, "SetShading");
+        let mut item = self
+            .base_mut()
+            .create_item()
+            .expect("Couldnt generate new Tree Item");
+        item.set_text(0, "CleanupMesh");
         //subscribe to item activated signal and connect it to on_item_activated
         let item_activated_handler = self.base().callable("on_item_activated");
         self.base_mut()
@@ -6,6 +6,7 @@ use editable::EditableAsset;
 use fixedbitset::FixedBitSet;
 pub use glam::Vec2;
 use instance::AssetInstance;
+use itertools::Either;
 use petgraph::{Directed, prelude::StableGraph};
 use runtime::RuntimeAsset;
 use rustc_hash::FxBuildHasher;
@@ -20,18 +21,21 @@ pub type NodeIndex = InternalNodeIndex<u16>;
 pub type EdgeIndex = InternalEdgeIndex<u16>;
 
 use crate::nodes::Nodes;
+use crate::property::PropertyMetadata;
 use crate::property::script::Script;
 use crate::type_system::OwnedDataType;
 use crate::type_system::Reference;
 use crate::type_system::data_types::TypeDescriptor;
 
 pub mod editable;
+mod history;
 pub mod instance;
 pub mod runtime;
 pub mod server;
 pub mod template;
 #[cfg(test)]
 pub mod test;
+pub mod validation;
 
 // The Graph that holds the connection state of the Asset
 pub(crate) type OneiroiGraph = StableGraph<(NodeMetadata, Nodes), Connection, Directed, u16>;
@@ -116,10 +120,10 @@ impl NodeMetadata {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum EmbeddedAsset {
     Internal(Asset, Vec<NodeIndex>),
-    //TODO add supsocket for external assets
+    // The template of external Assets gets injected by the AssetServer.
     External(AssetReference, Vec<NodeIndex>),
 }
 impl EmbeddedAsset {
@@ -130,21 +134,73 @@ impl EmbeddedAsset {
         }
     }
 
-    fn get_template(&self) -> Arc<AssetTemplate> {
+    fn graph_nodes_mut(&mut self) -> &mut Vec<NodeIndex> {
         match self {
-            EmbeddedAsset::Internal(asset, _) => asset.get_template(),
-            EmbeddedAsset::External(asset_reference, _) => todo!(),
+            EmbeddedAsset::Internal(_, items) => items,
+            EmbeddedAsset::External(_, items) => items,
+        }
+    }
+
+    /// Only available once all external Assets inside of it are resolved.
+    fn get_template(&self) -> Option<Arc<AssetTemplate>> {
+        match self {
+            EmbeddedAsset::Internal(asset, _) if asset.is_resolved() => Some(asset.get_template()),
+            EmbeddedAsset::Internal(..) => None,
+            EmbeddedAsset::External(..) => None,
+        }
+    }
+
+    /// Bakes the embedded Asset for the runtime form of the parent.
+    /// Only nodes which stayed dynamic in the parent template need the embedded template
+    /// since everything else already got evaluated into the constant cache.
+    fn to_runtime(&self, parent: &AssetTemplate) -> Option<EmbeddedAsset> {
+        let nodes = self
+            .graph_nodes()
+            .into_iter()
+            .filter(|node| parent.is_node_dynamic(*node))
+            .collect::<Vec<_>>();
+        if nodes.is_empty() {
+            return None;
+        }
+
+        match self {
+            EmbeddedAsset::Internal(asset, _) => Some(EmbeddedAsset::Internal(
+                Asset::Runtime(asset.to_runtime()),
+                nodes,
+            )),
+            EmbeddedAsset::External(asset_reference, _) => {
+                Some(EmbeddedAsset::External(asset_reference.clone(), nodes))
+            }
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct AssetReference {
     path: String,
     uid: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+impl AssetReference {
+    pub fn new(path: String, uid: Option<String>) -> Self {
+        Self { path, uid }
+    }
+
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn get_uid(&self) -> Option<&str> {
+        self.uid.as_deref()
+    }
+
+    /// Identifies the referenced file. The uid is preferred since it survives moving the file.
+    pub(crate) fn key(&self) -> &str {
+        self.uid.as_deref().unwrap_or(&self.path)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(untagged)]
 pub enum Asset {
     Editable(EditableAsset),
@@ -161,10 +217,42 @@ impl Asset {
     pub fn get_instance(&self) -> AssetInstance {
         match self {
             Asset::Editable(edit_asset) => edit_asset.get_instance(),
-            Asset::Runtime(runtime_asset) => todo!(),
+            Asset::Runtime(runtime_asset) => runtime_asset.get_instance(),
+        }
+    }
+
+    /// Produces the stripped form of the Asset which is meant to be shipped with the game.
+    pub fn to_runtime(&self) -> RuntimeAsset {
+        match self {
+            Asset::Editable(edit_asset) => edit_asset.to_runtime(),
+            Asset::Runtime(runtime_asset) => runtime_asset.clone(),
         }
     }
 
+    /// Whether all embedded Assets have their template so instances can be computed.
+    /// External Assets need to be resolved by an AssetServer first.
+    pub fn is_resolved(&self) -> bool {
+        match self {
+            Asset::Editable(edit_asset) => edit_asset.is_resolved(),
+            Asset::Runtime(runtime_asset) => runtime_asset.is_resolved(),
+        }
+    }
+
+    /// All external Assets directly embedded by this Asset.
+    pub fn get_external_references(&self) -> Vec<AssetReference> {
+        let embedded_assets = match self {
+            Asset::Editable(edit_asset) => edit_asset.get_embedded_assets(),
+            Asset::Runtime(runtime_asset) => runtime_asset.get_embedded_assets(),
+        };
+        embedded_assets
+            .iter()
+            .filter_map(|asset| match asset {
+                EmbeddedAsset::Internal(..) => None,
+                EmbeddedAsset::External(reference, _) => Some(reference.clone()),
+            })
+            .collect()
+    }
+
     pub fn get_edit_mut(&mut self) -> &mut EditableAsset {
         match self {
             Asset::Editable(edit_asset) => edit_asset,
@@ -195,6 +283,20 @@ pub(crate) trait AssetBase {
 
     fn get_node_dependencies(&self, index: NodeIndex) -> Box<[Reference]>;
 
+    /// Every Property connection leaving the Expose node exposes the targeted node property.
+    /// The targets are grouped by the exposed property they belong to in order of its index.
+    fn get_exposed_property_targets(&self) -> Box<[Box<[Reference]>]>;
+
+    /// Builds the metadata of one exposed property from the node properties it targets.
+    /// The current value of the first target is used as the default.
+    fn get_exposed_property_metadata(&self, targets: &[Reference]) -> PropertyMetadata;
+
+    /// Same as for the inputs but starting from the nodes each exposed property is connected to.
+    fn nodes_reachable_from_respective_property(
+        &self,
+        exposed_targets: &[Box<[Reference]>],
+    ) -> Box<[FixedBitSet]>;
+
     fn is_node_input(&self) -> FixedBitSet;
     fn is_node_output(&self) -> FixedBitSet;
 
@@ -203,10 +305,15 @@ pub(crate) trait AssetBase {
         dynamic_nodes: &FixedBitSet,
     ) -> HashMap<NodeIndex, (Box<[Reference]>, Nodes), FxBuildHasher>;
 
+    /// Evaluates all constant nodes.
+    /// Also returns the nodes which failed to evaluate together with the nodes depending on them.
     fn get_const_cache(
         &self,
         dynamic_nodes_without_outputs: &FixedBitSet,
-    ) -> HashMap<NodeIndex, Box<[OwnedDataType]>, FxBuildHasher>;
+    ) -> (
+        HashMap<NodeIndex, Box<[OwnedDataType]>, FxBuildHasher>,
+        FixedBitSet,
+    );
 
     fn get_outputs_and_info(
         &self,
@@ -223,7 +330,7 @@ impl AssetBase for Asset {
     fn get_template(&self) -> Arc<AssetTemplate> {
         match self {
             Asset::Editable(edit_asset) => edit_asset.get_template(),
-            Asset::Runtime(runtime_asset) => todo!(),
+            Asset::Runtime(runtime_asset) => runtime_asset.get_template(),
         }
     }
 
@@ -241,14 +348,43 @@ impl AssetBase for Asset {
     fn get_node_dependencies(&self, index: NodeIndex) -> Box<[Reference]> {
         match self {
             Asset::Editable(edit_asset) => edit_asset.get_node_dependencies(index),
-            Asset::Runtime(runtime_asset) => todo!(),
+            Asset::Runtime(runtime_asset) => runtime_asset.get_node_dependencies(index),
+        }
+    }
+
+    fn get_exposed_property_targets(&self) -> Box<[Box<[Reference]>]> {
+        match self {
+            Asset::Editable(edit_asset) => edit_asset.get_exposed_property_targets(),
+            Asset::Runtime(runtime_asset) => runtime_asset.get_exposed_property_targets(),
+        }
+    }
+
+    fn get_exposed_property_metadata(&self, targets: &[Reference]) -> PropertyMetadata {
+        match self {
+            Asset::Editable(edit_asset) => edit_asset.get_exposed_property_metadata(targets),
+            Asset::Runtime(runtime_asset) => runtime_asset.get_exposed_property_metadata(targets),
+        }
+    }
+
+    fn nodes_reachable_from_respective_property(
+        &self,
+        exposed_targets: &[Box<[Reference]>],
+    ) -> Box<[FixedBitSet]> {
+        match self {
+            Asset::Editable(edit_asset) => {
+                edit_asset.nodes_reachable_from_respective_property(exposed_targets)
+            }
+            Asset::Runtime(runtime_asset) => {
+                runtime_asset.nodes_reachable_from_respective_property(exposed_targets)
+            }
         }
     }
 
     fn get_scripts(&self) -> impl Iterator<Item = (&Reference, &Script)> {
         match self {
-            Asset::Editable(edit_asset) => edit_asset.get_scripts(),
-            Asset::Runtime(runtime_asset) => todo!(),
+            Asset::Editable(edit_asset) => Either::Left(edit_asset.get_scripts()),
+            // Scripts are already evaluated while baking the runtime form.
+            Asset::Runtime(_) => Either::Right(std::iter::empty()),
         }
     }
 
@@ -274,7 +410,10 @@ impl AssetBase for Asset {
     fn get_const_cache(
         &self,
         dynamic_nodes_without_outputs: &FixedBitSet,
-    ) -> HashMap<NodeIndex, Box<[OwnedDataType]>, FxBuildHasher> {
+    ) -> (
+        HashMap<NodeIndex, Box<[OwnedDataType]>, FxBuildHasher>,
+        FixedBitSet,
+    ) {
         todo!()
     }
     fn get_outputs_and_info(
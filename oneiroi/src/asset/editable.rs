@@ -7,15 +7,20 @@ use glam::Vec3;
 use itertools::Itertools;
 use petgraph::Direction::{self, Incoming, Outgoing};
 use petgraph::algo::toposort;
-use petgraph::visit::{Bfs, EdgeRef, NodeFiltered, Topo};
+use petgraph::visit::{Bfs, EdgeRef, NodeFiltered, NodeIndexable, Topo};
 use rustc_hash::FxBuildHasher;
 use serde::{Deserialize, Serialize};
 
+use super::Asset;
 use super::AssetBase;
+use super::AssetReference;
 use super::EdgeIndex;
 use super::NodeIndex;
 use super::OneiroiGraph;
+use super::history::{Edit, History, RemovedNode};
 use super::instance::AssetInstance;
+use super::runtime::RuntimeAsset;
+use super::server::{AssetServer, ResolveError};
 
 use crate::nodes::Node;
 use crate::nodes::Nodes;
@@ -23,10 +28,10 @@ use crate::nodes::PropertyInterface;
 use crate::nodes::SocketInterface;
 use crate::nodes::SocketMetadata;
 use crate::nodes::StaticNodeMetadata;
-use crate::property::PropertyMetadata;
 use crate::property::script::Script;
+use crate::property::{PropertyInstance, PropertyMetadata};
 use crate::type_system::Reference;
-use crate::type_system::data_types::TypeDescriptor;
+use crate::type_system::data_types::{DataTypeKind, TypeDescriptor};
 use crate::type_system::{OwnedDataType, TypeRef};
 
 use super::AssetTemplate;
@@ -51,12 +56,16 @@ struct EditableAssetDeserializeProxy {
 
 impl From<EditableAssetDeserializeProxy> for EditableAsset {
     fn from(mut value: EditableAssetDeserializeProxy) -> Self {
+        // Assets which embed external Assets get their templates from the AssetServer.
         for asset in &mut value.embedded_assets {
+            let Some(template) = asset.get_template() else {
+                continue;
+            };
             for node in asset.graph_nodes() {
                 value.graph[node]
                     .1
                     .get_embedded_instance()
-                    .set_template(asset.get_template());
+                    .set_template(template.clone());
             }
         }
 
@@ -68,11 +77,12 @@ impl From<EditableAssetDeserializeProxy> for EditableAsset {
             embedded_assets: value.embedded_assets,
             template: OnceLock::new(),
             property_scripts: value.property_scripts,
+            history: History::default(),
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(from = "EditableAssetDeserializeProxy")]
 pub struct EditableAsset {
     graph: OneiroiGraph,
@@ -87,6 +97,9 @@ pub struct EditableAsset {
 
     #[serde(skip)]
     template: OnceLock<Arc<AssetTemplate>>,
+
+    #[serde(skip)]
+    history: History,
 }
 
 //Takes care of providing a default cube with output
@@ -100,19 +113,22 @@ impl Default for EditableAsset {
             template: Default::default(),
             exposed_property_order: Default::default(),
             property_scripts: Default::default(),
+            history: Default::default(),
         };
 
         _ = asset.graph.add_node((NodeMetadata::empty(), Nodes::Expose));
 
         //add default cube
-        let n1 = asset.add_node("Output");
+        let n1 = asset.add_node("Output").unwrap();
 
-        let n2 = asset.add_node("Box");
+        let n2 = asset.add_node("Box").unwrap();
         let node = &mut asset.graph[n2];
         node.0.set_position(Vec2 { x: -160., y: 0. });
 
         _ = asset.try_add_node_connection(n2, 0, n1, 0);
 
+        // The default content is not an edit of the user.
+        asset.history.clear();
         asset
     }
 }
@@ -120,15 +136,17 @@ impl Default for EditableAsset {
 impl EditableAsset {
     //TODO this should theorethically go in the trivial helper trait
     #[cfg(feature = "editor")]
-    pub fn add_node(&mut self, alias: &str) -> NodeIndex {
+    pub fn add_node(&mut self, alias: &str) -> Result<NodeIndex, NodeError> {
         let node = match alias {
-            "EmbeddedAsset" => return self.add_subgraph(),
-            _ => Nodes::from_alias(alias),
+            "EmbeddedAsset" => return Ok(self.add_subgraph()),
+            _ => Nodes::from_alias(alias).ok_or_else(|| NodeError::UnknownAlias(alias.into()))?,
         };
 
         let mut meta = NodeMetadata::empty();
         meta.set_name(alias.into());
-        self.graph.add_node((meta, node))
+        let index = self.graph.add_node((meta, node));
+        self.record(Edit::RemoveNode(index));
+        Ok(index)
     }
 
     //TODO this should theorethically go in the trivial helper trait
@@ -145,14 +163,57 @@ impl EditableAsset {
             super::Asset::Editable(asset),
             vec![node],
         ));
+        // Undoing the node drops the entry of the embedded Asset as well.
+        self.history.begin_group();
+        self.record(Edit::RemoveEmbeddedAsset(self.embedded_assets.len() - 1));
+        self.record(Edit::RemoveNode(node));
+        self.history.end_group();
         node
     }
 
+    /// Embeds an Asset from another file which is shared by every Asset embedding the same file.
+    #[cfg(feature = "editor")]
+    pub fn add_external_subgraph(
+        &mut self,
+        reference: AssetReference,
+        server: &mut AssetServer,
+    ) -> Result<NodeIndex, ResolveError> {
+        let template = server.load_template(&reference, &mut Vec::new())?;
+        let mut meta = NodeMetadata::empty();
+        meta.set_name("EmbeddedAsset".into());
+        let node = self.graph.add_node((
+            meta,
+            Nodes::EmbeddedAsset(Box::new(AssetInstance::new(&template))),
+        ));
+
+        self.history.begin_group();
+        let existing = self
+            .embedded_assets
+            .iter_mut()
+            .find_map(|asset| match asset {
+                EmbeddedAsset::External(existing, nodes) if existing.key() == reference.key() => {
+                    Some(nodes)
+                }
+                _ => None,
+            });
+        match existing {
+            Some(nodes) => nodes.push(node),
+            None => {
+                self.embedded_assets
+                    .push(EmbeddedAsset::External(reference, vec![node]));
+                self.record(Edit::RemoveEmbeddedAsset(self.embedded_assets.len() - 1));
+            }
+        }
+        self.record(Edit::RemoveNode(node));
+        self.history.end_group();
+        Ok(node)
+    }
+
     #[cfg(feature = "editor")]
     pub fn is_connection_allowed(
         &self,
-        from: NodeIndex,
-        from_socket: u8, //TODO maybe distinct type dont know yet how it plays out with parameter
+        _from: NodeIndex,
+        _from_socket: u8, //TODO maybe distinct type dont know yet how it plays out with parameter
         to: NodeIndex,
         to_socket: u8, //TODO maybe distinct type dont know yet how it plays out with parameter
     ) -> bool {
@@ -204,6 +265,296 @@ impl EditableAsset {
                 .get_or_init(|| Arc::new(AssetTemplate::transform(self))),
         )
     }
+
+    pub(super) fn is_resolved(&self) -> bool {
+        self.embedded_assets
+            .iter()
+            .flat_map(|asset| asset.graph_nodes())
+            .all(|node| {
+                self.graph[node]
+                    .1
+                    .as_embedded_instance()
+                    .is_some_and(|instance| instance.has_template())
+            })
+    }
+
+    pub(super) fn get_embedded_assets(&self) -> &[EmbeddedAsset] {
+        &self.embedded_assets
+    }
+
+    pub(super) fn get_graph(&self) -> &OneiroiGraph {
+        &self.graph
+    }
+
+    pub(super) fn get_property_scripts(&self) -> &HashMap<Reference, Script, FxBuildHasher> {
+        &self.property_scripts
+    }
+
+    pub(super) fn get_exposed_property_order(&self) -> &[Reference] {
+        &self.exposed_property_order
+    }
+
+    /// Resolves all embedded Assets recursively and injects their templates into the nodes.
+    pub(super) fn resolve_embedded(
+        &mut self,
+        server: &mut AssetServer,
+        stack: &mut Vec<AssetReference>,
+    ) -> Result<(), ResolveError> {
+        for asset in &mut self.embedded_assets {
+            let template = match asset {
+                EmbeddedAsset::Internal(asset, _) => {
+                    server.resolve_nested(asset, stack)?;
+                    asset.get_template()
+                }
+                EmbeddedAsset::External(reference, _) => server.load_template(reference, stack)?,
+            };
+            for node in asset.graph_nodes() {
+                self.graph[node]
+                    .1
+                    .get_embedded_instance()
+                    .set_template(template.clone());
+            }
+        }
+        // The template bakes the results of the embedded Assets so it is outdated now.
+        self.template = OnceLock::new();
+        Ok(())
+    }
+
+    /// Inlines all external Assets so the embedded Assets no longer depend on other files.
+    /// The graph instances already hold the resolved templates so only the entries change.
+    pub(super) fn bundle_embedded(
+        &mut self,
+        server: &mut AssetServer,
+        stack: &mut Vec<AssetReference>,
+        bundled: &mut HashMap<String, Asset>,
+    ) -> Result<(), ResolveError> {
+        self.embedded_assets =
+            server.bundle_embedded_assets(&self.embedded_assets, stack, bundled)?;
+        Ok(())
+    }
+
+    /// Reverts the last undo step. Returns false if there is nothing to undo.
+    pub fn undo(&mut self) -> Result<bool, HistoryError> {
+        let Some(edits) = self.history.pop_undo() else {
+            return Ok(false);
+        };
+        let inverse = self.apply_step(edits)?;
+        self.history.push_redo(inverse);
+        Ok(true)
+    }
+
+    /// Repeats the last undone step. Returns false if there is nothing to redo.
+    pub fn redo(&mut self) -> Result<bool, HistoryError> {
+        let Some(edits) = self.history.pop_redo() else {
+            return Ok(false);
+        };
+        let inverse = self.apply_step(edits)?;
+        self.history.push_undo(inverse);
+        Ok(true)
+    }
+
+    /// Applies the inverse Edits of a step in reverse order and returns their inverses.
+    /// If one of them fails the history no longer matches the graph and is dropped.
+    fn apply_step(&mut self, edits: Vec<Edit>) -> Result<Vec<Edit>, HistoryError> {
+        let inverse = edits
+            .into_iter()
+            .rev()
+            .map(|edit| self.apply(edit))
+            .collect::<Result<_, _>>();
+        if inverse.is_err() {
+            self.history.clear();
+        }
+        inverse
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    /// All edits until the matching end_group are undone as a single step
+    /// e.g. when moving multiple nodes at once. Groups can be nested.
+    pub fn begin_group(&mut self) {
+        self.history.begin_group();
+    }
+
+    pub fn end_group(&mut self) {
+        self.history.end_group();
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// Applies the edit and records its inverse as a new undo step.
+    fn execute(&mut self, edit: Edit) {
+        let inverse = self
+            .apply(edit)
+            .expect("Only restoring a removed node can fail");
+        self.history.record(inverse);
+    }
+
+    /// Records the inverse of a change which was made without an Edit.
+    fn record(&mut self, inverse: Edit) {
+        self.template = OnceLock::new();
+        self.history.record(inverse);
+    }
+
+    /// Applies the edit and returns the edit reverting it.
+    fn apply(&mut self, edit: Edit) -> Result<Edit, HistoryError> {
+        // The template is baked from the graph so it is outdated after every change.
+        self.template = OnceLock::new();
+        Ok(match edit {
+            Edit::InsertNode(removed) => {
+                let RemovedNode {
+                    index,
+                    weight,
+                    connections,
+                    embedded_asset,
+                } = *removed;
+                self.restore_node(index, weight)?;
+                for (from, to, connection) in connections {
+                    self.graph.add_edge(from, to, connection);
+                }
+                if let Some(position) = embedded_asset {
+                    self.restore_embedded_instance(position, index);
+                }
+                Edit::RemoveNode(index)
+            }
+            Edit::RemoveNode(index) => {
+                let connections = self
+                    .graph
+                    .edges_directed(index, Incoming)
+                    .chain(self.graph.edges_directed(index, Outgoing))
+                    .map(|edge| (edge.source(), edge.target(), *edge.weight()))
+                    .collect();
+                let embedded_asset =
+                    self.embedded_assets
+                        .iter_mut()
+                        .enumerate()
+                        .find_map(|(position, asset)| {
+                            let nodes = asset.graph_nodes_mut();
+                            let node = nodes.iter().position(|node| *node == index)?;
+                            nodes.remove(node);
+                            Some(position)
+                        });
+                let weight = self
+                    .graph
+                    .remove_node(index)
+                    .expect("Only existing nodes get removed");
+                Edit::InsertNode(Box::new(RemovedNode {
+                    index,
+                    weight,
+                    connections,
+                    embedded_asset,
+                }))
+            }
+            Edit::AddConnection(from, to, connection) => {
+                self.graph.add_edge(from, to, connection);
+                Edit::RemoveConnection(from, to, connection)
+            }
+            Edit::RemoveConnection(from, to, connection) => {
+                let edge = self
+                    .graph
+                    .edges_connecting(from, to)
+                    .find(|edge| {
+                        edge.weight().is_socket() == connection.is_socket()
+                            && edge.weight().source() == connection.source()
+                            && edge.weight().target() == connection.target()
+                    })
+                    .map(|edge| edge.id())
+                    .expect("Only existing connections get removed");
+                self.graph.remove_edge(edge);
+                Edit::AddConnection(from, to, connection)
+            }
+            Edit::ReplaceNode(index, node) => {
+                let previous = std::mem::replace(&mut self.graph[index].1, *node);
+                Edit::ReplaceNode(index, Box::new(previous))
+            }
+            Edit::SetPosition(index, position) => {
+                let previous = self.graph[index].0.get_position();
+                self.graph[index].0.set_position(position);
+                Edit::SetPosition(index, previous)
+            }
+            Edit::ReplaceScript(reference, script) => {
+                let previous = match script {
+                    Some(script) => self.property_scripts.insert(reference, script),
+                    None => self.property_scripts.remove(&reference),
+                };
+                Edit::ReplaceScript(reference, previous)
+            }
+            Edit::InsertEmbeddedAsset(position, asset) => {
+                self.embedded_assets.insert(position, *asset);
+                Edit::RemoveEmbeddedAsset(position)
+            }
+            Edit::RemoveEmbeddedAsset(position) => {
+                let asset = self.embedded_assets.remove(position);
+                Edit::InsertEmbeddedAsset(position, Box::new(asset))
+            }
+        })
+    }
+
+    /// Puts a removed node back at its previous index since the history refers to it by that.
+    /// The graph hands out vacant indices in the reverse order they were freed,
+    /// so placeholders take the ones in front of it and are removed again afterwards.
+    fn restore_node(
+        &mut self,
+        index: NodeIndex,
+        weight: (NodeMetadata, Nodes),
+    ) -> Result<(), HistoryError> {
+        if self.graph.contains_node(index) {
+            return Err(HistoryError::IndexTaken(index));
+        }
+        let mut placeholders = Vec::new();
+        loop {
+            let vacant = self.graph.add_node((NodeMetadata::empty(), Nodes::Expose));
+            if vacant == index {
+                self.graph[index] = weight;
+                break;
+            }
+            placeholders.push(vacant);
+        }
+        for placeholder in placeholders.into_iter().rev() {
+            self.graph.remove_node(placeholder);
+        }
+        Ok(())
+    }
+
+    /// Adds the restored node back to its embedded Asset. The Asset might have been resolved
+    /// again while the node was removed, so it takes the template of the other instances.
+    fn restore_embedded_instance(&mut self, position: usize, index: NodeIndex) {
+        let nodes = self.embedded_assets[position].graph_nodes_mut();
+        let other = nodes.first().copied();
+        nodes.push(index);
+        let template = other.and_then(|other| {
+            self.graph[other]
+                .1
+                .as_embedded_instance()?
+                .get_template()
+                .cloned()
+        });
+        if let Some(template) = template {
+            self.graph[index]
+                .1
+                .get_embedded_instance()
+                .set_template(template);
+        }
+    }
+
+    /// Bakes the Asset into its runtime form which drops all editor information.
+    /// The template is shared with the already created instances of this Asset.
+    pub fn to_runtime(&self) -> RuntimeAsset {
+        let template = self.get_template();
+        let embedded_assets = self
+            .embedded_assets
+            .iter()
+            .filter_map(|asset| asset.to_runtime(&template))
+            .collect();
+        RuntimeAsset::new(template, embedded_assets)
+    }
 }
 
 #[derive(Debug)]
@@ -260,7 +611,7 @@ impl AssetEditorMethods for EditableAsset {
         self.graph[node_index]
             .1
             .try_get_property(property)
-            .map_err(|err| TrivialError::NodeNotFound(node_index))
+            .map_err(|_| TrivialError::NodeNotFound(node_index))
     }
 
     fn try_set_node_property(
@@ -269,7 +620,17 @@ impl AssetEditorMethods for EditableAsset {
         property: &str,
         value: OwnedDataType,
     ) -> Result<(), NodeError> {
-        self.graph[node_index].1.try_set_property(property, value);
+        if !self.graph.contains_node(node_index) {
+            return Err(NodeError::NodeNotFound(node_index));
+        }
+        let previous = self.graph[node_index].1.clone();
+        if self.graph[node_index]
+            .1
+            .try_set_property(property, value)
+            .is_ok()
+        {
+            self.record(Edit::ReplaceNode(node_index, Box::new(previous)));
+        }
         Ok(())
     }
 
@@ -278,8 +639,10 @@ impl AssetEditorMethods for EditableAsset {
         node_index: NodeIndex,
         position: Vec2,
     ) -> Result<(), NodeError> {
-        //TODO error handling
-        self.graph[node_index].0.set_position(position);
+        if !self.graph.contains_node(node_index) {
+            return Err(NodeError::NodeNotFound(node_index));
+        }
+        self.execute(Edit::SetPosition(node_index, position));
         Ok(())
     }
 
@@ -336,12 +699,24 @@ pub enum ScriptingError {
     TODO,
 }
 
+/// The undo history no longer matches the graph.
+#[derive(Debug, thiserror::Error)]
+pub enum HistoryError {
+    #[error("The removed node {0:?} can't be restored since its index is taken")]
+    IndexTaken(NodeIndex),
+}
+
 //TODO make a better name for that
 #[derive(Debug)]
 pub enum NodeError {
     NodeNotFound(NodeIndex),
     NodeInputMissing(),
     ExternalReferenceMissing(),
+    PropertyNotFound(u8),
+    PropertyTypeMismatch(),
+    PropertyAlreadyExposed(u8),
+    PropertyNotExposable(u8),
+    UnknownAlias(String),
 }
 
 // This helper trait is responsible for all *non-trivial* operations that put Graph Validity at risk
@@ -366,6 +741,17 @@ pub trait NonTrivialEditorAction {
         to: NodeIndex,
         to_socket: u8,
     ) -> Result<(), NodeError>;
+
+    /// Connects a node property to the exposed property with the given index.
+    /// Using an index which is not yet in use exposes a new property on the Asset.
+    fn try_add_property_connection(
+        &mut self,
+        exposed: u8,
+        to: NodeIndex,
+        property: u8,
+    ) -> Result<(), NodeError>;
+
+    fn delete_property_connection(&mut self, to: NodeIndex, property: u8) -> Result<(), NodeError>;
 }
 
 impl NonTrivialEditorAction for EditableAsset {
@@ -377,17 +763,20 @@ impl NonTrivialEditorAction for EditableAsset {
         target: u8, //TODO maybe distinct type dont know yet how it plays out with parameter
     ) -> Result<(), NodeError> {
         //TODO validation
-        self.graph
-            .add_edge(from, to, Connection::Socket { source, target });
+        self.execute(Edit::AddConnection(
+            from,
+            to,
+            Connection::Socket { source, target },
+        ));
         Ok(())
     }
 
     fn delete_node(&mut self, index: NodeIndex) -> Result<(), NodeError> {
-        {
-            //TODO error handling
-            self.graph.remove_node(index);
-            Ok(())
+        if !self.graph.contains_node(index) {
+            return Err(NodeError::NodeNotFound(index));
         }
+        self.execute(Edit::RemoveNode(index));
+        Ok(())
     }
 
     fn delete_connection(
@@ -400,20 +789,83 @@ impl NonTrivialEditorAction for EditableAsset {
         use petgraph::{Direction::Incoming, visit::EdgeRef};
 
         let connection = self.graph.edges_directed(to, Incoming).find(|er| {
-            if er.weight().is_socket() {
-                er.weight().target() == to_socket
-            } else {
-                false
-            }
-            //&& er.weight().socket_from() == from_socket
+            er.weight().is_socket()
+                && er.source() == from
+                && er.weight().source() == from_socket
+                && er.weight().target() == to_socket
         });
         if let Some(edge) = connection {
-            if self.graph.remove_edge(edge.id()).is_some() {
-                return Ok(());
-            }
+            let edit = Edit::RemoveConnection(edge.source(), to, *edge.weight());
+            self.execute(edit);
+            return Ok(());
         }
         Err(NodeError::NodeNotFound(to))
     }
+
+    fn try_add_property_connection(
+        &mut self,
+        exposed: u8,
+        to: NodeIndex,
+        property: u8,
+    ) -> Result<(), NodeError> {
+        // The Expose node itself has no properties to connect to.
+        if to == NodeIndex::new(0) || !self.graph.contains_node(to) {
+            return Err(NodeError::NodeNotFound(to));
+        }
+        let properties = self.graph[to].1.get_properties();
+        let metadata = properties
+            .get(property as usize)
+            .ok_or(NodeError::PropertyNotFound(property))?;
+        if !PropertyInstance::supports(metadata.get_type()) {
+            return Err(NodeError::PropertyNotExposable(property));
+        }
+        // A node property can only be driven by one exposed property.
+        if self
+            .graph
+            .edges_directed(to, Incoming)
+            .any(|edge| edge.weight().is_property() && edge.weight().target() == property)
+        {
+            return Err(NodeError::PropertyAlreadyExposed(property));
+        }
+
+        // All targets of one exposed property share the same value so they must share the type.
+        let expose = NodeIndex::new(0);
+        if let Some(edge) = self
+            .graph
+            .edges_directed(expose, Outgoing)
+            .find(|edge| edge.weight().is_property() && edge.weight().source() == exposed)
+        {
+            let existing = self.graph[edge.target()]
+                .1
+                .try_get_property_metadata(edge.weight().target());
+            if existing.get_type() != metadata.get_type() {
+                return Err(NodeError::PropertyTypeMismatch());
+            }
+        }
+
+        self.execute(Edit::AddConnection(
+            expose,
+            to,
+            Connection::Property {
+                source: exposed,
+                target: property,
+            },
+        ));
+        Ok(())
+    }
+
+    fn delete_property_connection(&mut self, to: NodeIndex, property: u8) -> Result<(), NodeError> {
+        let connection = self
+            .graph
+            .edges_directed(to, Incoming)
+            .find(|er| er.weight().is_property() && er.weight().target() == property)
+            .map(|er| Edit::RemoveConnection(er.source(), to, *er.weight()));
+        if let Some(edit) = connection {
+            self.execute(edit);
+            return Ok(());
+        }
+        Err(NodeError::PropertyNotFound(property))
+    }
 }
 
 pub trait ScriptingInterface {
@@ -440,13 +892,11 @@ impl ScriptingInterface for EditableAsset {
             .try_get_property_metadata(property)
             .get_type();
         if let Ok(script) = Script::check(node, property, resolver_type, &script, self) {
-            self.property_scripts.insert(
-                Reference::Property {
-                    node,
-                    index: property,
-                },
-                script,
-            );
+            let reference = Reference::Property {
+                node,
+                index: property,
+            };
+            self.execute(Edit::ReplaceScript(reference, Some(script)));
             Ok(())
         } else {
             Err(ScriptingError::TODO)
@@ -499,7 +949,7 @@ impl AssetBase for EditableAsset {
 
     fn get_toposort(&self, filter_mask: &FixedBitSet) -> Vec<NodeIndex> {
         let filtered_graph =
-            NodeFiltered::from_fn(&self.graph, |f| filter_mask.contains(f.index() as usize));
+            NodeFiltered::from_fn(&self.graph, |f| filter_mask.contains(f.index()));
         toposort(&filtered_graph, None).unwrap()
     }
 
@@ -570,6 +1020,75 @@ impl AssetBase for EditableAsset {
         dependencies.into_boxed_slice()
     }
 
+    fn get_exposed_property_targets(&self) -> Box<[Box<[Reference]>]> {
+        // The Expose node is always the first node in the graph.
+        let mut targets = self
+            .graph
+            .edges_directed(NodeIndex::new(0), Outgoing)
+            .filter(|edge| edge.weight().is_property())
+            .map(|edge| {
+                let target = Reference::Property {
+                    node: edge.target(),
+                    index: edge.weight().target(),
+                };
+                (edge.weight().source(), target)
+            })
+            .collect::<Vec<_>>();
+        // Edges are stored in insertion order so sort them to get a deterministic result.
+        targets.sort_by_key(|(exposed, target)| (*exposed, target.node(), target.index()));
+
+        // The exposed index is kept even if the properties before it lost all their targets,
+        // otherwise the values stored for the later properties would shift.
+        let count = targets
+            .last()
+            .map_or(0, |(exposed, _)| *exposed as usize + 1);
+        let mut grouped = vec![Vec::new(); count];
+        for (exposed, target) in targets {
+            grouped[exposed as usize].push(target);
+        }
+        grouped.into_iter().map(Vec::into_boxed_slice).collect()
+    }
+
+    fn get_exposed_property_metadata(&self, targets: &[Reference]) -> PropertyMetadata {
+        // A property without targets drives nothing and only keeps the index in use.
+        let Some(first) = targets.first() else {
+            return PropertyMetadata {
+                name: "unused".into(),
+                default: OwnedDataType::Bool(false),
+                r#type: DataTypeKind::Bool,
+                configuration: None,
+                documentation: "Not connected to any node property.".into(),
+            };
+        };
+        let node = &self.graph[first.node()].1;
+        let mut metadata = node.try_get_property_metadata(first.index());
+        // Nodes only report their type defaults so prefer the value set in the Asset.
+        if let Ok(value) = node.try_get_property(&metadata.name) {
+            metadata.default = value.into();
+        }
+        metadata
+    }
+
+    fn nodes_reachable_from_respective_property(
+        &self,
+        exposed_targets: &[Box<[Reference]>],
+    ) -> Box<[FixedBitSet]> {
+        let node_bound = self.graph.node_bound();
+        exposed_targets
+            .iter()
+            .map(|targets| {
+                let mut bits = FixedBitSet::with_capacity(node_bound);
+                for target in targets {
+                    let mut bfs = Bfs::new(&self.graph, target.node());
+                    while let Some(nx) = bfs.next(&self.graph) {
+                        bits.insert(nx.index());
+                    }
+                }
+                bits
+            })
+            .collect()
+    }
+
     fn get_scripts(&self) -> impl Iterator<Item = (&Reference, &Script)> {
         self.property_scripts.iter()
     }
@@ -577,7 +1096,10 @@ impl AssetBase for EditableAsset {
     fn get_const_cache(
         &self,
         dynamic_nodes_without_outputs: &FixedBitSet,
-    ) -> HashMap<NodeIndex, Box<[OwnedDataType]>, FxBuildHasher> {
+    ) -> (
+        HashMap<NodeIndex, Box<[OwnedDataType]>, FxBuildHasher>,
+        FixedBitSet,
+    ) {
         let mut constant_cache: HashMap<NodeIndex, Box<[OwnedDataType]>, FxBuildHasher> =
             HashMap::with_capacity_and_hasher(
                 dynamic_nodes_without_outputs.count_zeroes(..),
@@ -588,17 +1110,31 @@ impl AssetBase for EditableAsset {
             !dynamic_nodes_without_outputs.contains(f.index()) && f != NodeIndex::new(0)
         });
         let mut graph_to_traverse = Topo::new(&const_graph);
+        let mut failed_nodes = FixedBitSet::with_capacity(self.graph.node_bound());
 
         while let Some(node) = graph_to_traverse.next(&const_graph) {
             let dependencies = self.get_node_dependencies(node);
+            let dependency_failed = dependencies.iter().any(|dependency| match dependency {
+                Reference::Standard { node, .. } => failed_nodes.contains(node.index()),
+                _ => false,
+            });
+            if dependency_failed {
+                failed_nodes.insert(node.index());
+                continue;
+            }
 
-            let computed = self.graph[node]
+            match self.graph[node]
                 .1
-                .compute(Some(&dependencies), &constant_cache);
-
-            constant_cache.insert(node, computed.into_iter().flatten().collect());
+                .compute(Some(&dependencies), &constant_cache)
+            {
+                Ok(computed) => {
+                    constant_cache.insert(node, computed.into_iter().flatten().collect());
+                }
+                // The instances recompute the node to report the error.
+                Err(_) => failed_nodes.insert(node.index()),
+            }
         }
-        constant_cache
+        (constant_cache, failed_nodes)
     }
 
     fn get_outputs_and_info(
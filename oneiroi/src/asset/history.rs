@@ -0,0 +1,112 @@
+use glam::Vec2;
+
+use super::{Connection, EmbeddedAsset, NodeIndex, NodeMetadata};
+use crate::nodes::Nodes;
+use crate::property::script::Script;
+use crate::type_system::Reference;
+
+/// A primitive change of an EditableAsset.
+/// Applying an Edit yields the Edit which reverts it, so every change records its own inverse.
+#[derive(Debug, Clone)]
+pub(super) enum Edit {
+    InsertNode(Box<RemovedNode>),
+    RemoveNode(NodeIndex),
+    AddConnection(NodeIndex, NodeIndex, Connection),
+    RemoveConnection(NodeIndex, NodeIndex, Connection),
+    ReplaceNode(NodeIndex, Box<Nodes>),
+    SetPosition(NodeIndex, Vec2),
+    ReplaceScript(Reference, Option<Script>),
+    InsertEmbeddedAsset(usize, Box<EmbeddedAsset>),
+    RemoveEmbeddedAsset(usize),
+}
+
+/// Everything needed to put a removed node back into the graph.
+#[derive(Debug, Clone)]
+pub(super) struct RemovedNode {
+    pub(super) index: NodeIndex,
+    pub(super) weight: (NodeMetadata, Nodes),
+    pub(super) connections: Vec<(NodeIndex, NodeIndex, Connection)>,
+    // The embedded Asset entry the node was an instance of.
+    pub(super) embedded_asset: Option<usize>,
+}
+
+/// The undo and redo stacks of an EditableAsset.
+/// Each entry holds the inverse Edits of one user action in the order they were done.
+#[derive(Debug, Clone, Default)]
+pub(super) struct History {
+    undo: Vec<Vec<Edit>>,
+    redo: Vec<Vec<Edit>>,
+
+    // The nesting depth of the open group and the inverse Edits recorded so far.
+    group: Option<(usize, Vec<Edit>)>,
+}
+
+impl History {
+    /// Records the inverse of a new change which makes the redo stack obsolete.
+    pub(super) fn record(&mut self, inverse: Edit) {
+        self.redo.clear();
+        match &mut self.group {
+            Some((_, edits)) => edits.push(inverse),
+            None => self.undo.push(vec![inverse]),
+        }
+    }
+
+    /// Groups can be nested, only closing the outermost one creates an undo step.
+    pub(super) fn begin_group(&mut self) {
+        match &mut self.group {
+            Some((depth, _)) => *depth += 1,
+            None => self.group = Some((1, Vec::new())),
+        }
+    }
+
+    pub(super) fn end_group(&mut self) {
+        if let Some((depth, _)) = &mut self.group {
+            *depth -= 1;
+            if *depth == 0 {
+                self.close_group();
+            }
+        }
+    }
+
+    /// Ends the open group regardless of its nesting depth.
+    pub(super) fn close_group(&mut self) {
+        if let Some((_, edits)) = self.group.take()
+            && !edits.is_empty()
+        {
+            self.undo.push(edits);
+        }
+    }
+
+    pub(super) fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+            || self
+                .group
+                .as_ref()
+                .is_some_and(|(_, edits)| !edits.is_empty())
+    }
+
+    pub(super) fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub(super) fn pop_undo(&mut self) -> Option<Vec<Edit>> {
+        self.close_group();
+        self.undo.pop()
+    }
+
+    pub(super) fn pop_redo(&mut self) -> Option<Vec<Edit>> {
+        self.redo.pop()
+    }
+
+    pub(super) fn push_undo(&mut self, edits: Vec<Edit>) {
+        self.undo.push(edits);
+    }
+
+    pub(super) fn push_redo(&mut self, edits: Vec<Edit>) {
+        self.redo.push(edits);
+    }
+
+    pub(super) fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
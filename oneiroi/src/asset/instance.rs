@@ -1,4 +1,4 @@
-use std::{collections::HashMap, sync::Arc, time::Instant};
+use std::{cell::RefCell, collections::HashMap, sync::Arc, time::Instant};
 
 use fixedbitset::FixedBitSet;
 
@@ -6,9 +6,9 @@ use rustc_hash::FxBuildHasher;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    asset::template::AssetTemplate,
+    asset::{NodeIndex, template::AssetTemplate},
     nodes::{
-        ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
         SocketInterface, StaticNodeMetadata,
     },
     property::{PropertyInstance, PropertyMetadata},
@@ -20,6 +20,8 @@ struct ContextBridge<'a> {
     this: &'a dyn ContextProvider,
     node_cache: HashMap<Reference, OwnedDataType, FxBuildHasher>,
     parent_references: Box<[Reference]>,
+    // All nodes which get recomputed during this computation.
+    recomputed_nodes: FixedBitSet,
 }
 
 impl ContextProvider for ContextBridge<'_> {
@@ -41,6 +43,16 @@ impl ContextProvider for ContextBridge<'_> {
             Reference::Property { .. } => todo!(),
         }
     }
+
+    fn reference_changed(&self, index: Reference) -> bool {
+        match index {
+            Reference::Standard { node, .. } => self.recomputed_nodes.contains(node.index()),
+            Reference::External { socket } => self
+                .parent
+                .reference_changed(self.parent_references[socket as usize]),
+            _ => true,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -48,6 +60,20 @@ pub struct AssetInstance {
     // All the exposed properties from the Asset.
     properties: Box<[PropertyInstance]>,
 
+    // The results of all nodes computed by this instance are kept until they get dirty.
+    #[serde(skip)]
+    node_cache: RefCell<HashMap<Reference, OwnedDataType, FxBuildHasher>>,
+    // All nodes which need to be recomputed on the next computation.
+    #[serde(skip)]
+    dirty_nodes: RefCell<FixedBitSet>,
+    // For each output whether it changed during the last computation.
+    // Stays empty until the first computation happened.
+    #[serde(skip)]
+    changed_outputs: RefCell<Box<[bool]>>,
+    // Embedded Assets cache their results too so each instance needs its own copy of them.
+    #[serde(skip)]
+    embedded_instances: HashMap<NodeIndex, AssetInstance, FxBuildHasher>,
+
     //This is theoretically not optional but rather be injected on deserialization
     #[serde(skip)]
     template: Option<Arc<AssetTemplate>>,
@@ -57,6 +83,10 @@ impl Clone for AssetInstance {
     fn clone(&self) -> Self {
         Self {
             properties: self.properties.clone(),
+            node_cache: self.node_cache.clone(),
+            dirty_nodes: self.dirty_nodes.clone(),
+            changed_outputs: self.changed_outputs.clone(),
+            embedded_instances: self.embedded_instances.clone(),
             //graph: self.graph.clone(),
             // dynamic_nodes: self.dynamic_nodes.clone(),
             //output_nodes: self.output_nodes.clone(),
@@ -104,8 +134,16 @@ impl AssetInstance {
 
         let properties = unsafe { properties.assume_init() };
 
+        // Properties start out with the defaults which are already part of the constant cache.
+        let dirty_nodes = template.get_input_driven_nodes();
+        let embedded_instances = Self::copy_embedded_instances(&template);
+
         Self {
             properties,
+            node_cache: Default::default(),
+            dirty_nodes: RefCell::new(dirty_nodes),
+            changed_outputs: Default::default(),
+            embedded_instances,
             //output_sockets: output_sockets.iter().map(|s| SocketInstance {}).collect(),
             //asset_index: 0,
             //graph: dynamic_graph,
@@ -120,9 +158,40 @@ impl AssetInstance {
     }
 
     pub(super) fn set_template(&mut self, template: Arc<AssetTemplate>) {
+        // Deserialized properties might differ from the defaults so conservatively mark them all.
+        let dirty_nodes =
+            &template.get_input_driven_nodes() | &template.get_property_driven_nodes();
+
+        self.node_cache.get_mut().clear();
+        *self.dirty_nodes.get_mut() = dirty_nodes;
+        *self.changed_outputs.get_mut() = Default::default();
+        self.embedded_instances = Self::copy_embedded_instances(&template);
         self.template = Some(template)
     }
 
+    pub(crate) fn has_template(&self) -> bool {
+        self.template.is_some()
+    }
+
+    pub(super) fn get_template(&self) -> Option<&Arc<AssetTemplate>> {
+        self.template.as_ref()
+    }
+
+    fn copy_embedded_instances(
+        template: &AssetTemplate,
+    ) -> HashMap<NodeIndex, AssetInstance, FxBuildHasher> {
+        template
+            .get_embedded_instances()
+            .map(|(index, instance)| (index, instance.clone()))
+            .collect()
+    }
+
+    /// Reports for each output whether it changed during the last computation.
+    /// Integrations can use this to skip the conversion of unchanged outputs.
+    pub fn get_changed_outputs(&self) -> Box<[bool]> {
+        self.changed_outputs.borrow().clone()
+    }
+
     //fn update_properties(&mut self, property_name: &str, value: &DataTypeInstance) {
     /* let starting_node = self
         .template
@@ -191,7 +260,6 @@ impl PropertyInterface for AssetInstance {
                         )
                     }); */
 
-        println!("Property: {property}, to: {value:?}");
         if let Some(index) = self
             .template
             .as_ref()
@@ -200,7 +268,6 @@ impl PropertyInterface for AssetInstance {
             .iter()
             .position(|i| i.name() == property)
         {
-            println!("IT FOUND THE POSITION OF THE PROPERTY {property}");
             self.try_set_property_index(index as u8, value)
         } else {
             Err(SetPropertyError::NotFound)
@@ -232,11 +299,21 @@ impl PropertyInterface for AssetInstance {
         index: u8,
         value: OwnedDataType,
     ) -> Result<(), SetPropertyError> {
-        //TODO proper error handling
-        self.properties
+        let property = self
+            .properties
             .get_mut(index as usize)
+            .ok_or(SetPropertyError::WrongIndex)?;
+        if property.get_type() != value.get_data_type() {
+            return Err(SetPropertyError::WrongType);
+        }
+        property.set_value(value);
+
+        // Only the nodes downstream of the property need to be recomputed.
+        *self.dirty_nodes.get_mut() |= self
+            .template
+            .as_ref()
             .unwrap()
-            .set_value(value);
+            .get_property_connectivity(index);
         Ok(())
     }
 
@@ -268,22 +345,33 @@ impl Node for AssetInstance {
         context: &impl ContextProvider,
         //TODO explore this
         //write_back: Box<[&mut DataTypeValue]>,
-    ) -> Box<[Option<OwnedDataType>]> {
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
         let computation_instant = Instant::now();
 
         //Properly borrow recurring uses only one time instead of each call.
         let template = self.template.as_deref().unwrap();
-        // let mut node_cache = self.node_cache.borrow_mut();
-
-        //TODO Update all properties that have changed since the last computation
-        //template.
+        let input_sockets = input_sockets.unwrap();
+
+        // Only inputs which changed since the last computation need to be propagated.
+        let changed_inputs = (0..template.input_count())
+            .map(|socket| {
+                input_sockets
+                    .get(socket)
+                    .is_none_or(|reference| context.reference_changed(*reference))
+            })
+            .collect::<Box<[bool]>>();
+        let nodes_to_compute = template.query(&changed_inputs, self.dirty_nodes.take());
 
+        // The cache is moved into the bridge for the duration of the computation.
         let mut context = ContextBridge {
             parent: context,
             this: self,
-            node_cache: Default::default(),
-            parent_references: input_sockets.unwrap().into(),
+            node_cache: self.node_cache.take(),
+            parent_references: input_sockets.into(),
+            recomputed_nodes: nodes_to_compute.iter().map(|node| node.index()).collect(),
         };
+        // The replaced output values tell whether a recomputed output actually changed.
+        let mut previous_outputs = HashMap::<_, _, FxBuildHasher>::default();
 
         //Map the input References received from the outside to local References
         //It needs to be cached to be accessed by nodes calling get_reference()
@@ -300,22 +388,34 @@ impl Node for AssetInstance {
             }
         } */
 
-        //TODO receive which references have effectively changed
-        //TODO replace with changed slice
-        let baked_input: Box<[bool]> = Box::new([true]);
-        let input: &[bool] = &baked_input;
-
-        let nodes_to_compute = template.query(input, FixedBitSet::new());
-        for node in nodes_to_compute {
-            let computed = template.compute(node, &context);
+        for node in nodes_to_compute.iter().copied() {
+            let computed = match self.embedded_instances.get(&node) {
+                Some(instance) => {
+                    instance.compute(Some(template.get_node_dependencies(node)), &context)
+                }
+                None => template.compute(node, &context),
+            };
+            let computed = match computed {
+                Ok(computed) => computed,
+                Err(error) => {
+                    // Keep what was computed so far and retry the whole set on the next computation.
+                    *self.node_cache.borrow_mut() = context.node_cache;
+                    self.dirty_nodes
+                        .borrow_mut()
+                        .extend(nodes_to_compute.iter().map(|node| node.index()));
+                    return Err(error);
+                }
+            };
             for (index, value) in computed.into_iter().enumerate() {
-                context.node_cache.insert(
-                    Reference::Standard {
-                        node,
-                        socket: index as u8,
-                    },
-                    value.unwrap(),
-                );
+                let reference = Reference::Standard {
+                    node,
+                    socket: index as u8,
+                };
+                if let Some(previous) = context.node_cache.insert(reference, value.unwrap())
+                    && template.outputs().contains(&reference)
+                {
+                    previous_outputs.insert(reference, previous);
+                }
             }
         }
 
@@ -369,17 +469,37 @@ impl Node for AssetInstance {
         /*  println!("{:#?}", context.node_cache);
         println!("{:#?}", template.outputs().collect::<Vec<_>>()); */
 
-        template
+        // Before the first computation every output counts as changed.
+        let first_computation = self.changed_outputs.borrow().is_empty();
+        *self.changed_outputs.borrow_mut() = template
             .outputs()
             .iter()
             .map(|out| {
-                if let Some(value) = context.node_cache.remove(out) {
-                    Some(value)
+                first_computation
+                    || context.reference_changed(*out)
+                        && match out {
+                            Reference::Standard { .. } => {
+                                previous_outputs.get(out) != context.node_cache.get(out)
+                            }
+                            _ => true,
+                        }
+            })
+            .collect();
+
+        let outputs = template
+            .outputs()
+            .iter()
+            .map(|out| {
+                if let Some(value) = context.node_cache.get(out) {
+                    Some(value.clone())
                 } else {
                     Some(template.get_reference(*out).into())
                 }
             })
-            .collect::<Box<_>>()
+            .collect::<Box<_>>();
+
+        *self.node_cache.borrow_mut() = context.node_cache;
+        Ok(outputs)
     }
 
     fn node_metadata(&self) -> StaticNodeMetadata {
@@ -1,9 +1,167 @@
-use petgraph::{Directed, Graph};
-use serde::{Deserialize, Serialize};
-
-//TODO the runtime is for the player and should always be used when running the game
-#[derive(Debug, Serialize, Deserialize)]
-pub(crate) struct RuntimeAsset {
-    //graph: Graph<, (), Directed, u16>,
-    todo: i64,
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use fixedbitset::FixedBitSet;
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::property::PropertyMetadata;
+use crate::type_system::Reference;
+
+use super::{
+    Asset, AssetBase, AssetReference, AssetTemplate, EmbeddedAsset, NodeIndex,
+    instance::AssetInstance,
+    server::{AssetServer, ResolveError},
+};
+
+/// The stripped down form of an Asset which should always be used when running the game.
+/// It holds no editor information like names, positions or the node graph itself
+/// but only the baked template, so instances can be created without traversing a graph.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(from = "RuntimeAssetDeserializeProxy")]
+pub struct RuntimeAsset {
+    template: Arc<AssetTemplate>,
+
+    // Only the embedded assets which are required by dynamic nodes are kept.
+    embedded_assets: Arc<[EmbeddedAsset]>,
+}
+
+#[derive(Deserialize)]
+struct RuntimeAssetDeserializeProxy {
+    template: AssetTemplate,
+    embedded_assets: Vec<EmbeddedAsset>,
+}
+
+impl From<RuntimeAssetDeserializeProxy> for RuntimeAsset {
+    fn from(mut value: RuntimeAssetDeserializeProxy) -> Self {
+        // The templates of embedded instances are skipped on serialization so inject them again.
+        // Assets which embed external Assets get their templates from the AssetServer.
+        for asset in &value.embedded_assets {
+            let Some(template) = asset.get_template() else {
+                continue;
+            };
+            for node in asset.graph_nodes() {
+                value.template.set_embedded_template(node, template.clone());
+            }
+        }
+
+        RuntimeAsset {
+            template: Arc::new(value.template),
+            embedded_assets: value.embedded_assets.into(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct RuntimeAssetSerializeProxy<'a> {
+    template: &'a AssetTemplate,
+    embedded_assets: &'a [EmbeddedAsset],
+}
+
+impl Serialize for RuntimeAsset {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RuntimeAssetSerializeProxy {
+            template: &self.template,
+            embedded_assets: &self.embedded_assets,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl RuntimeAsset {
+    pub(super) fn new(template: Arc<AssetTemplate>, embedded_assets: Vec<EmbeddedAsset>) -> Self {
+        Self {
+            template,
+            embedded_assets: embedded_assets.into(),
+        }
+    }
+
+    pub fn get_instance(&self) -> AssetInstance {
+        AssetInstance::new(&self.template)
+    }
+
+    pub(super) fn get_template(&self) -> Arc<AssetTemplate> {
+        self.template.clone()
+    }
+
+    pub(super) fn is_resolved(&self) -> bool {
+        self.embedded_assets
+            .iter()
+            .flat_map(|asset| asset.graph_nodes())
+            .all(|node| self.template.is_embedded_template_set(node))
+    }
+
+    pub(super) fn get_embedded_assets(&self) -> &[EmbeddedAsset] {
+        &self.embedded_assets
+    }
+
+    /// Resolves all embedded Assets recursively and injects their templates into the nodes.
+    pub(super) fn resolve_embedded(
+        &mut self,
+        server: &mut AssetServer,
+        stack: &mut Vec<AssetReference>,
+    ) -> Result<(), ResolveError> {
+        let template = Arc::make_mut(&mut self.template);
+        for asset in Arc::make_mut(&mut self.embedded_assets) {
+            let embedded_template = match asset {
+                EmbeddedAsset::Internal(asset, _) => {
+                    server.resolve_nested(asset, stack)?;
+                    asset.get_template()
+                }
+                EmbeddedAsset::External(reference, _) => server.load_template(reference, stack)?,
+            };
+            for node in asset.graph_nodes() {
+                template.set_embedded_template(node, embedded_template.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Inlines all external Assets in their runtime form.
+    pub(super) fn bundle_embedded(
+        &mut self,
+        server: &mut AssetServer,
+        stack: &mut Vec<AssetReference>,
+        bundled: &mut HashMap<String, Asset>,
+    ) -> Result<(), ResolveError> {
+        self.embedded_assets = server
+            .bundle_embedded_assets(&self.embedded_assets, stack, bundled)?
+            .into_iter()
+            .map(|asset| match asset {
+                EmbeddedAsset::Internal(Asset::Editable(asset), nodes) => {
+                    EmbeddedAsset::Internal(Asset::Runtime(asset.to_runtime()), nodes)
+                }
+                asset => asset,
+            })
+            .collect();
+        Ok(())
+    }
+
+    pub(super) fn get_node_dependencies(&self, index: NodeIndex) -> Box<[Reference]> {
+        self.template.get_node_dependencies(index).into()
+    }
+
+    pub(super) fn get_exposed_property_targets(&self) -> Box<[Box<[Reference]>]> {
+        self.template.get_exposed_targets().into()
+    }
+
+    pub(super) fn get_exposed_property_metadata(&self, targets: &[Reference]) -> PropertyMetadata {
+        let index = self
+            .template
+            .get_exposed_index(targets)
+            .expect("The targets have to be exposed in the Asset");
+        self.template.get_properties()[index].clone()
+    }
+
+    pub(super) fn nodes_reachable_from_respective_property(
+        &self,
+        exposed_targets: &[Box<[Reference]>],
+    ) -> Box<[FixedBitSet]> {
+        exposed_targets
+            .iter()
+            .map(|targets| match self.template.get_exposed_index(targets) {
+                Some(index) => self.template.get_property_connectivity(index as u8).clone(),
+                None => FixedBitSet::new(),
+            })
+            .collect()
+    }
 }
@@ -1,4 +1,228 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
+use crate::serialization::migration::MigrationRegistry;
+use crate::serialization::{
+    AssetLoadError, deserialize_asset_binary_migrated, deserialize_asset_migrated, is_binary_asset,
+};
 
+use super::{Asset, AssetBase, AssetReference, AssetTemplate, EmbeddedAsset};
 
-struct AssetServer {}
\ No newline at end of file
+#[derive(Debug, thiserror::Error)]
+pub enum ResolveError {
+    /// The resolver couldn't find a file for the reference.
+    #[error("The referenced Asset {path} can't be found", path = .0.get_path())]
+    NotFound(AssetReference),
+    /// The referenced file doesn't contain a valid Asset.
+    #[error("The referenced Asset {path} is invalid: {1}", path = .0.get_path())]
+    Invalid(AssetReference, #[source] AssetLoadError),
+    /// The chain of references which leads back to an Asset that is already being resolved.
+    #[error(
+        "The referenced Assets form a cycle: {}",
+        .0.iter().map(AssetReference::get_path).collect::<Vec<_>>().join(" -> ")
+    )]
+    Cycle(Vec<AssetReference>),
+}
+
+/// Provides the contents of referenced Asset files.
+/// Integrations implement this on top of their own file system or resource database
+/// and are free to look up the reference by its path or its uid.
+/// The content is returned as is, both json and binary Assets are detected by the server.
+pub trait AssetResolver {
+    fn load(&self, reference: &AssetReference) -> Result<Vec<u8>, ResolveError>;
+}
+
+/// Loads externally referenced Assets and shares one template per referenced file
+/// between all Assets which embed it.
+pub struct AssetServer {
+    resolver: Box<dyn AssetResolver>,
+    migrations: MigrationRegistry,
+
+    templates: HashMap<String, (AssetReference, Arc<AssetTemplate>)>,
+    // For each referenced file all files which directly embed it.
+    dependents: HashMap<String, HashSet<String>>,
+}
+
+impl AssetServer {
+    pub fn new(resolver: impl AssetResolver + 'static) -> Self {
+        Self {
+            resolver: Box::new(resolver),
+            migrations: MigrationRegistry::default(),
+            templates: Default::default(),
+            dependents: Default::default(),
+        }
+    }
+
+    /// Injects the templates of all externally referenced Assets into the Asset.
+    /// This has to happen after deserialization before instances of the Asset can be computed.
+    pub fn resolve(&mut self, asset: &mut Asset) -> Result<(), ResolveError> {
+        self.resolve_nested(asset, &mut Vec::new())
+    }
+
+    /// Drops the cached template of a changed file together with all files depending on it.
+    /// Returns every invalidated reference so the Assets embedding them can be resolved again.
+    pub fn invalidate(&mut self, reference: &AssetReference) -> Box<[AssetReference]> {
+        let mut invalidated = Vec::new();
+        let mut pending = vec![reference.key().to_string()];
+        let mut visited = HashSet::new();
+
+        while let Some(key) = pending.pop() {
+            if !visited.insert(key.clone()) {
+                continue;
+            }
+            if let Some((reference, _)) = self.templates.remove(&key) {
+                invalidated.push(reference);
+            }
+            if let Some(dependents) = self.dependents.remove(&key) {
+                pending.extend(dependents);
+            }
+        }
+        invalidated.into_boxed_slice()
+    }
+
+    pub(super) fn resolve_nested(
+        &mut self,
+        asset: &mut Asset,
+        stack: &mut Vec<AssetReference>,
+    ) -> Result<(), ResolveError> {
+        match asset {
+            Asset::Editable(editable_asset) => editable_asset.resolve_embedded(self, stack),
+            Asset::Runtime(runtime_asset) => runtime_asset.resolve_embedded(self, stack),
+        }
+    }
+
+    /// Copies the Asset with every external Asset inlined recursively
+    /// so the result no longer depends on any other file.
+    pub fn bundle(&mut self, asset: &Asset) -> Result<Asset, ResolveError> {
+        let mut asset = asset.clone();
+        self.bundle_nested(&mut asset, &mut Vec::new(), &mut HashMap::new())?;
+        Ok(asset)
+    }
+
+    pub(super) fn bundle_nested(
+        &mut self,
+        asset: &mut Asset,
+        stack: &mut Vec<AssetReference>,
+        bundled: &mut HashMap<String, Asset>,
+    ) -> Result<(), ResolveError> {
+        match asset {
+            Asset::Editable(editable_asset) => editable_asset.bundle_embedded(self, stack, bundled),
+            Asset::Runtime(runtime_asset) => runtime_asset.bundle_embedded(self, stack, bundled),
+        }
+    }
+
+    /// Replaces all external Assets with their bundled content.
+    /// Entries referencing the same file are merged into a single internal Asset.
+    pub(super) fn bundle_embedded_assets(
+        &mut self,
+        assets: &[EmbeddedAsset],
+        stack: &mut Vec<AssetReference>,
+        bundled: &mut HashMap<String, Asset>,
+    ) -> Result<Vec<EmbeddedAsset>, ResolveError> {
+        let mut result = Vec::with_capacity(assets.len());
+        let mut inlined = HashMap::<&str, usize>::new();
+
+        for embedded_asset in assets {
+            match embedded_asset {
+                EmbeddedAsset::Internal(asset, nodes) => {
+                    let mut asset = asset.clone();
+                    self.bundle_nested(&mut asset, stack, bundled)?;
+                    result.push(EmbeddedAsset::Internal(asset, nodes.clone()));
+                }
+                EmbeddedAsset::External(reference, nodes) => {
+                    if let Some(&index) = inlined.get(reference.key()) {
+                        if let EmbeddedAsset::Internal(_, existing) = &mut result[index] {
+                            existing.extend(nodes);
+                        }
+                        continue;
+                    }
+                    let asset = self.load_bundled(reference, stack, bundled)?;
+                    inlined.insert(reference.key(), result.len());
+                    result.push(EmbeddedAsset::Internal(asset, nodes.clone()));
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Loads the referenced file with all of its dependencies inlined.
+    /// Files embedded multiple times are only bundled once.
+    fn load_bundled(
+        &mut self,
+        reference: &AssetReference,
+        stack: &mut Vec<AssetReference>,
+        bundled: &mut HashMap<String, Asset>,
+    ) -> Result<Asset, ResolveError> {
+        check_cycle(reference, stack)?;
+        if let Some(asset) = bundled.get(reference.key()) {
+            return Ok(asset.clone());
+        }
+
+        let mut asset = self.load_asset(reference, stack)?;
+        stack.push(reference.clone());
+        let result = self.bundle_nested(&mut asset, stack, bundled);
+        stack.pop();
+        result?;
+
+        bundled.insert(reference.key().to_string(), asset.clone());
+        Ok(asset)
+    }
+
+    /// Loads the template of the referenced file or reuses the one already loaded.
+    /// The stack holds the chain of files currently being resolved to detect cycles.
+    pub(crate) fn load_template(
+        &mut self,
+        reference: &AssetReference,
+        stack: &mut Vec<AssetReference>,
+    ) -> Result<Arc<AssetTemplate>, ResolveError> {
+        check_cycle(reference, stack)?;
+
+        let key = reference.key();
+        if let Some(parent) = stack.last() {
+            self.dependents
+                .entry(key.to_string())
+                .or_default()
+                .insert(parent.key().to_string());
+        }
+        if let Some((_, template)) = self.templates.get(key) {
+            return Ok(template.clone());
+        }
+
+        let template = self.load_asset(reference, stack)?.get_template();
+        self.templates
+            .insert(key.to_string(), (reference.clone(), template.clone()));
+        Ok(template)
+    }
+
+    /// Reads and resolves the referenced file.
+    fn load_asset(
+        &mut self,
+        reference: &AssetReference,
+        stack: &mut Vec<AssetReference>,
+    ) -> Result<Asset, ResolveError> {
+        let source = self.resolver.load(reference)?;
+        let loaded = if is_binary_asset(&source) {
+            deserialize_asset_binary_migrated(&source, &self.migrations)
+        } else {
+            String::from_utf8(source)
+                .map_err(AssetLoadError::Encoding)
+                .and_then(|source| deserialize_asset_migrated(&source, &self.migrations))
+        };
+        let (mut asset, _) = loaded.map_err(|err| ResolveError::Invalid(reference.clone(), err))?;
+
+        stack.push(reference.clone());
+        let resolved = self.resolve_nested(&mut asset, stack);
+        stack.pop();
+        resolved?;
+        Ok(asset)
+    }
+}
+
+fn check_cycle(reference: &AssetReference, stack: &[AssetReference]) -> Result<(), ResolveError> {
+    if stack.iter().any(|parent| parent.key() == reference.key()) {
+        let mut cycle = stack.to_vec();
+        cycle.push(reference.clone());
+        return Err(ResolveError::Cycle(cycle));
+    }
+    Ok(())
+}
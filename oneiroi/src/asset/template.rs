@@ -1,36 +1,52 @@
-use std::{collections::HashMap, time::Instant};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Instant,
+};
 
 use fixedbitset::FixedBitSet;
 
 use rustc_hash::FxBuildHasher;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    nodes::{ContextProvider, Node, Nodes, PropertyInterface},
+    nodes::{ComputeError, ContextProvider, Node, Nodes, PropertyInterface},
     property::PropertyMetadata,
-    type_system::{
-        OwnedDataType, Reference, TypeRef,
-        data_types::{DataTypeKind, TypeDescriptor},
-    },
+    type_system::{OwnedDataType, Reference, TypeRef, data_types::TypeDescriptor},
 };
 
-use super::{AssetBase, NodeIndex};
+use super::{AssetBase, NodeIndex, instance::AssetInstance};
 
 /// Holds all shared state between instances of a particular Asset.
 /// This allows multiple instances to be created much faster than without it.
-#[derive(Debug)]
+/// Since it contains no editor information it is also what a RuntimeAsset persists.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct AssetTemplate {
     // All nodes which are dynamic have their Dependencies and configuraion baked here.
+    #[serde(with = "crate::serialization::map_entries")]
     dependency_node_map: HashMap<NodeIndex, (Box<[Reference]>, Nodes), FxBuildHasher>,
     // For each node index all other changing nodes are cached here.
+    #[serde(with = "bitset_indices")]
     node_connectivity: Box<[FixedBitSet]>,
 
     // The Topological order of the graph to apply the node_connectivity to.
     topo_order: Vec<NodeIndex>,
 
     //All evaluations of constant nodes get cached here.
+    #[serde(with = "crate::serialization::map_entries")]
     constant_cache: HashMap<NodeIndex, Box<[OwnedDataType]>, FxBuildHasher>,
+    // Constant nodes which failed to evaluate and all nodes depending on them.
+    // They are computed by every instance on every computation so it can report the error.
+    #[serde(default)]
+    failed_nodes: Vec<NodeIndex>,
 
     exposed_properties: Box<[PropertyMetadata]>,
+    // For each exposed property the node properties it drives.
+    #[serde(default)]
+    exposed_targets: Box<[Box<[Reference]>]>,
+    // For each exposed property all nodes which need to recompute once it changes.
+    #[serde(with = "bitset_indices")]
+    property_connectivity: Box<[FixedBitSet]>,
 
     // All relevant information for inputs and outputs required by each instance.
     outputs: Box<[Reference]>,
@@ -38,6 +54,27 @@ pub(crate) struct AssetTemplate {
     input_infos: Option<Box<[TypeDescriptor]>>,
 }
 
+/// The serde implementation of FixedBitSet requires borrowed bytes which Json cannot provide.
+/// Therefore each set is stored as the list of its contained indices instead.
+mod bitset_indices {
+    use fixedbitset::FixedBitSet;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(
+        sets: &[FixedBitSet],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(sets.iter().map(|set| set.ones().collect::<Vec<_>>()))
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Box<[FixedBitSet]>, D::Error> {
+        let sets = Vec::<Vec<usize>>::deserialize(deserializer)?;
+        Ok(sets.into_iter().map(FixedBitSet::from_iter).collect())
+    }
+}
+
 impl AssetTemplate {
     /// This is the way in which different AssetBases get transformed into a template.
     /// This method is called internally when a template gets requested by a AssetProcessor.
@@ -52,47 +89,31 @@ impl AssetTemplate {
         }
         let is_node_dynamic = is_node_dynamic;
 
-        //TODO revive the property stuff
-        /*  for node in exposed_nodes {
-            let exposed_connection_properties = graph
-                .edges_directed(node, Outgoing)
-                .filter(|e| e.weight().is_exposed())
-                .map(|e| e.weight().get_target_property())
-                .collect::<Vec<_>>();
-
-            let mut outgoing_node_edges = graph_to_traverse
-                .edges_directed(node, Outgoing)
-                .filter(|e| e.weight().is_exposed());
-            for connection in exposed_connection_properties {
-                if let Some(edge) =
-                    outgoing_node_edges.find(|e| e.weight().get_target_property() == connection)
-                {
-                    node_tracker.insert(edge.target(), NodeInfo::Dynamic);
-                }
-            }
-        } */
-
         for (prop_ref, script) in base.get_scripts() {}
 
-        let mut exposed_properties = Box::new_uninit_slice(2);
-
-        exposed_properties[0].write(PropertyMetadata {
-            name: "distance".into(),
-            r#type: DataTypeKind::Float,
-            default: OwnedDataType::Float(0.5),
-            configuration: None,
-            documentation: "".into(),
-        });
-
-        exposed_properties[1].write(PropertyMetadata {
-            name: "height".into(),
-            r#type: DataTypeKind::Float,
-            default: OwnedDataType::Float(2.),
-            configuration: None,
-            documentation: "".into(),
-        });
-
-        let exposed_properties = unsafe { exposed_properties.assume_init() };
+        // Every exposed property drives the nodes it is connected to and all nodes downstream.
+        let exposed_targets = base.get_exposed_property_targets();
+        let mut exposed_properties = exposed_targets
+            .iter()
+            .map(|targets| base.get_exposed_property_metadata(targets))
+            .collect::<Box<_>>();
+        // Exposed properties are named after the first node property they drive, which other
+        // exposed properties might share, so later ones get a numbered suffix.
+        let mut names = HashSet::new();
+        for property in &mut exposed_properties {
+            let mut name = property.name.clone();
+            let mut suffix = 1;
+            while !names.insert(name.clone()) {
+                suffix += 1;
+                name = format!("{}_{suffix}", property.name);
+            }
+            property.name = name;
+        }
+        let property_connectivity = base.nodes_reachable_from_respective_property(&exposed_targets);
+        let mut is_node_property_driven = FixedBitSet::new();
+        for reachable_nodes in &property_connectivity {
+            is_node_property_driven |= reachable_nodes;
+        }
 
         // Evaluation of nodes
 
@@ -102,10 +123,13 @@ impl AssetTemplate {
 
         let dynamic_nodes_without_outputs = &is_node_dynamic | &output_nodes;
 
-        let constant_cache = base.get_const_cache(&dynamic_nodes_without_outputs);
+        let (constant_cache, failed_nodes) = base.get_const_cache(&dynamic_nodes_without_outputs);
         // To allow for a bitwise and we need to flip the bits to get all not output nodes.
         output_nodes.toggle_range(..);
-        let dynamic_output_filtered = &is_node_dynamic & &output_nodes;
+        // Property driven nodes also get evaluated with their defaults into the constant cache
+        // so instances only have to recompute them once a property actually changed.
+        let dynamic_output_filtered =
+            &(&(&is_node_dynamic | &is_node_property_driven) | &failed_nodes) & &output_nodes;
 
         // Retrieve the dependency map of the dynamic nodes.
         let mut dependency_node_map = base.get_node_map(&dynamic_output_filtered);
@@ -119,12 +143,17 @@ impl AssetTemplate {
 
         let topo_order = base.get_toposort(&dynamic_io_filtered);
 
-        //TODO this is a really bad hardcode
-        _ = dependency_node_map
-            .get_mut(&(5.into()))
-            .unwrap()
-            .1
-            .set_property_external(1, Reference::ExternalProperty { index: 0 });
+        // Redirect the connected node properties to the properties of the instance.
+        for (index, targets) in exposed_targets.iter().enumerate() {
+            for target in targets {
+                if let Some((_, node)) = dependency_node_map.get_mut(&target.node()) {
+                    _ = node.set_property_external(
+                        target.index(),
+                        Reference::ExternalProperty { index: index as u8 },
+                    );
+                }
+            }
+        }
 
         #[cfg(debug_assertions)]
         println!(
@@ -133,12 +162,15 @@ impl AssetTemplate {
         );
         AssetTemplate {
             constant_cache,
+            failed_nodes: failed_nodes.ones().map(NodeIndex::new).collect(),
 
             dependency_node_map,
             node_connectivity: dynamic_node_connectivity,
             topo_order,
 
             exposed_properties,
+            exposed_targets,
+            property_connectivity,
 
             outputs,
             output_infos,
@@ -160,6 +192,7 @@ impl AssetTemplate {
     ) -> Box<[NodeIndex]> {
         //Initial set comes from the nodes which need to be recomputed because of property changes.
         let mut set = property_changes;
+        set.extend(self.failed_nodes.iter().map(|node| node.index()));
 
         // If the corresponding inputs hace changed produce final bitset with every node set.
         for (index, value) in changed.iter().enumerate() {
@@ -191,7 +224,7 @@ impl AssetTemplate {
         &self,
         node: NodeIndex,
         processor: &impl ContextProvider,
-    ) -> Box<[Option<OwnedDataType>]> {
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
         let (inputs, node) = self.dependency_node_map.get(&node).unwrap();
         node.compute(Some(inputs), processor)
     }
@@ -211,6 +244,78 @@ impl AssetTemplate {
     pub fn get_properties(&self) -> &[PropertyMetadata] {
         &self.exposed_properties
     }
+
+    pub(crate) fn get_exposed_targets(&self) -> &[Box<[Reference]>] {
+        &self.exposed_targets
+    }
+
+    /// The index of the exposed property which drives exactly these node properties.
+    pub(crate) fn get_exposed_index(&self, targets: &[Reference]) -> Option<usize> {
+        self.exposed_targets
+            .iter()
+            .position(|exposed| exposed.as_ref() == targets)
+    }
+
+    pub(crate) fn get_property_connectivity(&self, index: u8) -> &FixedBitSet {
+        &self.property_connectivity[index as usize]
+    }
+
+    pub(crate) fn input_count(&self) -> usize {
+        self.node_connectivity.len()
+    }
+
+    /// Whether the node was baked as dynamic and is therefore part of the dependency map.
+    pub(crate) fn is_node_dynamic(&self, node: NodeIndex) -> bool {
+        self.dependency_node_map.contains_key(&node)
+    }
+
+    pub(crate) fn get_node_dependencies(&self, node: NodeIndex) -> &[Reference] {
+        &self.dependency_node_map[&node].0
+    }
+
+    /// All embedded instances of dynamic nodes.
+    /// These are shared between all instances of the template.
+    pub(crate) fn get_embedded_instances(
+        &self,
+    ) -> impl Iterator<Item = (NodeIndex, &AssetInstance)> {
+        self.dependency_node_map
+            .iter()
+            .filter_map(|(index, (_, node))| Some((*index, node.as_embedded_instance()?)))
+    }
+
+    /// All nodes which depend on any of the inputs.
+    pub(crate) fn get_input_driven_nodes(&self) -> FixedBitSet {
+        let mut nodes = FixedBitSet::new();
+        for reachable_nodes in &self.node_connectivity {
+            nodes |= reachable_nodes;
+        }
+        nodes
+    }
+
+    /// All nodes which depend on any of the exposed properties.
+    pub(crate) fn get_property_driven_nodes(&self) -> FixedBitSet {
+        let mut nodes = FixedBitSet::new();
+        for reachable_nodes in &self.property_connectivity {
+            nodes |= reachable_nodes;
+        }
+        nodes
+    }
+
+    /// Nodes which were baked into the constant cache dont need a template.
+    pub(super) fn is_embedded_template_set(&self, node: NodeIndex) -> bool {
+        self.dependency_node_map.get(&node).is_none_or(|(_, node)| {
+            node.as_embedded_instance()
+                .is_some_and(|instance| instance.has_template())
+        })
+    }
+
+    /// Injects the template of an embedded Asset into the baked node.
+    /// Nodes which were baked into the constant cache are ignored.
+    pub(super) fn set_embedded_template(&mut self, node: NodeIndex, template: Arc<AssetTemplate>) {
+        if let Some((_, node)) = self.dependency_node_map.get_mut(&node) {
+            node.get_embedded_instance().set_template(template);
+        }
+    }
 }
 
 //This is kind of a hack to access the right intermediate stuff but idk kinda fine
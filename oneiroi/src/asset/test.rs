@@ -1,4 +1,20 @@
-use super::EditableAsset;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use glam::Vec3;
+use rustc_hash::FxBuildHasher;
+
+use super::editable::{AssetEditorMethods, NodeError, NonTrivialEditorAction};
+use super::instance::AssetInstance;
+use super::server::{AssetResolver, AssetServer, ResolveError};
+use super::validation::{DiagnosticLocation, Severity};
+use super::{Asset, AssetBase, AssetReference, EditableAsset, EmbeddedAsset, NodeIndex};
+use crate::nodes::{ComputeError, Node, PropertyInterface, SetPropertyError};
+use crate::serialization::{
+    AssetLoadError, deserialize_asset_v1, export_asset_bundled, serialize_asset_binary,
+};
+use crate::type_system::OwnedDataType;
+use crate::type_system::data_types::{DataTypeKind, Selection};
 
 #[test]
 pub fn get_graph_instance() {
@@ -20,3 +36,592 @@ pub fn get_graph_instance() {
     result.add_node_dependency(node1, 0, node2, 0); */
     let instance = result.get_instance();
 }
+
+#[test]
+pub fn unknown_alias_is_rejected() {
+    let mut asset = EditableAsset::default();
+    assert!(matches!(
+        asset.add_node("Teapot"),
+        Err(NodeError::UnknownAlias(alias)) if alias == "Teapot"
+    ));
+}
+
+/// The default Asset with the size of its Box exposed.
+fn exposed_box_asset() -> EditableAsset {
+    let mut asset = EditableAsset::default();
+    // The default Asset consists of the Expose, Output and Box node in this order.
+    asset
+        .try_add_property_connection(0, NodeIndex::new(2), 0)
+        .unwrap();
+    asset
+}
+
+fn compute_positions(instance: &AssetInstance) -> Vec<Vec3> {
+    let context = HashMap::<NodeIndex, Box<[OwnedDataType]>, FxBuildHasher>::default();
+    let outputs = instance.compute(Some(&[]), &context).unwrap();
+    match outputs[0].as_ref().unwrap() {
+        OwnedDataType::Mesh(mesh) => mesh.get_index_mesh_buffers(false).positions,
+        _ => panic!("The default Asset should output a Mesh"),
+    }
+}
+
+#[test]
+pub fn exposed_properties_are_discovered() {
+    let instance = exposed_box_asset().get_instance();
+
+    let properties = instance.get_properties();
+    assert_eq!(properties.len(), 1);
+    assert_eq!(properties[0].name(), "size");
+    assert_eq!(properties[0].get_type(), DataTypeKind::Vec3);
+
+    let unexposed = EditableAsset::default().get_instance();
+    assert!(unexposed.get_properties().is_empty());
+}
+
+#[test]
+pub fn node_property_is_exposed_only_once() {
+    let mut asset = exposed_box_asset();
+    assert!(matches!(
+        asset.try_add_property_connection(1, NodeIndex::new(2), 0),
+        Err(NodeError::PropertyAlreadyExposed(0))
+    ));
+
+    let extrude = asset.add_node("Extrude").unwrap();
+    asset
+        .try_add_node_connection(NodeIndex::new(2), 0, extrude, 0)
+        .unwrap();
+    asset.try_add_property_connection(1, extrude, 1).unwrap();
+    let instance = asset.get_instance();
+    assert_eq!(instance.get_properties().len(), 2);
+    assert_eq!(instance.get_properties()[1].name(), "amount");
+
+    // Instances can't hold a Color so the albedo of a Material can't be exposed.
+    let material = asset.add_node("Material").unwrap();
+    assert!(matches!(
+        asset.try_add_property_connection(2, material, 0),
+        Err(NodeError::PropertyNotExposable(0))
+    ));
+}
+
+#[test]
+pub fn invalid_selection_fails_the_computation() {
+    let mut asset = EditableAsset::default();
+    let output = asset.add_node("Output").unwrap();
+    let node = asset.add_node("Box").unwrap();
+    let bevel = asset.add_node("Bevel").unwrap();
+    asset.try_add_node_connection(node, 0, bevel, 0).unwrap();
+    asset.try_add_node_connection(bevel, 0, output, 0).unwrap();
+    asset
+        .try_set_node_property(
+            bevel,
+            "query",
+            OwnedDataType::Selection(Box::new(Selection::new("grow("))),
+        )
+        .unwrap();
+
+    let context = HashMap::<NodeIndex, Box<[OwnedDataType]>, FxBuildHasher>::default();
+    let instance = asset.get_instance();
+    // The error is reported on every computation until the Asset is fixed.
+    for _ in 0..2 {
+        assert!(matches!(
+            instance.compute(Some(&[]), &context),
+            Err(ComputeError::InvalidSelection(_))
+        ));
+    }
+
+    asset
+        .try_set_node_property(
+            bevel,
+            "query",
+            OwnedDataType::Selection(Box::new(Selection::new("all"))),
+        )
+        .unwrap();
+    assert!(asset.get_instance().compute(Some(&[]), &context).is_ok());
+}
+
+#[test]
+pub fn set_exposed_property() {
+    let mut instance = exposed_box_asset().get_instance();
+    let max = |positions: Vec<Vec3>| positions.into_iter().reduce(Vec3::max).unwrap();
+
+    assert_eq!(max(compute_positions(&instance)), Vec3::splat(0.5));
+
+    instance
+        .try_set_property("size", OwnedDataType::Vec3(Vec3::splat(4.)))
+        .unwrap();
+    assert_eq!(max(compute_positions(&instance)), Vec3::splat(2.));
+
+    assert!(matches!(
+        instance.try_set_property("size", OwnedDataType::Float(1.)),
+        Err(SetPropertyError::WrongType)
+    ));
+    assert!(matches!(
+        instance.try_set_property("height", OwnedDataType::Float(1.)),
+        Err(SetPropertyError::NotFound)
+    ));
+}
+
+#[test]
+pub fn exposed_property_marks_downstream_nodes() {
+    let template = exposed_box_asset().get_template();
+
+    let affected = template.query(&[], template.get_property_connectivity(0).clone());
+    assert_eq!(&*affected, &[NodeIndex::new(2)]);
+}
+
+#[test]
+pub fn exposed_properties_are_forwarded_by_both_forms() {
+    let editable = Asset::Editable(exposed_box_asset());
+    let runtime = Asset::Runtime(editable.to_runtime());
+
+    for asset in [&editable, &runtime] {
+        let targets = asset.get_exposed_property_targets();
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0].len(), 1);
+        assert_eq!(targets[0][0].node(), NodeIndex::new(2));
+        assert_eq!(
+            asset.get_exposed_property_metadata(&targets[0]).name(),
+            "size"
+        );
+        let reachable = asset.nodes_reachable_from_respective_property(&targets);
+        assert!(reachable[0].contains(2));
+    }
+}
+
+#[test]
+pub fn exposed_properties_keep_their_index() {
+    let mut asset = exposed_box_asset();
+    let node = asset.add_node("Box").unwrap();
+    asset.try_add_property_connection(2, node, 0).unwrap();
+    asset
+        .delete_property_connection(NodeIndex::new(2), 0)
+        .unwrap();
+
+    let editable = Asset::Editable(asset);
+    let runtime = Asset::Runtime(editable.to_runtime());
+    for asset in [&editable, &runtime] {
+        let targets = asset.get_exposed_property_targets();
+        assert_eq!(targets.len(), 3);
+        assert!(targets[0].is_empty() && targets[1].is_empty());
+        assert_eq!(targets[2][0].node(), node);
+        assert_eq!(
+            asset.get_exposed_property_metadata(&targets[2]).name(),
+            "size"
+        );
+    }
+}
+
+#[test]
+pub fn exposed_property_names_are_unique() {
+    let mut asset = exposed_box_asset();
+    let node = asset.add_node("Box").unwrap();
+    asset.try_add_property_connection(1, node, 0).unwrap();
+
+    let instance = asset.get_instance();
+    let properties = instance.get_properties();
+    assert_eq!(properties[0].name(), "size");
+    assert_eq!(properties[1].name(), "size_2");
+    assert!(instance.try_get_property("size_2").is_ok());
+}
+
+#[test]
+pub fn runtime_asset_roundtrip() {
+    let asset = Asset::Editable(exposed_box_asset());
+    let serialized = serde_json::to_string(&asset.to_runtime()).unwrap();
+    let runtime: Asset = serde_json::from_str(&serialized).unwrap();
+    assert!(matches!(runtime, Asset::Runtime(_)));
+
+    let mut editable_instance = asset.get_instance();
+    let mut runtime_instance = runtime.get_instance();
+    for instance in [&mut editable_instance, &mut runtime_instance] {
+        instance
+            .try_set_property("size", OwnedDataType::Vec3(Vec3::new(1., 2., 3.)))
+            .unwrap();
+    }
+
+    assert_eq!(
+        compute_positions(&editable_instance),
+        compute_positions(&runtime_instance)
+    );
+}
+
+#[test]
+pub fn recompute_only_changed_outputs() {
+    let mut asset = exposed_box_asset();
+    // A second independent branch which isn't affected by the exposed property.
+    let output = asset.add_node("Output").unwrap();
+    let node = asset.add_node("Box").unwrap();
+    asset.try_add_node_connection(node, 0, output, 0).unwrap();
+
+    let mut instance = asset.get_instance();
+    compute_positions(&instance);
+    assert_eq!(&*instance.get_changed_outputs(), &[true, true]);
+
+    compute_positions(&instance);
+    assert_eq!(&*instance.get_changed_outputs(), &[false, false]);
+
+    instance
+        .try_set_property("size", OwnedDataType::Vec3(Vec3::splat(2.)))
+        .unwrap();
+    compute_positions(&instance);
+    assert_eq!(&*instance.get_changed_outputs(), &[true, false]);
+
+    // The cached result has to be reused once the node is no longer dirty.
+    let positions = compute_positions(&instance);
+    assert_eq!(&*instance.get_changed_outputs(), &[false, false]);
+    assert_eq!(
+        positions.into_iter().reduce(Vec3::max),
+        Some(Vec3::splat(1.))
+    );
+
+    // Recomputing the same result doesn't count as a change.
+    instance
+        .try_set_property("size", OwnedDataType::Vec3(Vec3::splat(2.)))
+        .unwrap();
+    compute_positions(&instance);
+    assert_eq!(&*instance.get_changed_outputs(), &[false, false]);
+}
+
+/// Serves Assets from memory keyed by their path.
+#[derive(Default)]
+struct MemoryResolver(HashMap<String, Vec<u8>>);
+
+impl MemoryResolver {
+    fn with(mut self, path: &str, asset: &EditableAsset) -> Self {
+        let source = serde_json::to_vec(&Asset::Editable(asset.clone())).unwrap();
+        self.0.insert(path.to_string(), source);
+        self
+    }
+
+    fn with_binary(mut self, path: &str, asset: &EditableAsset) -> Self {
+        let source = serialize_asset_binary(&Asset::Editable(asset.clone()));
+        self.0.insert(path.to_string(), source);
+        self
+    }
+}
+
+impl AssetResolver for MemoryResolver {
+    fn load(&self, reference: &AssetReference) -> Result<Vec<u8>, ResolveError> {
+        self.0
+            .get(reference.get_path())
+            .cloned()
+            .ok_or_else(|| ResolveError::NotFound(reference.clone()))
+    }
+}
+
+fn reference(path: &str) -> AssetReference {
+    AssetReference::new(path.to_string(), None)
+}
+
+/// An Asset which outputs the Mesh of the referenced Asset as its second output.
+fn asset_embedding(path: &str, server: &mut AssetServer) -> EditableAsset {
+    let mut asset = EditableAsset::default();
+    let node = asset
+        .add_external_subgraph(reference(path), server)
+        .unwrap();
+    let output = asset.add_node("Output").unwrap();
+    asset.try_add_node_connection(node, 0, output, 0).unwrap();
+    asset
+}
+
+#[test]
+pub fn external_asset_is_resolved() {
+    let child = MemoryResolver::default().with("child", &EditableAsset::default());
+    let parent = asset_embedding("child", &mut AssetServer::new(child));
+
+    let serialized = serde_json::to_string(&Asset::Editable(parent)).unwrap();
+    let mut asset: Asset = serde_json::from_str(&serialized).unwrap();
+    assert!(!asset.is_resolved());
+    assert_eq!(asset.get_external_references(), vec![reference("child")]);
+
+    let resolver = MemoryResolver::default().with("child", &EditableAsset::default());
+    AssetServer::new(resolver).resolve(&mut asset).unwrap();
+    assert!(asset.is_resolved());
+
+    let context = HashMap::<NodeIndex, Box<[OwnedDataType]>, FxBuildHasher>::default();
+    let outputs = asset.get_instance().compute(Some(&[]), &context).unwrap();
+    let positions = |output: &Option<OwnedDataType>| match output {
+        Some(OwnedDataType::Mesh(mesh)) => mesh.get_index_mesh_buffers(false).positions,
+        _ => panic!("Both outputs should be Meshes"),
+    };
+    assert_eq!(positions(&outputs[0]), positions(&outputs[1]));
+}
+
+#[test]
+pub fn binary_external_asset_is_resolved() {
+    let child = MemoryResolver::default().with_binary("child", &EditableAsset::default());
+    let mut server = AssetServer::new(child);
+    let mut asset = Asset::Editable(asset_embedding("child", &mut server));
+    server.resolve(&mut asset).unwrap();
+    assert!(asset.is_resolved());
+
+    let context = HashMap::<NodeIndex, Box<[OwnedDataType]>, FxBuildHasher>::default();
+    let outputs = asset.get_instance().compute(Some(&[]), &context).unwrap();
+    assert!(matches!(outputs[1], Some(OwnedDataType::Mesh(_))));
+}
+
+#[test]
+pub fn external_template_is_shared() {
+    let resolver = MemoryResolver::default().with("child", &EditableAsset::default());
+    let mut server = AssetServer::new(resolver);
+
+    let first = server.load_template(&reference("child"), &mut Vec::new());
+    let second = server.load_template(&reference("child"), &mut Vec::new());
+    assert!(Arc::ptr_eq(&first.unwrap(), &second.unwrap()));
+
+    assert!(matches!(
+        server.load_template(&reference("missing"), &mut Vec::new()),
+        Err(ResolveError::NotFound(_))
+    ));
+}
+
+#[test]
+pub fn external_invalid_text_is_rejected() {
+    let mut resolver = MemoryResolver::default();
+    let source = vec![b'{', 0xff, b'}'];
+    resolver.0.insert("child".to_string(), source);
+    let mut server = AssetServer::new(resolver);
+
+    assert!(matches!(
+        server.load_template(&reference("child"), &mut Vec::new()),
+        Err(ResolveError::Invalid(_, AssetLoadError::Encoding(_)))
+    ));
+}
+
+#[test]
+pub fn external_reference_cycle_is_detected() {
+    // b embeds a while a is still empty, then a is replaced by an Asset embedding b.
+    let resolver = MemoryResolver::default().with("a", &EditableAsset::default());
+    let b = asset_embedding("a", &mut AssetServer::new(resolver));
+    let resolver = MemoryResolver::default()
+        .with("a", &EditableAsset::default())
+        .with("b", &b);
+    let a = asset_embedding("b", &mut AssetServer::new(resolver));
+
+    let resolver = MemoryResolver::default().with("a", &a).with("b", &b);
+    let mut server = AssetServer::new(resolver);
+    match server.load_template(&reference("a"), &mut Vec::new()) {
+        Err(ResolveError::Cycle(cycle)) => {
+            assert_eq!(cycle, vec![reference("a"), reference("b"), reference("a")])
+        }
+        other => panic!("Expected a cycle but got {other:?}"),
+    }
+}
+
+#[test]
+pub fn invalidation_reaches_dependents() {
+    let resolver = MemoryResolver::default().with("child", &EditableAsset::default());
+    let parent = asset_embedding("child", &mut AssetServer::new(resolver));
+    let resolver = MemoryResolver::default()
+        .with("child", &EditableAsset::default())
+        .with("parent", &parent)
+        .with("unrelated", &EditableAsset::default());
+    let mut server = AssetServer::new(resolver);
+    server
+        .load_template(&reference("parent"), &mut Vec::new())
+        .unwrap();
+    server
+        .load_template(&reference("unrelated"), &mut Vec::new())
+        .unwrap();
+
+    let mut invalidated = server.invalidate(&reference("child")).into_vec();
+    invalidated.sort_by(|a, b| a.get_path().cmp(b.get_path()));
+    assert_eq!(invalidated, vec![reference("child"), reference("parent")]);
+}
+
+#[test]
+pub fn bundled_asset_has_no_external_references() {
+    let resolver = MemoryResolver::default().with("child", &EditableAsset::default());
+    let mut parent = asset_embedding("child", &mut AssetServer::new(resolver));
+    let resolver = MemoryResolver::default().with("child", &EditableAsset::default());
+    let mut server = AssetServer::new(resolver);
+    // Both nodes embed the same file so the bundle should only contain it once.
+    parent
+        .add_external_subgraph(reference("child"), &mut server)
+        .unwrap();
+    let resolver = MemoryResolver::default()
+        .with("child", &EditableAsset::default())
+        .with("parent", &parent);
+    let grandparent = asset_embedding("parent", &mut AssetServer::new(resolver));
+
+    let resolver = MemoryResolver::default()
+        .with("child", &EditableAsset::default())
+        .with("parent", &parent);
+    let mut server = AssetServer::new(resolver);
+    let serialized =
+        export_asset_bundled(&Asset::Editable(grandparent.clone()), &mut server).unwrap();
+
+    // Loading the bundle must not require any of the referenced files.
+    let bundled = deserialize_asset_v1(&serialized).unwrap();
+    assert!(bundled.is_resolved());
+    assert!(bundled.get_external_references().is_empty());
+
+    let Asset::Editable(bundled) = bundled else {
+        panic!("Bundling should keep the Asset editable");
+    };
+    let [EmbeddedAsset::Internal(Asset::Editable(parent), _)] = bundled.get_embedded_assets()
+    else {
+        panic!("The parent should be inlined");
+    };
+    assert!(matches!(
+        parent.get_embedded_assets(),
+        [EmbeddedAsset::Internal(_, nodes)] if nodes.len() == 2
+    ));
+
+    let context = HashMap::<NodeIndex, Box<[OwnedDataType]>, FxBuildHasher>::default();
+    let expected = grandparent
+        .get_instance()
+        .compute(Some(&[]), &context)
+        .unwrap();
+    let outputs = bundled.get_instance().compute(Some(&[]), &context).unwrap();
+    assert_eq!(format!("{outputs:?}"), format!("{expected:?}"));
+}
+
+#[test]
+pub fn undo_and_redo_edits() {
+    let mut asset = EditableAsset::default();
+    assert!(!asset.can_undo());
+
+    let node = asset.add_node("Cylinder").unwrap();
+    let output = NodeIndex::new(1);
+    asset
+        .delete_connection(NodeIndex::new(2), 0, output, 0)
+        .unwrap();
+    asset.try_add_node_connection(node, 0, output, 0).unwrap();
+    assert_eq!(asset.get_nodes().len(), 3);
+
+    // Deleting a node removes its connections which have to come back on undo.
+    asset.delete_node(node).unwrap();
+    assert_eq!(asset.get_nodes().len(), 2);
+    assert!(asset.undo().unwrap());
+    assert_eq!(asset.get_nodes(), vec![output, NodeIndex::new(2), node]);
+    assert_eq!(asset.get_node_connections().len(), 1);
+
+    // Undo the connection, the disconnect and finally the node itself.
+    for _ in 0..3 {
+        assert!(asset.undo().unwrap());
+    }
+    assert!(!asset.can_undo());
+    assert!(!asset.undo().unwrap());
+    assert_eq!(asset.get_nodes().len(), 2);
+    assert_eq!(
+        asset.get_edge_endpoints(asset.get_node_connections()[0]),
+        (NodeIndex::new(2), output)
+    );
+
+    assert!(asset.redo().unwrap());
+    assert_eq!(asset.get_nodes().len(), 3);
+    // A new edit makes the undone steps unreachable.
+    asset
+        .try_set_node_position(node, glam::Vec2::new(1., 2.))
+        .unwrap();
+    assert!(!asset.can_redo());
+}
+
+#[test]
+pub fn grouped_edits_are_undone_together() {
+    let mut asset = exposed_box_asset();
+    asset.clear_history();
+    let nodes = asset.get_nodes();
+    let position =
+        |asset: &EditableAsset, node| asset.try_get_node_metadata(node).unwrap().get_position();
+
+    asset.begin_group();
+    for node in &nodes {
+        asset
+            .try_set_node_position(*node, glam::Vec2::splat(10.))
+            .unwrap();
+    }
+    asset.begin_group();
+    asset
+        .try_set_node_property(
+            NodeIndex::new(2),
+            "size",
+            OwnedDataType::Vec3(Vec3::splat(4.)),
+        )
+        .unwrap();
+    asset.end_group();
+    asset.end_group();
+
+    assert!(asset.undo().unwrap());
+    assert!(!asset.can_undo());
+    assert_eq!(position(&asset, nodes[1]), glam::Vec2::new(-160., 0.));
+    // The template has to pick up the reverted property.
+    let max = compute_positions(&asset.get_instance())
+        .into_iter()
+        .reduce(Vec3::max);
+    assert_eq!(max, Some(Vec3::splat(0.5)));
+
+    assert!(asset.redo().unwrap());
+    assert_eq!(position(&asset, nodes[0]), glam::Vec2::splat(10.));
+    let max = compute_positions(&asset.get_instance())
+        .into_iter()
+        .reduce(Vec3::max);
+    assert_eq!(max, Some(Vec3::splat(2.)));
+}
+
+#[test]
+pub fn undo_external_subgraph_drops_its_reference() {
+    let resolver = MemoryResolver::default().with("child", &EditableAsset::default());
+    let mut server = AssetServer::new(resolver);
+    let mut asset = EditableAsset::default();
+    let node = asset
+        .add_external_subgraph(reference("child"), &mut server)
+        .unwrap();
+
+    assert!(asset.undo().unwrap());
+    assert!(!asset.can_undo());
+    let undone = Asset::Editable(asset.clone());
+    assert!(undone.get_external_references().is_empty());
+
+    assert!(asset.redo().unwrap());
+    assert_eq!(asset.get_nodes().last(), Some(&node));
+    let redone = Asset::Editable(asset);
+    assert_eq!(redone.get_external_references(), vec![reference("child")]);
+    assert!(redone.is_resolved());
+}
+
+#[test]
+pub fn validate_reports_graph_problems() {
+    let mut asset = EditableAsset::default();
+    assert!(asset.validate().is_empty());
+
+    let output = NodeIndex::new(1);
+    let set_material = asset.add_node("SetMaterial").unwrap();
+    let material = asset.add_node("Material").unwrap();
+    // The Material is connected to the Mesh input while the Material input stays empty.
+    asset
+        .try_add_node_connection(material, 0, set_material, 0)
+        .unwrap();
+    let errors = asset.validate();
+    assert_eq!(errors.len(), 2);
+    assert!(errors.iter().all(|error| error.severity == Severity::Error));
+    assert!(errors.iter().all(|error| error.node == Some(set_material)));
+    assert_eq!(errors[0].location, Some(DiagnosticLocation::InputSocket(0)));
+    assert_eq!(errors[1].location, Some(DiagnosticLocation::InputSocket(1)));
+
+    // Two nodes feeding each other form a cycle.
+    asset.delete_node(material).unwrap();
+    let other = asset.add_node("SetMaterial").unwrap();
+    asset
+        .try_add_node_connection(set_material, 0, other, 0)
+        .unwrap();
+    asset
+        .try_add_node_connection(other, 0, set_material, 0)
+        .unwrap();
+    let cycle = asset
+        .validate()
+        .into_iter()
+        .filter(|diagnostic| diagnostic.message.contains("cycle"))
+        .filter_map(|diagnostic| diagnostic.node)
+        .collect::<std::collections::HashSet<_>>();
+    assert_eq!(cycle, [set_material, other].into_iter().collect());
+
+    asset.delete_node(output).unwrap();
+    assert!(
+        asset
+            .validate()
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Warning && diagnostic.node.is_none())
+    );
+}
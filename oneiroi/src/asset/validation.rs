@@ -0,0 +1,288 @@
+use std::fmt::Display;
+
+use petgraph::Direction::{Incoming, Outgoing};
+use petgraph::algo::tarjan_scc;
+use petgraph::visit::EdgeRef;
+
+use super::editable::EditableAsset;
+use super::{Connection, NodeIndex};
+use crate::nodes::{Nodes, PropertyInterface, SocketInterface};
+use crate::type_system::Reference;
+use crate::type_system::data_types::DataTypeKind;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The Asset can still be computed but probably not as intended.
+    Warning,
+    /// Computing the Asset fails or panics.
+    Error,
+}
+
+/// The part of a node a Diagnostic refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticLocation {
+    InputSocket(u8),
+    Property(u8),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// None if the problem concerns the Asset as a whole.
+    pub node: Option<NodeIndex>,
+    pub location: Option<DiagnosticLocation>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn error(node: NodeIndex, location: Option<DiagnosticLocation>, message: String) -> Self {
+        Self {
+            severity: Severity::Error,
+            node: Some(node),
+            location,
+            message,
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.severity)?;
+        if let Some(node) = self.node {
+            write!(f, " in node {}", node.index())?;
+        }
+        match self.location {
+            Some(DiagnosticLocation::InputSocket(socket)) => write!(f, " at input {socket}")?,
+            Some(DiagnosticLocation::Property(property)) => write!(f, " at property {property}")?,
+            None => {}
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+fn types_match(a: DataTypeKind, b: DataTypeKind) -> bool {
+    a == b || a == DataTypeKind::Omni || b == DataTypeKind::Omni
+}
+
+impl EditableAsset {
+    /// Checks the whole graph for problems which single edits can't detect,
+    /// e.g. after nodes got swapped or the file was edited by hand.
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let graph = self.get_graph();
+        let mut diagnostics = Vec::new();
+
+        let expose = NodeIndex::new(0);
+        if !graph
+            .node_weight(expose)
+            .is_some_and(|(_, node)| matches!(node, Nodes::Expose))
+        {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Error,
+                node: None,
+                location: None,
+                message: "The first node has to be the Expose node".into(),
+            });
+        }
+
+        for component in tarjan_scc(graph) {
+            let is_cycle = component.len() > 1 || graph.contains_edge(component[0], component[0]);
+            if is_cycle {
+                diagnostics.extend(component.into_iter().map(|node| {
+                    Diagnostic::error(node, None, "The node is part of a cycle".into())
+                }));
+            }
+        }
+
+        // Sockets can only be queried once the templates of embedded Assets are known.
+        let is_unresolved = |node: NodeIndex| {
+            graph[node]
+                .1
+                .as_embedded_instance()
+                .is_some_and(|instance| !instance.has_template())
+        };
+
+        let mut has_output = false;
+        for node in graph.node_indices() {
+            let weight = &graph[node].1;
+            if matches!(weight, Nodes::Expose) {
+                if node != expose {
+                    diagnostics.push(Diagnostic::error(
+                        node,
+                        None,
+                        "Only a single Expose node is allowed".into(),
+                    ));
+                }
+                continue;
+            }
+            has_output |= weight.is_output_node();
+            if is_unresolved(node) {
+                diagnostics.push(Diagnostic::error(
+                    node,
+                    None,
+                    "The embedded Asset is not resolved".into(),
+                ));
+                continue;
+            }
+
+            self.validate_sockets(node, &is_unresolved, &mut diagnostics);
+            self.validate_properties(node, &mut diagnostics);
+        }
+
+        if !has_output {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                node: None,
+                location: None,
+                message: "The Asset has no Output node".into(),
+            });
+        }
+
+        let references = self
+            .get_property_scripts()
+            .keys()
+            .chain(self.get_exposed_property_order());
+        for reference in references {
+            let Reference::Property { node, index } = *reference else {
+                continue;
+            };
+            let exists = graph
+                .node_weight(node)
+                .is_some_and(|(_, weight)| (index as usize) < weight.get_properties().len());
+            if !exists {
+                diagnostics.push(Diagnostic::error(
+                    node,
+                    Some(DiagnosticLocation::Property(index)),
+                    "A script or exposed property refers to a property which doesn't exist".into(),
+                ));
+            }
+        }
+
+        diagnostics
+    }
+
+    /// Every input socket requires exactly one connection from an output of the same type.
+    fn validate_sockets(
+        &self,
+        node: NodeIndex,
+        is_unresolved: &impl Fn(NodeIndex) -> bool,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        let graph = self.get_graph();
+        let inputs = graph[node].1.get_input_sockets();
+        let mut connected = vec![0usize; inputs.len()];
+
+        for edge in graph.edges_directed(node, Incoming) {
+            let Connection::Socket { source, target } = *edge.weight() else {
+                continue;
+            };
+            let location = Some(DiagnosticLocation::InputSocket(target));
+            let Some(input) = inputs.get(target as usize) else {
+                diagnostics.push(Diagnostic::error(
+                    node,
+                    location,
+                    format!("The node has no input socket {target}"),
+                ));
+                continue;
+            };
+            connected[target as usize] += 1;
+
+            let from = edge.source();
+            if matches!(graph[from].1, Nodes::Expose) {
+                diagnostics.push(Diagnostic::error(
+                    node,
+                    location,
+                    "The Expose node only provides properties".into(),
+                ));
+                continue;
+            }
+            if is_unresolved(from) {
+                continue;
+            }
+            match graph[from].1.get_output_sockets().get(source as usize) {
+                None => diagnostics.push(Diagnostic::error(
+                    node,
+                    location,
+                    format!("Node {} has no output socket {source}", from.index()),
+                )),
+                Some(output) if !types_match(output.get_type(), input.get_type()) => diagnostics
+                    .push(Diagnostic::error(
+                        node,
+                        location,
+                        format!(
+                            "Expected {:?} but node {} provides {:?}",
+                            input.get_type(),
+                            from.index(),
+                            output.get_type()
+                        ),
+                    )),
+                Some(_) => {}
+            }
+        }
+
+        for (socket, count) in connected.into_iter().enumerate() {
+            let location = Some(DiagnosticLocation::InputSocket(socket as u8));
+            match count {
+                0 => diagnostics.push(Diagnostic::error(
+                    node,
+                    location,
+                    "The input socket is not connected".into(),
+                )),
+                1 => {}
+                _ => diagnostics.push(Diagnostic::error(
+                    node,
+                    location,
+                    "The input socket has multiple connections".into(),
+                )),
+            }
+        }
+    }
+
+    /// Property connections have to target existing properties and every target
+    /// of one exposed property has to share its type.
+    fn validate_properties(&self, node: NodeIndex, diagnostics: &mut Vec<Diagnostic>) {
+        let graph = self.get_graph();
+        let properties = graph[node].1.get_properties();
+
+        for edge in graph.edges_directed(node, Incoming) {
+            let Connection::Property { source, target } = *edge.weight() else {
+                continue;
+            };
+            let location = Some(DiagnosticLocation::Property(target));
+            let Some(property) = properties.get(target as usize) else {
+                diagnostics.push(Diagnostic::error(
+                    node,
+                    location,
+                    format!("The node has no property {target}"),
+                ));
+                continue;
+            };
+
+            // Compare against the first target of the exposed property which is valid.
+            let first = graph
+                .edges_directed(edge.source(), Outgoing)
+                .filter_map(|other| match *other.weight() {
+                    Connection::Property {
+                        source: other_source,
+                        target,
+                    } if other_source == source => graph[other.target()]
+                        .1
+                        .get_properties()
+                        .get(target as usize)
+                        .map(|property| property.get_type()),
+                    _ => None,
+                })
+                .next();
+            if first.is_some_and(|first| first != property.get_type()) {
+                diagnostics.push(Diagnostic::error(
+                    node,
+                    location,
+                    format!(
+                        "The exposed property {source} is of type {:?} but this property is {:?}",
+                        first.unwrap(),
+                        property.get_type()
+                    ),
+                ));
+            }
+        }
+    }
+}
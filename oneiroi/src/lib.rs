@@ -5,6 +5,7 @@ use rustc_hash::FxBuildHasher;
 pub mod asset;
 pub mod nodes;
 pub mod property;
+mod random;
 pub mod serialization;
 pub mod type_system;
 
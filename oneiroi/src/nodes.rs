@@ -2,7 +2,7 @@ use crate::{
     property::PropertyMetadata,
     type_system::{
         OwnedDataType, Reference, TypeRef,
-        data_types::{DataTypeKind, TypeDescriptor, Vec3},
+        data_types::{DataTypeKind, SelectionError, TypeDescriptor, Vec3},
     },
 };
 mod nodes_enum;
@@ -28,6 +28,25 @@ pub enum SetPropertyError {
 
 #[derive(Debug)]
 pub struct PropertyNotFound;
+
+/// Why a node couldn't produce its outputs.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ComputeError {
+    #[error(transparent)]
+    InvalidSelection(#[from] SelectionError),
+    #[error("{node} can't take an input of type {found:?}")]
+    UnsupportedInput {
+        node: &'static str,
+        found: DataTypeKind,
+    },
+    #[error("{value} is not a valid {property} of {node}")]
+    InvalidProperty {
+        node: &'static str,
+        property: &'static str,
+        value: i64,
+    },
+}
+
 pub trait PropertyInterface {
     /// Should be called one time at the start.
     /// Returns the properties in the right order
@@ -42,12 +61,24 @@ pub trait PropertyInterface {
     ) -> Result<(), SetPropertyError>;
     fn try_get_property(&self, property: &str) -> Result<TypeRef, PropertyNotFound>;
 
+    /// Sets the property at `index` in the order of [`get_properties`](Self::get_properties).
     fn try_set_property_index(
         &mut self,
         index: u8,
         value: OwnedDataType,
-    ) -> Result<(), SetPropertyError>;
-    fn try_get_property_index(&self, index: u8) -> Result<TypeRef, PropertyNotFound>;
+    ) -> Result<(), SetPropertyError> {
+        let properties = self.get_properties();
+        let property = properties
+            .get(index as usize)
+            .ok_or(SetPropertyError::WrongIndex)?;
+        self.try_set_property(&property.name, value)
+    }
+    /// Gets the property at `index` in the order of [`get_properties`](Self::get_properties).
+    fn try_get_property_index(&self, index: u8) -> Result<TypeRef, PropertyNotFound> {
+        let properties = self.get_properties();
+        let property = properties.get(index as usize).ok_or(PropertyNotFound)?;
+        self.try_get_property(&property.name)
+    }
     //TODO this could somehow work maybe
     // fn try_get_property_ref<T: DataType>(&self, index: u8) -> Result<&T, PropertyNotFound>;
 
@@ -121,7 +152,7 @@ pub trait Node: PropertyInterface + SocketInterface {
         context: &impl ContextProvider,
         //TODO explore this
         //write_back: Box<[&mut DataTypeValue]>,
-    ) -> Box<[Option<OwnedDataType>]>;
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError>;
 
     /* //These 4 methods can be generated by a proc macro with the exception of Handlers aka AssetInstance
     fn try_set_property(
@@ -214,7 +245,11 @@ pub trait ContextProvider {
 
     //fn set_template(&mut self, base: Arc<AssetTemplate>);
 
-    //fn refenrence_changed(&self) -> bool;
+    /// Whether the value behind the Reference changed since the last computation.
+    /// Providers which dont track their changes report every Reference as changed.
+    fn reference_changed(&self, index: Reference) -> bool {
+        true
+    }
 
     //n try_set_input_socket(&mut self);
 
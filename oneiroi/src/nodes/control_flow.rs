@@ -2,6 +2,7 @@ mod socket_input {
     use serde::Deserialize;
     use serde::Serialize;
 
+    use crate::nodes::ComputeError;
     use crate::nodes::ContextProvider;
     use crate::nodes::Node;
     use crate::nodes::PropertyInterface;
@@ -63,10 +64,11 @@ mod socket_input {
 
         fn set_property_external(
             &mut self,
-            index: u8,
-            reference: Reference,
+            _index: u8,
+            _reference: Reference,
         ) -> Result<(), SetPropertyError> {
-            todo!()
+            // There are no properties which could be exposed.
+            Err(SetPropertyError::WrongIndex)
         }
 
         /* fn try_get_property_script(&self, property: &str) -> Result<String, PropertyNotFound> {
@@ -97,7 +99,7 @@ mod socket_input {
             &self,
             _: Option<&[Reference]>,
             context: &impl ContextProvider,
-        ) -> Box<[Option<OwnedDataType>]> {
+        ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
             unreachable!()
         }
 
@@ -131,6 +133,7 @@ mod socket_output {
     use serde::Deserialize;
     use serde::Serialize;
 
+    use crate::nodes::ComputeError;
     use crate::nodes::ContextProvider;
     use crate::nodes::Node;
 
@@ -183,10 +186,11 @@ mod socket_output {
 
         fn set_property_external(
             &mut self,
-            index: u8,
-            reference: Reference,
+            _index: u8,
+            _reference: Reference,
         ) -> Result<(), SetPropertyError> {
-            todo!()
+            // There are no properties which could be exposed.
+            Err(SetPropertyError::WrongIndex)
         }
 
         /* fn try_get_property_script(&self, property: &str) -> Result<String, PropertyNotFound> {
@@ -217,7 +221,7 @@ mod socket_output {
             &self,
             input_sockets: Option<&[Reference]>,
             context: &impl ContextProvider,
-        ) -> Box<[Option<OwnedDataType>]> {
+        ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
             unreachable!()
         }
 
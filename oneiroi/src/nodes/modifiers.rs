@@ -1,7 +1,23 @@
 mod bevel;
+mod boolean;
+mod cleanup_mesh;
 mod extrude;
+mod join_meshes;
+mod mirror;
 mod set_material;
+mod set_shading;
+mod subdivide;
+#[cfg(test)]
+mod tests;
+mod uv_project;
 
 pub use bevel::BevelV1;
+pub use boolean::BooleanV1;
+pub use cleanup_mesh::CleanupMeshV1;
 pub use extrude::ExtrudeV1;
+pub use join_meshes::JoinMeshesV1;
+pub use mirror::MirrorV1;
 pub use set_material::SetMaterialV1;
+pub use set_shading::SetShadingV1;
+pub use subdivide::SubdivideV1;
+pub use uv_project::UvProjectV1;
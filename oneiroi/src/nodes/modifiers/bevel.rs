@@ -4,14 +4,13 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     nodes::{
-        ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
         SocketInterface, StaticNodeMetadata,
     },
     property::{Property, PropertyMetadata},
     type_system::{
         OwnedDataType, Reference, TypeRef,
-        data_types::{DataType, DataTypeKind, Int, Mesh, Selection, TypeDescriptor},
-        trait_types::MeshMut2D,
+        data_types::{DataType, DataTypeKind, Float, Int, Mesh, Selection, TypeDescriptor},
     },
 };
 
@@ -19,12 +18,21 @@ use crate::{
 pub struct BevelV1 {
     query: Property<Selection>,
     segments: Property<Int>,
+    // Files written before the width existed use the default.
+    #[serde(default = "default_width")]
+    width: Property<Float>,
 }
+
+fn default_width() -> Property<Float> {
+    Property::new(0.1)
+}
+
 impl Default for BevelV1 {
     fn default() -> Self {
         Self {
-            query: Property::new(Selection::new("1")),
+            query: Property::new(Selection::new("sharp")),
             segments: Property::new(1),
+            width: default_width(),
         }
     }
 }
@@ -36,16 +44,15 @@ impl Node for BevelV1 {
         context: &impl ContextProvider,
         //TODO explore this
         // write_back: [&mut DataTypeValue],
-    ) -> Box<[Option<OwnedDataType>]> {
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
         let input = context.get_reference(input_sockets.unwrap()[0]);
         let input: &Mesh = input.dispatch_ref().unwrap();
-        let mut new_surface = input.clone();
-        //let selection: Vec<FaceHandle> = self.query.get_value(context).try_get().unwrap();
-        //TODO
+        let edges = self.query.get_value(context).edges(input)?;
+        let segments = (*self.segments.get_value(context)).clamp(1, 64) as u32;
 
-        new_surface.bevel_edges();
+        let new_surface = input.bevel_edges(edges, *self.width.get_value(context), segments);
 
-        Box::new([Some(OwnedDataType::Mesh(Box::new(new_surface)))])
+        Ok(Box::new([Some(OwnedDataType::Mesh(Box::new(new_surface)))]))
     }
 
     fn node_metadata(&self) -> StaticNodeMetadata {
@@ -84,6 +91,11 @@ impl PropertyInterface for BevelV1 {
                     .set_value(value.dispatch().expect("TODO error handling"));
                 Ok(())
             }
+            "width" => {
+                self.width
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
 
             _ => {
                 println!("called set_prop with {:?}", property);
@@ -96,6 +108,7 @@ impl PropertyInterface for BevelV1 {
         match property {
             "query" => Ok(self.query.get_literal_value().to_data_type_ref()),
             "segments" => Ok(self.segments.get_literal_value().to_data_type_ref()),
+            "width" => Ok(self.width.get_literal_value().to_data_type_ref()),
             //"origin" => Ok(self.origin.get_instance()),
             _ => {
                 //println!("called get_prop with {:?}", property);
@@ -124,7 +137,15 @@ impl PropertyInterface for BevelV1 {
             documentation: "".into(),
         };
 
-        Box::new([info, info2])
+        let info3 = PropertyMetadata {
+            name: "width".into(),
+            r#type: default.width.get_type(),
+            default: default.width.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "The distance of the new edges to the beveled edge.".into(),
+        };
+
+        Box::new([info, info2, info3])
     }
 
     fn try_set_property_index(
@@ -144,7 +165,12 @@ impl PropertyInterface for BevelV1 {
         index: u8,
         reference: Reference,
     ) -> Result<(), SetPropertyError> {
-        todo!()
+        match index {
+            0 => Ok(self.query.set_external(reference)),
+            1 => Ok(self.segments.set_external(reference)),
+            2 => Ok(self.width.set_external(reference)),
+            _ => Err(SetPropertyError::WrongIndex),
+        }
     }
 
     /* fn try_get_property_script(&self, property: &str) -> Result<String, PropertyNotFound> {
@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    nodes::{
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        SocketInterface, StaticNodeMetadata,
+    },
+    property::{Property, PropertyMetadata},
+    type_system::{
+        OwnedDataType, Reference, TypeRef,
+        data_types::{BooleanMode, DataType, DataTypeKind, Int, Mesh, TypeDescriptor},
+    },
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BooleanV1 {
+    //TODO use an enum once they are supported by properties
+    mode: Property<Int>,
+}
+
+impl Default for BooleanV1 {
+    fn default() -> Self {
+        Self {
+            mode: Property::new(0),
+        }
+    }
+}
+
+impl Node for BooleanV1 {
+    fn compute(
+        &self,
+        input_sockets: Option<&[Reference]>,
+        context: &impl ContextProvider,
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
+        let first = context.get_reference(input_sockets.unwrap()[0]);
+        let first: &Mesh = first.dispatch_ref().unwrap();
+        let second = context.get_reference(input_sockets.unwrap()[1]);
+        let second: &Mesh = second.dispatch_ref().unwrap();
+
+        // Values driven by other nodes aren't checked when they are set.
+        let mode = *self.mode.get_value(context);
+        let mode = BooleanMode::from_index(mode).ok_or(ComputeError::InvalidProperty {
+            node: "Boolean",
+            property: "mode",
+            value: mode,
+        })?;
+        let new_surface = first.boolean(second, mode);
+
+        Ok(Box::new([Some(OwnedDataType::Mesh(Box::new(new_surface)))]))
+    }
+
+    fn node_metadata(&self) -> StaticNodeMetadata {
+        StaticNodeMetadata { color: "#4338ca" }
+    }
+}
+impl SocketInterface for BooleanV1 {
+    fn get_input_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::new([
+            TypeDescriptor {
+                r#type: DataTypeKind::Mesh,
+                mutable: true,
+            },
+            TypeDescriptor {
+                r#type: DataTypeKind::Mesh,
+                mutable: false,
+            },
+        ])
+    }
+
+    fn get_output_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::new([TypeDescriptor {
+            r#type: DataTypeKind::Mesh,
+            mutable: true,
+        }])
+    }
+}
+
+impl PropertyInterface for BooleanV1 {
+    fn try_set_property(
+        &mut self,
+        property: &str,
+        value: OwnedDataType,
+    ) -> Result<(), SetPropertyError> {
+        match property {
+            "mode" => {
+                let mode = value.dispatch().expect("TODO error handling");
+                if BooleanMode::from_index(mode).is_none() {
+                    return Err(SetPropertyError::WrongValue);
+                }
+                self.mode.set_value(mode);
+                Ok(())
+            }
+
+            _ => Err(SetPropertyError::NotFound),
+        }
+    }
+
+    fn try_get_property(&self, property: &str) -> Result<TypeRef, PropertyNotFound> {
+        match property {
+            "mode" => Ok(self.mode.get_literal_value().to_data_type_ref()),
+            _ => Err(PropertyNotFound),
+        }
+    }
+
+    fn get_properties(&self) -> Box<[PropertyMetadata]> {
+        let default = Self::default();
+
+        let info = PropertyMetadata {
+            name: "mode".into(),
+            r#type: default.mode.get_type(),
+            default: default.mode.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "0 = union, 1 = subtract the second Mesh, 2 = intersection.".into(),
+        };
+
+        Box::new([info])
+    }
+
+    fn set_property_external(
+        &mut self,
+        index: u8,
+        reference: Reference,
+    ) -> Result<(), SetPropertyError> {
+        match index {
+            0 => self.mode.set_external(reference),
+            _ => return Err(SetPropertyError::WrongIndex),
+        }
+        Ok(())
+    }
+}
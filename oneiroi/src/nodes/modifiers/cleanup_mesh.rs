@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    nodes::{
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        SocketInterface, StaticNodeMetadata,
+    },
+    property::{Property, PropertyMetadata},
+    type_system::{
+        OwnedDataType, Reference, TypeRef,
+        data_types::{DataType, DataTypeKind, Float, Mesh, TypeDescriptor},
+    },
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CleanupMeshV1 {
+    distance: Property<Float>,
+    unify_winding: Property<bool>,
+}
+
+impl Default for CleanupMeshV1 {
+    fn default() -> Self {
+        Self {
+            distance: Property::new(0.0001),
+            unify_winding: Property::new(true),
+        }
+    }
+}
+
+impl Node for CleanupMeshV1 {
+    fn compute(
+        &self,
+        input_sockets: Option<&[Reference]>,
+        context: &impl ContextProvider,
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
+        let input = context.get_reference(input_sockets.unwrap()[0]);
+        let input: &Mesh = input.dispatch_ref().unwrap();
+        let distance = *self.distance.get_value(context);
+        let unify_winding = *self.unify_winding.get_value(context);
+
+        let new_surface = input.cleanup(distance, unify_winding);
+
+        Ok(Box::new([Some(OwnedDataType::Mesh(Box::new(new_surface)))]))
+    }
+
+    fn node_metadata(&self) -> StaticNodeMetadata {
+        StaticNodeMetadata { color: "#4338ca" }
+    }
+}
+impl SocketInterface for CleanupMeshV1 {
+    fn get_input_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::new([TypeDescriptor {
+            r#type: DataTypeKind::Mesh,
+            mutable: true,
+        }])
+    }
+
+    fn get_output_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::new([TypeDescriptor {
+            r#type: DataTypeKind::Mesh,
+            mutable: true,
+        }])
+    }
+}
+
+impl PropertyInterface for CleanupMeshV1 {
+    fn try_set_property(
+        &mut self,
+        property: &str,
+        value: OwnedDataType,
+    ) -> Result<(), SetPropertyError> {
+        match property {
+            "distance" => {
+                self.distance
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "unify_winding" => {
+                self.unify_winding
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+
+            _ => Err(SetPropertyError::NotFound),
+        }
+    }
+
+    fn try_get_property(&self, property: &str) -> Result<TypeRef, PropertyNotFound> {
+        match property {
+            "distance" => Ok(self.distance.get_literal_value().to_data_type_ref()),
+            "unify_winding" => Ok(self.unify_winding.get_literal_value().to_data_type_ref()),
+            _ => Err(PropertyNotFound),
+        }
+    }
+
+    fn get_properties(&self) -> Box<[PropertyMetadata]> {
+        let default = Self::default();
+
+        let info = PropertyMetadata {
+            name: "distance".into(),
+            r#type: default.distance.get_type(),
+            default: default.distance.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Points closer than this are merged into one.".into(),
+        };
+        let info2 = PropertyMetadata {
+            name: "unify_winding".into(),
+            r#type: default.unify_winding.get_type(),
+            default: default
+                .unify_winding
+                .get_literal_value()
+                .to_data_type_value(),
+            configuration: None,
+            documentation: "Whether faces are flipped to match the winding of their neighbours."
+                .into(),
+        };
+        Box::new([info, info2])
+    }
+
+    fn set_property_external(
+        &mut self,
+        index: u8,
+        reference: Reference,
+    ) -> Result<(), SetPropertyError> {
+        match index {
+            0 => self.distance.set_external(reference),
+            1 => self.unify_winding.set_external(reference),
+            _ => return Err(SetPropertyError::WrongIndex),
+        }
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     nodes::{
-        ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
         SocketInterface, StaticNodeMetadata,
     },
     property::{Property, PropertyMetadata},
@@ -47,13 +47,13 @@ impl Node for ExtrudeV1 {
         context: &impl ContextProvider,
         //TODO explore this
         // write_back: [&mut DataTypeValue],
-    ) -> Box<[Option<OwnedDataType>]> {
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
         let input = context.get_reference(input_sockets.unwrap()[0]);
         let input: &Mesh = input.dispatch_ref().unwrap();
         let new_surface = input.clone();
         //let selection: Vec<FaceHandle> = self.query.get_value(context).try_get().unwrap();
         //TODO
-        return Box::new([Some(OwnedDataType::Mesh(Box::new(new_surface)))]);
+        return Ok(Box::new([Some(OwnedDataType::Mesh(Box::new(new_surface)))]));
         /* let mut point_map = HashMap::new();
         let mut point_set: HashSet<PointHandle> = HashSet::new();
         //let mut edge_set = HashSet::new();
@@ -179,7 +179,7 @@ impl Node for ExtrudeV1 {
                 );
                 } */
 
-        Box::new([Some(OwnedDataType::new(new_surface))])
+        Ok(Box::new([Some(OwnedDataType::new(new_surface))]))
     }
 
     /* fn get_sockets(
@@ -286,7 +286,11 @@ impl PropertyInterface for ExtrudeV1 {
         index: u8,
         reference: Reference,
     ) -> Result<(), SetPropertyError> {
-        todo!()
+        match index {
+            0 => Ok(self.query.set_external(reference)),
+            1 => Ok(self.amount.set_external(reference)),
+            _ => Err(SetPropertyError::WrongIndex),
+        }
     }
 
     /* fn try_get_property_script(&self, property: &str) -> Result<String, PropertyNotFound> {
@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    nodes::{
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        SocketInterface, StaticNodeMetadata,
+    },
+    property::{Property, PropertyMetadata},
+    type_system::{
+        OwnedDataType, Reference, TypeRef,
+        data_types::{DataType, DataTypeKind, Int, Mesh, TypeDescriptor},
+    },
+};
+
+const MAX_INPUTS: Int = 8;
+
+/// Combines Meshes and Collections of Meshes into a single Mesh.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JoinMeshesV1 {
+    inputs: Property<Int>,
+}
+
+impl Default for JoinMeshesV1 {
+    fn default() -> Self {
+        Self {
+            inputs: Property::new(2),
+        }
+    }
+}
+
+impl Node for JoinMeshesV1 {
+    fn compute(
+        &self,
+        input_sockets: Option<&[Reference]>,
+        context: &impl ContextProvider,
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
+        let mut meshes: Vec<&Mesh> = Vec::new();
+        for input in input_sockets.unwrap_or_default() {
+            match context.get_reference(*input) {
+                TypeRef::Mesh(mesh) => meshes.push(mesh),
+                TypeRef::Collection(collection) => {
+                    meshes.extend(collection.flatten().into_iter().filter_map(
+                        |value| match value {
+                            OwnedDataType::Mesh(mesh) => Some(&**mesh),
+                            _ => None,
+                        },
+                    ))
+                }
+                other => {
+                    return Err(ComputeError::UnsupportedInput {
+                        node: "JoinMeshes",
+                        found: other.get_type(),
+                    });
+                }
+            }
+        }
+
+        let new_surface = Mesh::join(meshes);
+
+        Ok(Box::new([Some(OwnedDataType::Mesh(Box::new(new_surface)))]))
+    }
+
+    fn node_metadata(&self) -> StaticNodeMetadata {
+        StaticNodeMetadata { color: "#4338ca" }
+    }
+}
+impl SocketInterface for JoinMeshesV1 {
+    /// Every input accepts a Mesh or a Collection of them.
+    fn get_input_sockets(&self) -> Box<[TypeDescriptor]> {
+        let inputs = (*self.inputs.get_literal_value()).clamp(1, MAX_INPUTS);
+        (0..inputs)
+            .map(|_| TypeDescriptor {
+                r#type: DataTypeKind::Omni,
+                mutable: false,
+            })
+            .collect()
+    }
+
+    fn get_output_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::new([TypeDescriptor {
+            r#type: DataTypeKind::Mesh,
+            mutable: true,
+        }])
+    }
+}
+
+impl PropertyInterface for JoinMeshesV1 {
+    fn try_set_property(
+        &mut self,
+        property: &str,
+        value: OwnedDataType,
+    ) -> Result<(), SetPropertyError> {
+        match property {
+            "inputs" => {
+                self.inputs
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+
+            _ => Err(SetPropertyError::NotFound),
+        }
+    }
+
+    fn try_get_property(&self, property: &str) -> Result<TypeRef, PropertyNotFound> {
+        match property {
+            "inputs" => Ok(self.inputs.get_literal_value().to_data_type_ref()),
+            _ => Err(PropertyNotFound),
+        }
+    }
+
+    fn get_properties(&self) -> Box<[PropertyMetadata]> {
+        let default = Self::default();
+
+        let info = PropertyMetadata {
+            name: "inputs".into(),
+            r#type: default.inputs.get_type(),
+            default: default.inputs.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: format!("The number of input sockets, at most {MAX_INPUTS}."),
+        };
+
+        Box::new([info])
+    }
+
+    fn set_property_external(
+        &mut self,
+        _index: u8,
+        _reference: Reference,
+    ) -> Result<(), SetPropertyError> {
+        // The number of sockets has to be known without computing the graph.
+        Err(SetPropertyError::WrongIndex)
+    }
+}
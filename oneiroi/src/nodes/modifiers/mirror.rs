@@ -0,0 +1,147 @@
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    nodes::{
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        SocketInterface, StaticNodeMetadata,
+    },
+    property::{Property, PropertyMetadata},
+    type_system::{
+        OwnedDataType, Reference, TypeRef,
+        data_types::{DataType, DataTypeKind, Float, Mesh, TypeDescriptor},
+    },
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MirrorV1 {
+    axis: Property<Vec3>,
+    offset: Property<Float>,
+    weld: Property<bool>,
+}
+
+impl Default for MirrorV1 {
+    fn default() -> Self {
+        Self {
+            axis: Property::new(Vec3::X),
+            offset: Property::new(0.),
+            weld: Property::new(true),
+        }
+    }
+}
+
+impl Node for MirrorV1 {
+    fn compute(
+        &self,
+        input_sockets: Option<&[Reference]>,
+        context: &impl ContextProvider,
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
+        let input = context.get_reference(input_sockets.unwrap()[0]);
+        let input: &Mesh = input.dispatch_ref().unwrap();
+        let axis = *self.axis.get_value(context);
+        let offset = *self.offset.get_value(context);
+        let weld = *self.weld.get_value(context);
+
+        let new_surface = input.mirror(axis, offset, weld);
+
+        Ok(Box::new([Some(OwnedDataType::Mesh(Box::new(new_surface)))]))
+    }
+
+    fn node_metadata(&self) -> StaticNodeMetadata {
+        StaticNodeMetadata { color: "#4338ca" }
+    }
+}
+impl SocketInterface for MirrorV1 {
+    fn get_input_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::new([TypeDescriptor {
+            r#type: DataTypeKind::Mesh,
+            mutable: true,
+        }])
+    }
+
+    fn get_output_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::new([TypeDescriptor {
+            r#type: DataTypeKind::Mesh,
+            mutable: true,
+        }])
+    }
+}
+
+impl PropertyInterface for MirrorV1 {
+    fn try_set_property(
+        &mut self,
+        property: &str,
+        value: OwnedDataType,
+    ) -> Result<(), SetPropertyError> {
+        match property {
+            "axis" => {
+                self.axis
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "offset" => {
+                self.offset
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "weld" => {
+                self.weld
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+
+            _ => Err(SetPropertyError::NotFound),
+        }
+    }
+
+    fn try_get_property(&self, property: &str) -> Result<TypeRef, PropertyNotFound> {
+        match property {
+            "axis" => Ok(self.axis.get_literal_value().to_data_type_ref()),
+            "offset" => Ok(self.offset.get_literal_value().to_data_type_ref()),
+            "weld" => Ok(self.weld.get_literal_value().to_data_type_ref()),
+            _ => Err(PropertyNotFound),
+        }
+    }
+
+    fn get_properties(&self) -> Box<[PropertyMetadata]> {
+        let default = Self::default();
+
+        let info = PropertyMetadata {
+            name: "axis".into(),
+            r#type: default.axis.get_type(),
+            default: default.axis.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "The normal of the mirror plane.".into(),
+        };
+        let info2 = PropertyMetadata {
+            name: "offset".into(),
+            r#type: default.offset.get_type(),
+            default: default.offset.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Distance of the mirror plane from the origin along the axis.".into(),
+        };
+        let info3 = PropertyMetadata {
+            name: "weld".into(),
+            r#type: default.weld.get_type(),
+            default: default.weld.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Connects both halves at the points lying on the mirror plane.".into(),
+        };
+
+        Box::new([info, info2, info3])
+    }
+
+    fn set_property_external(
+        &mut self,
+        index: u8,
+        reference: Reference,
+    ) -> Result<(), SetPropertyError> {
+        match index {
+            0 => self.axis.set_external(reference),
+            1 => self.offset.set_external(reference),
+            2 => self.weld.set_external(reference),
+            _ => return Err(SetPropertyError::WrongIndex),
+        }
+        Ok(())
+    }
+}
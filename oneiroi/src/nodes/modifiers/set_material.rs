@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     nodes::{
-        ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
         SocketInterface, StaticNodeMetadata,
     },
     property::PropertyMetadata,
@@ -26,7 +26,7 @@ impl Node for SetMaterialV1 {
         context: &impl ContextProvider,
         //TODO explore this
         // write_back: [&mut DataTypeValue],
-    ) -> Box<[Option<OwnedDataType>]> {
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
         let input_mesh = context.get_reference(input_sockets.unwrap()[0]);
         let input_mesh: &Mesh = input_mesh.dispatch_ref().unwrap();
         let mut output_mesh = input_mesh.clone();
@@ -34,7 +34,7 @@ impl Node for SetMaterialV1 {
         let material = input_sockets.unwrap()[1];
         output_mesh.set_material(material);
 
-        Box::new([Some(OwnedDataType::new(output_mesh))])
+        Ok(Box::new([Some(OwnedDataType::new(output_mesh))]))
     }
 
     fn node_metadata(&self) -> StaticNodeMetadata {
@@ -107,9 +107,10 @@ impl PropertyInterface for SetMaterialV1 {
 
     fn set_property_external(
         &mut self,
-        index: u8,
-        reference: Reference,
+        _index: u8,
+        _reference: Reference,
     ) -> Result<(), SetPropertyError> {
-        todo!()
+        // There are no properties which could be exposed.
+        Err(SetPropertyError::WrongIndex)
     }
 }
@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    nodes::{
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        SocketInterface, StaticNodeMetadata,
+    },
+    property::{Property, PropertyMetadata},
+    type_system::{
+        OwnedDataType, Reference, TypeRef,
+        data_types::{DataType, DataTypeKind, Float, Int, Mesh, NormalWeighting, TypeDescriptor},
+    },
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SetShadingV1 {
+    auto_smooth: Property<bool>,
+    angle: Property<Float>,
+    weighting: Property<Int>,
+}
+
+impl Default for SetShadingV1 {
+    fn default() -> Self {
+        Self {
+            auto_smooth: Property::new(true),
+            angle: Property::new(30.),
+            weighting: Property::new(2),
+        }
+    }
+}
+
+impl Node for SetShadingV1 {
+    fn compute(
+        &self,
+        input_sockets: Option<&[Reference]>,
+        context: &impl ContextProvider,
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
+        let input = context.get_reference(input_sockets.unwrap()[0]);
+        let input: &Mesh = input.dispatch_ref().unwrap();
+        // Values driven by other nodes aren't checked when they are set.
+        let weighting = *self.weighting.get_value(context);
+        let weighting =
+            NormalWeighting::from_index(weighting).ok_or(ComputeError::InvalidProperty {
+                node: "SetShading",
+                property: "weighting",
+                value: weighting,
+            })?;
+
+        let mut new_surface = input.clone();
+        if *self.auto_smooth.get_value(context) {
+            new_surface.auto_smooth(self.angle.get_value(context).to_radians());
+        }
+        new_surface.set_normal_weighting(weighting);
+
+        Ok(Box::new([Some(OwnedDataType::Mesh(Box::new(new_surface)))]))
+    }
+
+    fn node_metadata(&self) -> StaticNodeMetadata {
+        StaticNodeMetadata { color: "#4338ca" }
+    }
+}
+impl SocketInterface for SetShadingV1 {
+    fn get_input_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::new([TypeDescriptor {
+            r#type: DataTypeKind::Mesh,
+            mutable: true,
+        }])
+    }
+
+    fn get_output_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::new([TypeDescriptor {
+            r#type: DataTypeKind::Mesh,
+            mutable: true,
+        }])
+    }
+}
+
+impl PropertyInterface for SetShadingV1 {
+    fn try_set_property(
+        &mut self,
+        property: &str,
+        value: OwnedDataType,
+    ) -> Result<(), SetPropertyError> {
+        match property {
+            "auto_smooth" => {
+                self.auto_smooth
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "angle" => {
+                self.angle
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "weighting" => {
+                let weighting = value.dispatch().expect("TODO error handling");
+                if NormalWeighting::from_index(weighting).is_none() {
+                    return Err(SetPropertyError::WrongValue);
+                }
+                self.weighting.set_value(weighting);
+                Ok(())
+            }
+
+            _ => Err(SetPropertyError::NotFound),
+        }
+    }
+
+    fn try_get_property(&self, property: &str) -> Result<TypeRef, PropertyNotFound> {
+        match property {
+            "auto_smooth" => Ok(self.auto_smooth.get_literal_value().to_data_type_ref()),
+            "angle" => Ok(self.angle.get_literal_value().to_data_type_ref()),
+            "weighting" => Ok(self.weighting.get_literal_value().to_data_type_ref()),
+            _ => Err(PropertyNotFound),
+        }
+    }
+
+    fn get_properties(&self) -> Box<[PropertyMetadata]> {
+        let default = Self::default();
+
+        let info = PropertyMetadata {
+            name: "auto_smooth".into(),
+            r#type: default.auto_smooth.get_type(),
+            default: default.auto_smooth.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Whether Edges between faces meeting at a sharp angle are marked hard."
+                .into(),
+        };
+        let info2 = PropertyMetadata {
+            name: "angle".into(),
+            r#type: default.angle.get_type(),
+            default: default.angle.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation:
+                "The angle in degrees above which an Edge is sharp, Edges already hard stay hard."
+                    .into(),
+        };
+        let info3 = PropertyMetadata {
+            name: "weighting".into(),
+            r#type: default.weighting.get_type(),
+            default: default.weighting.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "How the faces around a point are weighted for its normal, 0 uniform, 1 by area or 2 by the angle of their corner.".into(),
+        };
+
+        Box::new([info, info2, info3])
+    }
+
+    fn set_property_external(
+        &mut self,
+        index: u8,
+        reference: Reference,
+    ) -> Result<(), SetPropertyError> {
+        match index {
+            0 => self.auto_smooth.set_external(reference),
+            1 => self.angle.set_external(reference),
+            2 => self.weighting.set_external(reference),
+            _ => return Err(SetPropertyError::WrongIndex),
+        }
+        Ok(())
+    }
+}
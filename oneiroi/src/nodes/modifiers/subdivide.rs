@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    nodes::{
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        SocketInterface, StaticNodeMetadata,
+    },
+    property::{Property, PropertyMetadata},
+    type_system::{
+        OwnedDataType, Reference, TypeRef,
+        data_types::{DataType, DataTypeKind, Int, Mesh, TypeDescriptor},
+    },
+};
+
+/// Every level quadruples the faces so larger values would freeze the editor.
+const MAX_LEVEL: Int = 6;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SubdivideV1 {
+    level: Property<Int>,
+    smooth: Property<bool>,
+}
+
+impl Default for SubdivideV1 {
+    fn default() -> Self {
+        Self {
+            level: Property::new(1),
+            smooth: Property::new(true),
+        }
+    }
+}
+
+impl Node for SubdivideV1 {
+    fn compute(
+        &self,
+        input_sockets: Option<&[Reference]>,
+        context: &impl ContextProvider,
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
+        let input = context.get_reference(input_sockets.unwrap()[0]);
+        let input: &Mesh = input.dispatch_ref().unwrap();
+        let level = (*self.level.get_value(context)).clamp(0, MAX_LEVEL);
+        let smooth = *self.smooth.get_value(context);
+
+        let mut new_surface = input.clone();
+        for _ in 0..level {
+            new_surface = new_surface.subdivide(smooth);
+        }
+
+        Ok(Box::new([Some(OwnedDataType::Mesh(Box::new(new_surface)))]))
+    }
+
+    fn node_metadata(&self) -> StaticNodeMetadata {
+        StaticNodeMetadata { color: "#4338ca" }
+    }
+}
+impl SocketInterface for SubdivideV1 {
+    fn get_input_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::new([TypeDescriptor {
+            r#type: DataTypeKind::Mesh,
+            mutable: true,
+        }])
+    }
+
+    fn get_output_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::new([TypeDescriptor {
+            r#type: DataTypeKind::Mesh,
+            mutable: true,
+        }])
+    }
+}
+
+impl PropertyInterface for SubdivideV1 {
+    fn try_set_property(
+        &mut self,
+        property: &str,
+        value: OwnedDataType,
+    ) -> Result<(), SetPropertyError> {
+        match property {
+            "level" => {
+                self.level
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "smooth" => {
+                self.smooth
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+
+            _ => Err(SetPropertyError::NotFound),
+        }
+    }
+
+    fn try_get_property(&self, property: &str) -> Result<TypeRef, PropertyNotFound> {
+        match property {
+            "level" => Ok(self.level.get_literal_value().to_data_type_ref()),
+            "smooth" => Ok(self.smooth.get_literal_value().to_data_type_ref()),
+            _ => Err(PropertyNotFound),
+        }
+    }
+
+    fn get_properties(&self) -> Box<[PropertyMetadata]> {
+        let default = Self::default();
+
+        let info = PropertyMetadata {
+            name: "level".into(),
+            r#type: default.level.get_type(),
+            default: default.level.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: format!("How often the faces are split, at most {MAX_LEVEL}."),
+        };
+        let info2 = PropertyMetadata {
+            name: "smooth".into(),
+            r#type: default.smooth.get_type(),
+            default: default.smooth.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Smooths with the Loop scheme except along hard Edges, otherwise the shape stays the same.".into(),
+        };
+
+        Box::new([info, info2])
+    }
+
+    fn set_property_external(
+        &mut self,
+        index: u8,
+        reference: Reference,
+    ) -> Result<(), SetPropertyError> {
+        match index {
+            0 => self.level.set_external(reference),
+            1 => self.smooth.set_external(reference),
+            _ => return Err(SetPropertyError::WrongIndex),
+        }
+        Ok(())
+    }
+}
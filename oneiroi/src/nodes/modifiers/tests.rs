@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use glam::Vec3;
+use rustc_hash::FxBuildHasher;
+
+use super::{BooleanV1, JoinMeshesV1, SetShadingV1, UvProjectV1};
+use crate::asset::NodeIndex;
+use crate::nodes::{ComputeError, Node, PropertyInterface, SetPropertyError};
+use crate::type_system::data_types::{DataTypeKind, Mesh};
+use crate::type_system::trait_types::{MeshMut0D, MeshMut2D};
+use crate::type_system::{OwnedDataType, Reference, TypeRef};
+
+type Context = HashMap<NodeIndex, Box<[OwnedDataType]>, FxBuildHasher>;
+
+fn triangle() -> Mesh {
+    let mut mesh = Mesh::default();
+    let points = mesh.add_points([Vec3::ZERO, Vec3::X, Vec3::Y]);
+    mesh.add_tri([points[0], points[1], points[2]]);
+    mesh
+}
+
+fn context(inputs: impl IntoIterator<Item = OwnedDataType>) -> (Context, Box<[Reference]>) {
+    let mut context = Context::default();
+    let references = inputs
+        .into_iter()
+        .enumerate()
+        .map(|(index, input)| {
+            let node = NodeIndex::new(index + 1);
+            context.insert(node, Box::new([input]));
+            Reference::Standard { node, socket: 0 }
+        })
+        .collect();
+    (context, references)
+}
+
+#[test]
+fn join_meshes_rejects_other_inputs() {
+    let join = JoinMeshesV1::default();
+    let (meshes, inputs) = context([
+        OwnedDataType::new(triangle()),
+        OwnedDataType::new(triangle()),
+    ]);
+    let output = join.compute(Some(&inputs), &meshes).unwrap();
+    let mesh: Mesh = output[0].clone().unwrap().dispatch().unwrap();
+    assert_eq!(mesh.face_handles().count(), 2);
+
+    let (invalid, inputs) = context([OwnedDataType::new(triangle()), OwnedDataType::Float(1.)]);
+    assert!(matches!(
+        join.compute(Some(&inputs), &invalid),
+        Err(ComputeError::UnsupportedInput {
+            found: DataTypeKind::Float,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn property_index_maps_to_name() {
+    let mut boolean = BooleanV1::default();
+    boolean
+        .try_set_property_index(0, OwnedDataType::Int(2))
+        .unwrap();
+    assert!(matches!(
+        boolean.try_get_property("mode"),
+        Ok(TypeRef::Int(2))
+    ));
+    assert!(matches!(
+        boolean.try_get_property_index(0),
+        Ok(TypeRef::Int(2))
+    ));
+    assert!(matches!(
+        boolean.try_set_property_index(1, OwnedDataType::Int(0)),
+        Err(SetPropertyError::WrongIndex)
+    ));
+    assert!(matches!(
+        boolean.try_set_property_index(0, OwnedDataType::Int(3)),
+        Err(SetPropertyError::WrongValue)
+    ));
+    assert!(boolean.try_get_property_index(1).is_err());
+}
+
+#[test]
+fn unknown_projection_is_rejected() {
+    let mut uv_project = UvProjectV1::default();
+    assert!(matches!(
+        uv_project.try_set_property("projection", OwnedDataType::Int(4)),
+        Err(SetPropertyError::WrongValue)
+    ));
+    assert!(matches!(
+        uv_project.try_get_property("projection"),
+        Ok(TypeRef::Int(0))
+    ));
+}
+
+#[test]
+fn unknown_weighting_is_rejected() {
+    let mut set_shading = SetShadingV1::default();
+    assert!(matches!(
+        set_shading.try_set_property("weighting", OwnedDataType::Int(-1)),
+        Err(SetPropertyError::WrongValue)
+    ));
+    assert!(
+        set_shading
+            .try_set_property("weighting", OwnedDataType::Int(0))
+            .is_ok()
+    );
+}
@@ -0,0 +1,159 @@
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    nodes::{
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        SocketInterface, StaticNodeMetadata,
+    },
+    property::{Property, PropertyMetadata},
+    type_system::{
+        OwnedDataType, Reference, TypeRef,
+        data_types::{DataType, DataTypeKind, Float, Int, Mesh, TypeDescriptor, UvProjection},
+    },
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UvProjectV1 {
+    projection: Property<Int>,
+    axis: Property<Vec3>,
+    scale: Property<Float>,
+}
+
+impl Default for UvProjectV1 {
+    fn default() -> Self {
+        Self {
+            projection: Property::new(0),
+            axis: Property::new(Vec3::Y),
+            scale: Property::new(1.),
+        }
+    }
+}
+
+impl Node for UvProjectV1 {
+    fn compute(
+        &self,
+        input_sockets: Option<&[Reference]>,
+        context: &impl ContextProvider,
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
+        let input = context.get_reference(input_sockets.unwrap()[0]);
+        let input: &Mesh = input.dispatch_ref().unwrap();
+        // Values driven by other nodes aren't checked when they are set.
+        let projection = *self.projection.get_value(context);
+        let projection =
+            UvProjection::from_index(projection).ok_or(ComputeError::InvalidProperty {
+                node: "UvProject",
+                property: "projection",
+                value: projection,
+            })?;
+        let axis = *self.axis.get_value(context);
+        let scale = *self.scale.get_value(context);
+
+        let mut new_surface = input.clone();
+        new_surface.project_uvs(projection, axis, scale);
+
+        Ok(Box::new([Some(OwnedDataType::Mesh(Box::new(new_surface)))]))
+    }
+
+    fn node_metadata(&self) -> StaticNodeMetadata {
+        StaticNodeMetadata { color: "#4338ca" }
+    }
+}
+impl SocketInterface for UvProjectV1 {
+    fn get_input_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::new([TypeDescriptor {
+            r#type: DataTypeKind::Mesh,
+            mutable: true,
+        }])
+    }
+
+    fn get_output_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::new([TypeDescriptor {
+            r#type: DataTypeKind::Mesh,
+            mutable: true,
+        }])
+    }
+}
+
+impl PropertyInterface for UvProjectV1 {
+    fn try_set_property(
+        &mut self,
+        property: &str,
+        value: OwnedDataType,
+    ) -> Result<(), SetPropertyError> {
+        match property {
+            "projection" => {
+                let projection = value.dispatch().expect("TODO error handling");
+                if UvProjection::from_index(projection).is_none() {
+                    return Err(SetPropertyError::WrongValue);
+                }
+                self.projection.set_value(projection);
+                Ok(())
+            }
+            "axis" => {
+                self.axis
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "scale" => {
+                self.scale
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+
+            _ => Err(SetPropertyError::NotFound),
+        }
+    }
+
+    fn try_get_property(&self, property: &str) -> Result<TypeRef, PropertyNotFound> {
+        match property {
+            "projection" => Ok(self.projection.get_literal_value().to_data_type_ref()),
+            "axis" => Ok(self.axis.get_literal_value().to_data_type_ref()),
+            "scale" => Ok(self.scale.get_literal_value().to_data_type_ref()),
+            _ => Err(PropertyNotFound),
+        }
+    }
+
+    fn get_properties(&self) -> Box<[PropertyMetadata]> {
+        let default = Self::default();
+
+        let info = PropertyMetadata {
+            name: "projection".into(),
+            r#type: default.projection.get_type(),
+            default: default.projection.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "0 planar, 1 box, 2 cylindrical or 3 spherical.".into(),
+        };
+        let info2 = PropertyMetadata {
+            name: "axis".into(),
+            r#type: default.axis.get_type(),
+            default: default.axis.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation:
+                "The direction to project along or around, unused by the box projection.".into(),
+        };
+        let info3 = PropertyMetadata {
+            name: "scale".into(),
+            r#type: default.scale.get_type(),
+            default: default.scale.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "How often the texture repeats per unit, only along the axis for the cylindrical projection and unused by the spherical one.".into(),
+        };
+
+        Box::new([info, info2, info3])
+    }
+
+    fn set_property_external(
+        &mut self,
+        index: u8,
+        reference: Reference,
+    ) -> Result<(), SetPropertyError> {
+        match index {
+            0 => self.projection.set_external(reference),
+            1 => self.axis.set_external(reference),
+            2 => self.scale.set_external(reference),
+            _ => return Err(SetPropertyError::WrongIndex),
+        }
+        Ok(())
+    }
+}
@@ -21,21 +21,36 @@ pub enum Nodes {
 
     //Producers
     BoxV1(Box<BoxV1>),
+    ConeV1(Box<ConeV1>),
     CylinderV1(Box<CylinderV1>),
     DistributePointsV1(Box<DistributePointsV1>),
+    GridV1(Box<GridV1>),
+    IcosphereV1(Box<IcosphereV1>),
     MaterialV1(Box<MaterialV1>),
+    PlaneV1(Box<PlaneV1>),
     PolygonV1(Box<PolygonV1>),
+    SphereV1(Box<SphereV1>),
+    TorusV1(Box<TorusV1>),
 
     //Modifiers
     BevelV1(Box<BevelV1>),
+    BooleanV1(Box<BooleanV1>),
     ExtrudeV1(Box<ExtrudeV1>),
+    JoinMeshesV1(Box<JoinMeshesV1>),
+    MirrorV1(Box<MirrorV1>),
     SetMaterialV1(Box<SetMaterialV1>),
+    SubdivideV1(Box<SubdivideV1>),
+    UvProjectV1(Box<UvProjectV1>),
+    SetShadingV1(Box<SetShadingV1>),
+    CleanupMeshV1(Box<CleanupMeshV1>),
 
     //Transformers
+    ArrayV1(Box<ArrayV1>),
     CreateInstanceV1(Box<CreateInstanceV1>),
     InstancesFromTransformsV1(Box<InstancesFromTransformsV1>),
     SampleCurveV1(Box<SampleCurveV1>),
     SweepV1(Box<SweepV1>),
+    TransformV1(Box<TransformV1>),
 
     //Control Flow
     SocketInput(Box<SocketInputV1>),
@@ -63,6 +78,13 @@ impl Nodes {
         }
     }
 
+    pub(crate) fn as_embedded_instance(&self) -> Option<&AssetInstance> {
+        match self {
+            Nodes::EmbeddedAsset(instance) => Some(instance),
+            _ => None,
+        }
+    }
+
     /* pub(crate) fn set_property_external(
         &mut self,
         index: u8,
@@ -87,12 +109,28 @@ impl Nodes {
 
     //pub fn get_static_metadata(&self) -> StaticNodeMetadata {
 
-    pub(crate) fn from_alias(alias: &str) -> Self {
-        match alias {
+    /// The node with default properties for the name shown in the editor.
+    pub(crate) fn from_alias(alias: &str) -> Option<Self> {
+        let node = match alias {
             "Box" => Nodes::BoxV1(Box::default()),
             "Cylinder" => Nodes::CylinderV1(Box::default()),
+            "Sphere" => Nodes::SphereV1(Box::default()),
+            "Icosphere" => Nodes::IcosphereV1(Box::default()),
+            "Torus" => Nodes::TorusV1(Box::default()),
+            "Cone" => Nodes::ConeV1(Box::default()),
+            "Plane" => Nodes::PlaneV1(Box::default()),
+            "Grid" => Nodes::GridV1(Box::default()),
             "Extrude" => Nodes::ExtrudeV1(Box::default()),
             "Bevel" => Nodes::BevelV1(Box::default()),
+            "Boolean" => Nodes::BooleanV1(Box::default()),
+            "JoinMeshes" => Nodes::JoinMeshesV1(Box::default()),
+            "Mirror" => Nodes::MirrorV1(Box::default()),
+            "UvProject" => Nodes::UvProjectV1(Box::default()),
+            "SetShading" => Nodes::SetShadingV1(Box::default()),
+            "CleanupMesh" => Nodes::CleanupMeshV1(Box::default()),
+            "Subdivide" => Nodes::SubdivideV1(Box::default()),
+            "Transform" => Nodes::TransformV1(Box::default()),
+            "Array" => Nodes::ArrayV1(Box::default()),
             "Input" => Nodes::SocketInput(Box::default()),
             "Output" => Nodes::SocketOutput(Box::default()),
             "CreateInstance" => Nodes::CreateInstanceV1(Box::default()),
@@ -103,8 +141,9 @@ impl Nodes {
             "SetMaterial" => Nodes::SetMaterialV1(Box::default()),
             "Sweep" => Nodes::SweepV1(Box::default()),
             "Polygon" => Nodes::PolygonV1(Box::default()),
-            _ => panic!("This should not panic but instead return an error"),
-        }
+            _ => return None,
+        };
+        Some(node)
     }
 }
 
@@ -128,6 +167,12 @@ impl PropertyInterface for Nodes {
                 asset_instance.try_set_property(property, value)
             }
             Nodes::CylinderV1(cylinder_v1) => cylinder_v1.try_set_property(property, value),
+            Nodes::ConeV1(cone_v1) => cone_v1.try_set_property(property, value),
+            Nodes::GridV1(grid_v1) => grid_v1.try_set_property(property, value),
+            Nodes::IcosphereV1(icosphere_v1) => icosphere_v1.try_set_property(property, value),
+            Nodes::PlaneV1(plane_v1) => plane_v1.try_set_property(property, value),
+            Nodes::SphereV1(sphere_v1) => sphere_v1.try_set_property(property, value),
+            Nodes::TorusV1(torus_v1) => torus_v1.try_set_property(property, value),
             Nodes::CreateInstanceV1(create_instance_v1) => {
                 create_instance_v1.try_set_property(property, value)
             }
@@ -147,6 +192,17 @@ impl PropertyInterface for Nodes {
             Nodes::SweepV1(sweep_v1) => sweep_v1.try_set_property(property, value),
             Nodes::PolygonV1(polygon_v1) => polygon_v1.try_set_property(property, value),
             Nodes::BevelV1(bevel_v1) => bevel_v1.try_set_property(property, value),
+            Nodes::BooleanV1(boolean_v1) => boolean_v1.try_set_property(property, value),
+            Nodes::ArrayV1(array_v1) => array_v1.try_set_property(property, value),
+            Nodes::TransformV1(transform_v1) => transform_v1.try_set_property(property, value),
+            Nodes::SubdivideV1(subdivide_v1) => subdivide_v1.try_set_property(property, value),
+            Nodes::JoinMeshesV1(join_meshes_v1) => join_meshes_v1.try_set_property(property, value),
+            Nodes::MirrorV1(mirror_v1) => mirror_v1.try_set_property(property, value),
+            Nodes::UvProjectV1(uv_project_v1) => uv_project_v1.try_set_property(property, value),
+            Nodes::SetShadingV1(set_shading_v1) => set_shading_v1.try_set_property(property, value),
+            Nodes::CleanupMeshV1(cleanup_mesh_v1) => {
+                cleanup_mesh_v1.try_set_property(property, value)
+            }
         }
     }
 
@@ -159,6 +215,12 @@ impl PropertyInterface for Nodes {
             Nodes::SocketOutput(socket_output_v1) => socket_output_v1.try_get_property(property),
             Nodes::EmbeddedAsset(asset_instance) => asset_instance.try_get_property(property),
             Nodes::CylinderV1(cylinder_v1) => cylinder_v1.try_get_property(property),
+            Nodes::ConeV1(cone_v1) => cone_v1.try_get_property(property),
+            Nodes::GridV1(grid_v1) => grid_v1.try_get_property(property),
+            Nodes::IcosphereV1(icosphere_v1) => icosphere_v1.try_get_property(property),
+            Nodes::PlaneV1(plane_v1) => plane_v1.try_get_property(property),
+            Nodes::SphereV1(sphere_v1) => sphere_v1.try_get_property(property),
+            Nodes::TorusV1(torus_v1) => torus_v1.try_get_property(property),
             Nodes::CreateInstanceV1(create_instance_v1) => {
                 create_instance_v1.try_get_property(property)
             }
@@ -174,6 +236,15 @@ impl PropertyInterface for Nodes {
             Nodes::SweepV1(sweep_v1) => sweep_v1.try_get_property(property),
             Nodes::PolygonV1(polygon_v1) => polygon_v1.try_get_property(property),
             Nodes::BevelV1(bevel_v1) => bevel_v1.try_get_property(property),
+            Nodes::BooleanV1(boolean_v1) => boolean_v1.try_get_property(property),
+            Nodes::ArrayV1(array_v1) => array_v1.try_get_property(property),
+            Nodes::TransformV1(transform_v1) => transform_v1.try_get_property(property),
+            Nodes::SubdivideV1(subdivide_v1) => subdivide_v1.try_get_property(property),
+            Nodes::JoinMeshesV1(join_meshes_v1) => join_meshes_v1.try_get_property(property),
+            Nodes::MirrorV1(mirror_v1) => mirror_v1.try_get_property(property),
+            Nodes::UvProjectV1(uv_project_v1) => uv_project_v1.try_get_property(property),
+            Nodes::SetShadingV1(set_shading_v1) => set_shading_v1.try_get_property(property),
+            Nodes::CleanupMeshV1(cleanup_mesh_v1) => cleanup_mesh_v1.try_get_property(property),
         }
     }
 
@@ -186,6 +257,12 @@ impl PropertyInterface for Nodes {
             Nodes::SocketOutput(socket_output_v1) => socket_output_v1.get_properties(),
             Nodes::EmbeddedAsset(asset_instance) => asset_instance.get_properties(),
             Nodes::CylinderV1(cylinder_v1) => cylinder_v1.get_properties(),
+            Nodes::ConeV1(cone_v1) => cone_v1.get_properties(),
+            Nodes::GridV1(grid_v1) => grid_v1.get_properties(),
+            Nodes::IcosphereV1(icosphere_v1) => icosphere_v1.get_properties(),
+            Nodes::PlaneV1(plane_v1) => plane_v1.get_properties(),
+            Nodes::SphereV1(sphere_v1) => sphere_v1.get_properties(),
+            Nodes::TorusV1(torus_v1) => torus_v1.get_properties(),
             Nodes::CreateInstanceV1(create_instance_v1) => create_instance_v1.get_properties(),
             Nodes::DistributePointsV1(distribute_points_v1) => {
                 distribute_points_v1.get_properties()
@@ -199,21 +276,18 @@ impl PropertyInterface for Nodes {
             Nodes::SweepV1(sweep_v1) => sweep_v1.get_properties(),
             Nodes::PolygonV1(polygon_v1) => polygon_v1.get_properties(),
             Nodes::BevelV1(bevel_v1) => bevel_v1.get_properties(),
+            Nodes::BooleanV1(boolean_v1) => boolean_v1.get_properties(),
+            Nodes::ArrayV1(array_v1) => array_v1.get_properties(),
+            Nodes::TransformV1(transform_v1) => transform_v1.get_properties(),
+            Nodes::SubdivideV1(subdivide_v1) => subdivide_v1.get_properties(),
+            Nodes::JoinMeshesV1(join_meshes_v1) => join_meshes_v1.get_properties(),
+            Nodes::MirrorV1(mirror_v1) => mirror_v1.get_properties(),
+            Nodes::UvProjectV1(uv_project_v1) => uv_project_v1.get_properties(),
+            Nodes::SetShadingV1(set_shading_v1) => set_shading_v1.get_properties(),
+            Nodes::CleanupMeshV1(cleanup_mesh_v1) => cleanup_mesh_v1.get_properties(),
         }
     }
 
-    fn try_set_property_index(
-        &mut self,
-        index: u8,
-        value: OwnedDataType,
-    ) -> Result<(), SetPropertyError> {
-        todo!()
-    }
-
-    fn try_get_property_index(&self, index: u8) -> Result<TypeRef, PropertyNotFound> {
-        todo!()
-    }
-
     fn set_property_external(
         &mut self,
         index: u8,
@@ -223,6 +297,12 @@ impl PropertyInterface for Nodes {
             Nodes::Expose => unreachable!(),
             Nodes::BoxV1(node) => node.set_property_external(index, reference),
             Nodes::CylinderV1(node) => node.set_property_external(index, reference),
+            Nodes::ConeV1(node) => node.set_property_external(index, reference),
+            Nodes::GridV1(node) => node.set_property_external(index, reference),
+            Nodes::IcosphereV1(node) => node.set_property_external(index, reference),
+            Nodes::PlaneV1(node) => node.set_property_external(index, reference),
+            Nodes::SphereV1(node) => node.set_property_external(index, reference),
+            Nodes::TorusV1(node) => node.set_property_external(index, reference),
             Nodes::DistributePointsV1(node) => node.set_property_external(index, reference),
             Nodes::MaterialV1(node) => node.set_property_external(index, reference),
             Nodes::ExtrudeV1(node) => node.set_property_external(index, reference),
@@ -236,6 +316,27 @@ impl PropertyInterface for Nodes {
             Nodes::SweepV1(node) => node.set_property_external(index, reference),
             Nodes::PolygonV1(node) => node.set_property_external(index, reference),
             Nodes::BevelV1(bevel_v1) => bevel_v1.set_property_external(index, reference),
+            Nodes::BooleanV1(boolean_v1) => boolean_v1.set_property_external(index, reference),
+            Nodes::ArrayV1(array_v1) => array_v1.set_property_external(index, reference),
+            Nodes::TransformV1(transform_v1) => {
+                transform_v1.set_property_external(index, reference)
+            }
+            Nodes::SubdivideV1(subdivide_v1) => {
+                subdivide_v1.set_property_external(index, reference)
+            }
+            Nodes::JoinMeshesV1(join_meshes_v1) => {
+                join_meshes_v1.set_property_external(index, reference)
+            }
+            Nodes::MirrorV1(mirror_v1) => mirror_v1.set_property_external(index, reference),
+            Nodes::UvProjectV1(uv_project_v1) => {
+                uv_project_v1.set_property_external(index, reference)
+            }
+            Nodes::SetShadingV1(set_shading_v1) => {
+                set_shading_v1.set_property_external(index, reference)
+            }
+            Nodes::CleanupMeshV1(cleanup_mesh_v1) => {
+                cleanup_mesh_v1.set_property_external(index, reference)
+            }
         }
     }
 
@@ -293,7 +394,7 @@ impl Node for Nodes {
         &self,
         input_sockets: Option<&[Reference]>,
         context: &impl ContextProvider,
-    ) -> Box<[Option<OwnedDataType>]> {
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
         match self {
             Nodes::Expose => unreachable!(),
             Nodes::SocketInput(_) => unreachable!(),
@@ -302,6 +403,12 @@ impl Node for Nodes {
             Nodes::ExtrudeV1(extrude_v1) => extrude_v1.compute(input_sockets, context),
             Nodes::EmbeddedAsset(asset_template) => asset_template.compute(input_sockets, context),
             Nodes::CylinderV1(cylinder_v1) => cylinder_v1.compute(input_sockets, context),
+            Nodes::ConeV1(cone_v1) => cone_v1.compute(input_sockets, context),
+            Nodes::GridV1(grid_v1) => grid_v1.compute(input_sockets, context),
+            Nodes::IcosphereV1(icosphere_v1) => icosphere_v1.compute(input_sockets, context),
+            Nodes::PlaneV1(plane_v1) => plane_v1.compute(input_sockets, context),
+            Nodes::SphereV1(sphere_v1) => sphere_v1.compute(input_sockets, context),
+            Nodes::TorusV1(torus_v1) => torus_v1.compute(input_sockets, context),
             Nodes::CreateInstanceV1(create_instance_v1) => {
                 create_instance_v1.compute(input_sockets, context)
             }
@@ -321,6 +428,17 @@ impl Node for Nodes {
             Nodes::SweepV1(sweep_v1) => sweep_v1.compute(input_sockets, context),
             Nodes::PolygonV1(node) => node.compute(input_sockets, context),
             Nodes::BevelV1(bevel_v1) => bevel_v1.compute(input_sockets, context),
+            Nodes::BooleanV1(boolean_v1) => boolean_v1.compute(input_sockets, context),
+            Nodes::ArrayV1(array_v1) => array_v1.compute(input_sockets, context),
+            Nodes::TransformV1(transform_v1) => transform_v1.compute(input_sockets, context),
+            Nodes::SubdivideV1(subdivide_v1) => subdivide_v1.compute(input_sockets, context),
+            Nodes::JoinMeshesV1(join_meshes_v1) => join_meshes_v1.compute(input_sockets, context),
+            Nodes::MirrorV1(mirror_v1) => mirror_v1.compute(input_sockets, context),
+            Nodes::UvProjectV1(uv_project_v1) => uv_project_v1.compute(input_sockets, context),
+            Nodes::SetShadingV1(set_shading_v1) => set_shading_v1.compute(input_sockets, context),
+            Nodes::CleanupMeshV1(cleanup_mesh_v1) => {
+                cleanup_mesh_v1.compute(input_sockets, context)
+            }
         }
     }
 
@@ -329,6 +447,12 @@ impl Node for Nodes {
             Nodes::Expose => unimplemented!(),
             Nodes::BoxV1(var) => var.node_metadata(),
             Nodes::CylinderV1(var) => var.node_metadata(),
+            Nodes::ConeV1(var) => var.node_metadata(),
+            Nodes::GridV1(var) => var.node_metadata(),
+            Nodes::IcosphereV1(var) => var.node_metadata(),
+            Nodes::PlaneV1(var) => var.node_metadata(),
+            Nodes::SphereV1(var) => var.node_metadata(),
+            Nodes::TorusV1(var) => var.node_metadata(),
             Nodes::ExtrudeV1(var) => var.node_metadata(),
             Nodes::SocketInput(var) => var.node_metadata(),
             Nodes::SocketOutput(var) => var.node_metadata(),
@@ -344,6 +468,15 @@ impl Node for Nodes {
             Nodes::SweepV1(sweep_v1) => sweep_v1.node_metadata(),
             Nodes::PolygonV1(polygon_v1) => polygon_v1.node_metadata(),
             Nodes::BevelV1(bevel_v1) => bevel_v1.node_metadata(),
+            Nodes::BooleanV1(boolean_v1) => boolean_v1.node_metadata(),
+            Nodes::ArrayV1(array_v1) => array_v1.node_metadata(),
+            Nodes::TransformV1(transform_v1) => transform_v1.node_metadata(),
+            Nodes::SubdivideV1(subdivide_v1) => subdivide_v1.node_metadata(),
+            Nodes::JoinMeshesV1(join_meshes_v1) => join_meshes_v1.node_metadata(),
+            Nodes::MirrorV1(mirror_v1) => mirror_v1.node_metadata(),
+            Nodes::UvProjectV1(uv_project_v1) => uv_project_v1.node_metadata(),
+            Nodes::SetShadingV1(set_shading_v1) => set_shading_v1.node_metadata(),
+            Nodes::CleanupMeshV1(cleanup_mesh_v1) => cleanup_mesh_v1.node_metadata(),
         }
     }
     /* fn parse_sockets(input_sockets: Vec<&DataTypeInstance>) -> Result<Self::InputSockets, ()> {
@@ -372,6 +505,12 @@ impl SocketInterface for Nodes {
             Nodes::SocketOutput(socket_output_v1) => socket_output_v1.get_output_sockets(),
             Nodes::EmbeddedAsset(asset_template) => asset_template.get_output_sockets(),
             Nodes::CylinderV1(cylinder_v1) => cylinder_v1.get_output_sockets(),
+            Nodes::ConeV1(cone_v1) => cone_v1.get_output_sockets(),
+            Nodes::GridV1(grid_v1) => grid_v1.get_output_sockets(),
+            Nodes::IcosphereV1(icosphere_v1) => icosphere_v1.get_output_sockets(),
+            Nodes::PlaneV1(plane_v1) => plane_v1.get_output_sockets(),
+            Nodes::SphereV1(sphere_v1) => sphere_v1.get_output_sockets(),
+            Nodes::TorusV1(torus_v1) => torus_v1.get_output_sockets(),
             Nodes::CreateInstanceV1(create_instance_v1) => create_instance_v1.get_output_sockets(),
             Nodes::DistributePointsV1(distribute_points_v1) => {
                 distribute_points_v1.get_output_sockets()
@@ -385,6 +524,15 @@ impl SocketInterface for Nodes {
             Nodes::SweepV1(node) => node.get_output_sockets(),
             Nodes::PolygonV1(polygon_v1) => polygon_v1.get_output_sockets(),
             Nodes::BevelV1(bevel_v1) => bevel_v1.get_output_sockets(),
+            Nodes::BooleanV1(boolean_v1) => boolean_v1.get_output_sockets(),
+            Nodes::ArrayV1(array_v1) => array_v1.get_output_sockets(),
+            Nodes::TransformV1(transform_v1) => transform_v1.get_output_sockets(),
+            Nodes::SubdivideV1(subdivide_v1) => subdivide_v1.get_output_sockets(),
+            Nodes::JoinMeshesV1(join_meshes_v1) => join_meshes_v1.get_output_sockets(),
+            Nodes::MirrorV1(mirror_v1) => mirror_v1.get_output_sockets(),
+            Nodes::UvProjectV1(uv_project_v1) => uv_project_v1.get_output_sockets(),
+            Nodes::SetShadingV1(set_shading_v1) => set_shading_v1.get_output_sockets(),
+            Nodes::CleanupMeshV1(cleanup_mesh_v1) => cleanup_mesh_v1.get_output_sockets(),
         }
     }
 
@@ -397,6 +545,12 @@ impl SocketInterface for Nodes {
             Nodes::SocketOutput(socket_output_v1) => socket_output_v1.get_input_sockets(),
             Nodes::EmbeddedAsset(asset_template) => asset_template.get_input_sockets(),
             Nodes::CylinderV1(cylinder_v1) => cylinder_v1.get_input_sockets(),
+            Nodes::ConeV1(cone_v1) => cone_v1.get_input_sockets(),
+            Nodes::GridV1(grid_v1) => grid_v1.get_input_sockets(),
+            Nodes::IcosphereV1(icosphere_v1) => icosphere_v1.get_input_sockets(),
+            Nodes::PlaneV1(plane_v1) => plane_v1.get_input_sockets(),
+            Nodes::SphereV1(sphere_v1) => sphere_v1.get_input_sockets(),
+            Nodes::TorusV1(torus_v1) => torus_v1.get_input_sockets(),
             Nodes::CreateInstanceV1(create_instance_v1) => create_instance_v1.get_input_sockets(),
             Nodes::DistributePointsV1(distribute_points_v1) => {
                 distribute_points_v1.get_input_sockets()
@@ -410,6 +564,15 @@ impl SocketInterface for Nodes {
             Nodes::SweepV1(node) => node.get_input_sockets(),
             Nodes::PolygonV1(polygon_v1) => polygon_v1.get_input_sockets(),
             Nodes::BevelV1(bevel_v1) => bevel_v1.get_input_sockets(),
+            Nodes::BooleanV1(boolean_v1) => boolean_v1.get_input_sockets(),
+            Nodes::ArrayV1(array_v1) => array_v1.get_input_sockets(),
+            Nodes::TransformV1(transform_v1) => transform_v1.get_input_sockets(),
+            Nodes::SubdivideV1(subdivide_v1) => subdivide_v1.get_input_sockets(),
+            Nodes::JoinMeshesV1(join_meshes_v1) => join_meshes_v1.get_input_sockets(),
+            Nodes::MirrorV1(mirror_v1) => mirror_v1.get_input_sockets(),
+            Nodes::UvProjectV1(uv_project_v1) => uv_project_v1.get_input_sockets(),
+            Nodes::SetShadingV1(set_shading_v1) => set_shading_v1.get_input_sockets(),
+            Nodes::CleanupMeshV1(cleanup_mesh_v1) => cleanup_mesh_v1.get_input_sockets(),
         }
     }
 }
@@ -1,13 +1,27 @@
 mod r#box;
+mod cone;
 mod cylinder;
 mod distribute_points;
+mod grid;
 mod helix;
+mod icosphere;
 mod material;
+mod plane;
 mod polygon;
+mod sphere;
+#[cfg(test)]
+mod tests;
+mod torus;
 
 pub use r#box::BoxV1;
+pub use cone::ConeV1;
 pub use cylinder::CylinderV1;
 pub use distribute_points::DistributePointsV1;
+pub use grid::GridV1;
 pub use helix::HelixV1;
+pub use icosphere::IcosphereV1;
 pub use material::MaterialV1;
+pub use plane::PlaneV1;
 pub use polygon::PolygonV1;
+pub use sphere::SphereV1;
+pub use torus::TorusV1;
@@ -3,13 +3,13 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     nodes::{
-        ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
         SocketInterface, SocketMetadata, StaticNodeMetadata,
     },
     property::{Property, PropertyMetadata},
     type_system::{
         OwnedDataType, Reference, TypeRef,
-        data_types::{DataType, DataTypeKind, Mesh, TypeDescriptor},
+        data_types::{DataType, DataTypeKind, Mesh, TypeDescriptor, box_uv},
         trait_types::{MeshMut0D, MeshMut2D},
     },
 };
@@ -143,7 +143,7 @@ impl Node for BoxV1 {
         context: &impl ContextProvider,
         //TODO explore this
         // write_back: [&mut DataTypeValue],
-    ) -> Box<[Option<OwnedDataType>]> {
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
         let mut surface = Mesh::default();
 
         let size = self.size.get_value(context) * 0.5;
@@ -176,7 +176,20 @@ impl Node for BoxV1 {
 
         //surface.set_all_edges_hard();
 
-        Box::new([Some(OwnedDataType::Mesh(Box::new(surface)))])
+        // Every side shows the whole texture.
+        let full_size = (size * 2.0).max(Vec3::splat(f32::EPSILON));
+        let uvs = surface
+            .face_handles()
+            .flat_map(|face| {
+                let normal = surface.face_normal(face);
+                surface
+                    .points_in_face(face)
+                    .map(|point| box_uv(surface.position(point) / full_size, normal) + 0.5)
+            })
+            .collect::<Vec<_>>();
+        surface.set_uvs(uvs);
+
+        Ok(Box::new([Some(OwnedDataType::Mesh(Box::new(surface)))]))
     }
 
     /* fn get_sockets(
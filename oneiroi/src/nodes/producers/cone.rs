@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    nodes::{
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        SocketInterface, StaticNodeMetadata,
+    },
+    property::{Property, PropertyMetadata},
+    type_system::{
+        OwnedDataType, Reference, TypeRef,
+        data_types::{DataType, DataTypeKind, Mesh, TypeDescriptor},
+    },
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConeV1 {
+    bottom_radius: Property<f32>,
+    top_radius: Property<f32>,
+    height: Property<f32>,
+    segments: Property<i64>,
+    has_caps: Property<bool>,
+}
+impl Default for ConeV1 {
+    fn default() -> Self {
+        Self {
+            bottom_radius: Property::new(0.5),
+            top_radius: Property::new(0.0),
+            height: Property::new(2.0),
+            segments: Property::new(6),
+            has_caps: Property::new(true),
+        }
+    }
+}
+
+impl PropertyInterface for ConeV1 {
+    fn try_set_property(
+        &mut self,
+        property: &str,
+        value: OwnedDataType,
+    ) -> Result<(), SetPropertyError> {
+        match property {
+            "bottom_radius" => {
+                self.bottom_radius
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "top_radius" => {
+                self.top_radius
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "height" => {
+                self.height
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "segments" => {
+                self.segments
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "has_caps" => {
+                self.has_caps
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+
+            _ => Err(SetPropertyError::NotFound),
+        }
+    }
+
+    fn try_get_property(&self, property: &str) -> Result<TypeRef, PropertyNotFound> {
+        match property {
+            "bottom_radius" => Ok(self.bottom_radius.get_literal_value().to_data_type_ref()),
+            "top_radius" => Ok(self.top_radius.get_literal_value().to_data_type_ref()),
+            "height" => Ok(self.height.get_literal_value().to_data_type_ref()),
+            "segments" => Ok(self.segments.get_literal_value().to_data_type_ref()),
+            "has_caps" => Ok(self.has_caps.get_literal_value().to_data_type_ref()),
+            _ => Err(PropertyNotFound),
+        }
+    }
+
+    fn get_properties(&self) -> Box<[PropertyMetadata]> {
+        let default = Self::default();
+
+        let info = PropertyMetadata {
+            name: "bottom_radius".into(),
+            r#type: default.bottom_radius.get_type(),
+            default: default
+                .bottom_radius
+                .get_literal_value()
+                .to_data_type_value(),
+            configuration: None,
+            documentation: "Radius at the base, zero ends it in a point.".into(),
+        };
+        let info2 = PropertyMetadata {
+            name: "top_radius".into(),
+            r#type: default.top_radius.get_type(),
+            default: default.top_radius.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Radius at the top, zero ends it in a point.".into(),
+        };
+        let info3 = PropertyMetadata {
+            name: "height".into(),
+            r#type: default.height.get_type(),
+            default: default.height.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Distance between the base and the top.".into(),
+        };
+        let info4 = PropertyMetadata {
+            name: "segments".into(),
+            r#type: default.segments.get_type(),
+            default: default.segments.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Amount of segments around the Y axis, at least 3.".into(),
+        };
+        let info5 = PropertyMetadata {
+            name: "has_caps".into(),
+            r#type: default.has_caps.get_type(),
+            default: default.has_caps.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Closes the sides which do not end in a point.".into(),
+        };
+
+        Box::new([info, info2, info3, info4, info5])
+    }
+
+    fn set_property_external(
+        &mut self,
+        index: u8,
+        reference: Reference,
+    ) -> Result<(), SetPropertyError> {
+        match index {
+            0 => self.bottom_radius.set_external(reference),
+            1 => self.top_radius.set_external(reference),
+            2 => self.height.set_external(reference),
+            3 => self.segments.set_external(reference),
+            4 => self.has_caps.set_external(reference),
+            _ => return Err(SetPropertyError::WrongIndex),
+        }
+        Ok(())
+    }
+}
+
+impl Node for ConeV1 {
+    fn compute(
+        &self,
+        _input_sockets: Option<&[Reference]>,
+        context: &impl ContextProvider,
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
+        let bottom_radius = *self.bottom_radius.get_value(context);
+        let top_radius = *self.top_radius.get_value(context);
+        let height = *self.height.get_value(context);
+        let segments = *self.segments.get_value(context);
+        let has_caps = *self.has_caps.get_value(context);
+
+        let surface = Mesh::cone(
+            bottom_radius,
+            top_radius,
+            height,
+            segments.max(0) as usize,
+            has_caps,
+        );
+
+        Ok(Box::new([Some(OwnedDataType::new(surface))]))
+    }
+
+    fn node_metadata(&self) -> StaticNodeMetadata {
+        StaticNodeMetadata { color: "#15803d" }
+    }
+}
+impl SocketInterface for ConeV1 {
+    fn get_input_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::default()
+    }
+
+    fn get_output_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::new([TypeDescriptor {
+            r#type: DataTypeKind::Mesh,
+            mutable: false,
+        }])
+    }
+}
@@ -6,13 +6,13 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     nodes::{
-        ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
         SocketInterface, StaticNodeMetadata,
     },
     property::{Property, PropertyMetadata},
     type_system::{
         OwnedDataType, Reference, TypeRef,
-        data_types::{DataType, DataTypeKind, Mesh, TypeDescriptor},
+        data_types::{DataType, DataTypeKind, Mesh, TypeDescriptor, UvProjection},
         trait_types::MeshMut2D,
     },
 };
@@ -189,7 +189,7 @@ impl Node for CylinderV1 {
         context: &impl ContextProvider,
         //TODO explore this
         // write_back: [&mut DataTypeValue],
-    ) -> Box<[Option<OwnedDataType>]> {
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
         let mut surface = Mesh::default();
         let segments = *self.segments.get_value(context);
         let height = *self.height.get_value(context); // can also / 2 and add a origin point
@@ -226,6 +226,9 @@ impl Node for CylinderV1 {
             point_handles[1],
         ]);
         surface.add_triangle_strip_with_points(point_handles);
+        // The texture wraps around once and covers the whole height.
+        let uv_scale = if height == 0.0 { 0.0 } else { 1.0 / height };
+        surface.project_uvs(UvProjection::Cylindrical, Vec3::Y, uv_scale);
         /* let vertical_and_diagonal_edges =
             surface.add_edge_strip(point_handles.iter().copied().chain([point_handles[0]]));
         let top_edges = surface.add_edge_strip(
@@ -306,7 +309,7 @@ impl Node for CylinderV1 {
                 surface.add_tri(face_edges);
             }
         } */
-        Box::new([Some(OwnedDataType::new(surface))])
+        Ok(Box::new([Some(OwnedDataType::new(surface))]))
     }
 
     /* fn get_sockets(
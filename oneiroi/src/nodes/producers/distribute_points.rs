@@ -1,27 +1,60 @@
-use glam::Vec3;
+use std::f32::consts::TAU;
+
+use glam::{Affine3A, Quat, Vec3};
 use serde::{Deserialize, Serialize};
 
 use crate::{
     nodes::{
-        ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
         SocketInterface, StaticNodeMetadata,
     },
     property::{Property, PropertyMetadata},
+    random::Random,
     type_system::{
         OwnedDataType, Reference, TypeRef,
-        data_types::{Collection, DataType, DataTypeKind, TypeDescriptor},
+        data_types::{Collection, DataType, DataTypeKind, Float, Int, Mesh, TypeDescriptor},
     },
 };
 
+/// Scatters Transforms over the surface of a Mesh.
+/// Files written before the Mesh input existed use the defaults for the new properties.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DistributePointsV1 {
     amount: Property<i64>,
+    #[serde(default = "default_seed")]
+    seed: Property<Int>,
+    #[serde(default = "default_min_distance")]
+    min_distance: Property<Float>,
+    #[serde(default = "default_true")]
+    align_to_normal: Property<bool>,
+    #[serde(default = "default_false")]
+    random_rotation: Property<bool>,
+}
+
+fn default_seed() -> Property<Int> {
+    Property::new(0)
+}
+
+fn default_min_distance() -> Property<Float> {
+    Property::new(0.)
+}
+
+fn default_true() -> Property<bool> {
+    Property::new(true)
+}
+
+fn default_false() -> Property<bool> {
+    Property::new(false)
 }
 
 impl Default for DistributePointsV1 {
     fn default() -> Self {
         Self {
             amount: Property::new(5),
+            seed: default_seed(),
+            min_distance: default_min_distance(),
+            align_to_normal: default_true(),
+            random_rotation: default_false(),
         }
     }
 }
@@ -38,6 +71,26 @@ impl PropertyInterface for DistributePointsV1 {
                     .set_value(value.dispatch().expect("TODO error handling"));
                 Ok(())
             }
+            "seed" => {
+                self.seed
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "min_distance" => {
+                self.min_distance
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "align_to_normal" => {
+                self.align_to_normal
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "random_rotation" => {
+                self.random_rotation
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
 
             _ => {
                 println!("called set_prop with {:?}", property);
@@ -48,6 +101,10 @@ impl PropertyInterface for DistributePointsV1 {
     fn try_get_property(&self, property: &str) -> Result<TypeRef, PropertyNotFound> {
         match property {
             "amount" => Ok(self.amount.get_literal_value().to_data_type_ref()),
+            "seed" => Ok(self.seed.get_literal_value().to_data_type_ref()),
+            "min_distance" => Ok(self.min_distance.get_literal_value().to_data_type_ref()),
+            "align_to_normal" => Ok(self.align_to_normal.get_literal_value().to_data_type_ref()),
+            "random_rotation" => Ok(self.random_rotation.get_literal_value().to_data_type_ref()),
 
             _ => {
                 //println!("called get_prop with {:?}", property);
@@ -57,8 +114,6 @@ impl PropertyInterface for DistributePointsV1 {
     }
 
     fn get_properties(&self) -> Box<[PropertyMetadata]> {
-        println!("Get property called on Box");
-
         //TODO this can be most likely optimized
         let default = Self::default();
 
@@ -70,8 +125,45 @@ impl PropertyInterface for DistributePointsV1 {
             //configuration: default.size.get_configuration().to_owned(),
             documentation: "".into(),
         };
+        let info2 = PropertyMetadata {
+            name: "seed".into(),
+            r#type: default.seed.get_type(),
+            default: default.seed.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "The same seed always places the points at the same positions.".into(),
+        };
+        let info3 = PropertyMetadata {
+            name: "min_distance".into(),
+            r#type: default.min_distance.get_type(),
+            default: default
+                .min_distance
+                .get_literal_value()
+                .to_data_type_value(),
+            configuration: None,
+            documentation: "Points closer than this are discarded, 0 disables the check.".into(),
+        };
+        let info4 = PropertyMetadata {
+            name: "align_to_normal".into(),
+            r#type: default.align_to_normal.get_type(),
+            default: default
+                .align_to_normal
+                .get_literal_value()
+                .to_data_type_value(),
+            configuration: None,
+            documentation: "Rotates the up axis of each point onto the face normal.".into(),
+        };
+        let info5 = PropertyMetadata {
+            name: "random_rotation".into(),
+            r#type: default.random_rotation.get_type(),
+            default: default
+                .random_rotation
+                .get_literal_value()
+                .to_data_type_value(),
+            configuration: None,
+            documentation: "Randomly rotates each point around its up axis.".into(),
+        };
 
-        Box::new([info])
+        Box::new([info, info2, info3, info4, info5])
     }
 
     fn try_set_property_index(
@@ -91,24 +183,15 @@ impl PropertyInterface for DistributePointsV1 {
         index: u8,
         reference: Reference,
     ) -> Result<(), SetPropertyError> {
-        todo!()
+        match index {
+            0 => Ok(self.amount.set_external(reference)),
+            1 => Ok(self.seed.set_external(reference)),
+            2 => Ok(self.min_distance.set_external(reference)),
+            3 => Ok(self.align_to_normal.set_external(reference)),
+            4 => Ok(self.random_rotation.set_external(reference)),
+            _ => Err(SetPropertyError::WrongIndex),
+        }
     }
-
-    /* fn try_get_property_script(&self, property: &str) -> Result<String, PropertyNotFound> {
-        let string = match property {
-            "amount" => match &self.amount {
-                Property::Script(oneiroi_script) => oneiroi_script.get_string(),
-                Property::Value(v) => v.generate_script(),
-            },
-
-            _ => return Err(PropertyNotFound),
-        };
-        Ok(string)
-    } */
-
-    /* fn try_get_property_metadata(&self, property: &str) -> Result<PropertyMetadata, ()> {
-        todo!()
-    } */
 }
 
 impl Node for DistributePointsV1 {
@@ -118,31 +201,45 @@ impl Node for DistributePointsV1 {
         context: &impl ContextProvider,
         //TODO explore this
         // write_back: [&mut DataTypeValue],
-    ) -> Box<[Option<OwnedDataType>]> {
-        let mut points = Collection::new(DataTypeKind::Vec3);
-
-        points.push(OwnedDataType::Vec3(Vec3::new(0.0, 0.0, 0.0)));
-        points.push(OwnedDataType::Vec3(Vec3::new(3.0, 0.0, 0.0)));
-        points.push(OwnedDataType::Vec3(Vec3::new(1.0, 1.0, 1.0)));
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
+        let input = context.get_reference(input_sockets.unwrap()[0]);
+        let input: &Mesh = input.dispatch_ref().unwrap();
+
+        let amount = (*self.amount.get_value(context)).max(0) as usize;
+        let seed = *self.seed.get_value(context) as u64;
+        let min_distance = self.min_distance.get_value(context).max(0.);
+        let align_to_normal = *self.align_to_normal.get_value(context);
+        let random_rotation = *self.random_rotation.get_value(context);
+
+        // The rotations use their own sequence so toggling them doesn't move the points.
+        let mut random = Random::new(seed.wrapping_add(1));
+        let mut points = Collection::new(DataTypeKind::Transform);
+        for sample in input.sample_surface(amount, seed, min_distance) {
+            let mut rotation = if align_to_normal {
+                Quat::from_rotation_arc(Vec3::Y, sample.normal)
+            } else {
+                Quat::IDENTITY
+            };
+            if random_rotation {
+                rotation *= Quat::from_rotation_y(random.next_f32() * TAU);
+            }
+            let transform = Affine3A::from_rotation_translation(rotation, sample.position);
+            points.push(OwnedDataType::Transform(Box::new(transform)));
+        }
 
-        Box::new([Some(OwnedDataType::new(points))])
+        Ok(Box::new([Some(OwnedDataType::new(points))]))
     }
 
-    /* fn get_sockets(
-        &self,
-    ) -> (
-        Vec<crate::data_types::DataTypeType>,
-        Vec<crate::data_types::DataTypeType>,
-    ) {
-        (Vec::new(), vec![DataTypeType::Mesh])
-    } */
     fn node_metadata(&self) -> StaticNodeMetadata {
         StaticNodeMetadata { color: "#15803d" }
     }
 }
 impl SocketInterface for DistributePointsV1 {
     fn get_input_sockets(&self) -> Box<[TypeDescriptor]> {
-        Box::default()
+        Box::new([TypeDescriptor {
+            r#type: DataTypeKind::Mesh,
+            mutable: false,
+        }])
     }
 
     fn get_output_sockets(&self) -> Box<[TypeDescriptor]> {
@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    nodes::{
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        SocketInterface, StaticNodeMetadata,
+    },
+    property::{Property, PropertyMetadata},
+    type_system::{
+        OwnedDataType, Reference, TypeRef,
+        data_types::{DataType, DataTypeKind, Mesh, TypeDescriptor},
+    },
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GridV1 {
+    width: Property<f32>,
+    depth: Property<f32>,
+    columns: Property<i64>,
+    rows: Property<i64>,
+}
+impl Default for GridV1 {
+    fn default() -> Self {
+        Self {
+            width: Property::new(1.0),
+            depth: Property::new(1.0),
+            columns: Property::new(10),
+            rows: Property::new(10),
+        }
+    }
+}
+
+impl PropertyInterface for GridV1 {
+    fn try_set_property(
+        &mut self,
+        property: &str,
+        value: OwnedDataType,
+    ) -> Result<(), SetPropertyError> {
+        match property {
+            "width" => {
+                self.width
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "depth" => {
+                self.depth
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "columns" => {
+                self.columns
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "rows" => {
+                self.rows
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+
+            _ => Err(SetPropertyError::NotFound),
+        }
+    }
+
+    fn try_get_property(&self, property: &str) -> Result<TypeRef, PropertyNotFound> {
+        match property {
+            "width" => Ok(self.width.get_literal_value().to_data_type_ref()),
+            "depth" => Ok(self.depth.get_literal_value().to_data_type_ref()),
+            "columns" => Ok(self.columns.get_literal_value().to_data_type_ref()),
+            "rows" => Ok(self.rows.get_literal_value().to_data_type_ref()),
+            _ => Err(PropertyNotFound),
+        }
+    }
+
+    fn get_properties(&self) -> Box<[PropertyMetadata]> {
+        let default = Self::default();
+
+        let info = PropertyMetadata {
+            name: "width".into(),
+            r#type: default.width.get_type(),
+            default: default.width.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Size along the X axis.".into(),
+        };
+        let info2 = PropertyMetadata {
+            name: "depth".into(),
+            r#type: default.depth.get_type(),
+            default: default.depth.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Size along the Z axis.".into(),
+        };
+        let info3 = PropertyMetadata {
+            name: "columns".into(),
+            r#type: default.columns.get_type(),
+            default: default.columns.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Amount of quads along the X axis, at least 1.".into(),
+        };
+        let info4 = PropertyMetadata {
+            name: "rows".into(),
+            r#type: default.rows.get_type(),
+            default: default.rows.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Amount of quads along the Z axis, at least 1.".into(),
+        };
+
+        Box::new([info, info2, info3, info4])
+    }
+
+    fn set_property_external(
+        &mut self,
+        index: u8,
+        reference: Reference,
+    ) -> Result<(), SetPropertyError> {
+        match index {
+            0 => self.width.set_external(reference),
+            1 => self.depth.set_external(reference),
+            2 => self.columns.set_external(reference),
+            3 => self.rows.set_external(reference),
+            _ => return Err(SetPropertyError::WrongIndex),
+        }
+        Ok(())
+    }
+}
+
+impl Node for GridV1 {
+    fn compute(
+        &self,
+        _input_sockets: Option<&[Reference]>,
+        context: &impl ContextProvider,
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
+        let width = *self.width.get_value(context);
+        let depth = *self.depth.get_value(context);
+        let columns = *self.columns.get_value(context);
+        let rows = *self.rows.get_value(context);
+
+        let surface = Mesh::grid(width, depth, columns.max(0) as usize, rows.max(0) as usize);
+
+        Ok(Box::new([Some(OwnedDataType::new(surface))]))
+    }
+
+    fn node_metadata(&self) -> StaticNodeMetadata {
+        StaticNodeMetadata { color: "#15803d" }
+    }
+}
+impl SocketInterface for GridV1 {
+    fn get_input_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::default()
+    }
+
+    fn get_output_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::new([TypeDescriptor {
+            r#type: DataTypeKind::Mesh,
+            mutable: false,
+        }])
+    }
+}
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     nodes::{
-        ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
         SocketInterface, StaticNodeMetadata,
     },
     property::{Property, PropertyMetadata},
@@ -112,7 +112,11 @@ impl PropertyInterface for HelixV1 {
         index: u8,
         reference: Reference,
     ) -> Result<(), SetPropertyError> {
-        todo!()
+        match index {
+            0 => Ok(self.height.set_external(reference)),
+            1 => Ok(self.radius.set_external(reference)),
+            _ => Err(SetPropertyError::WrongIndex),
+        }
     }
 
     /* fn try_get_property_script(&self, property: &str) -> Result<String, PropertyNotFound> {
@@ -142,8 +146,8 @@ impl Node for HelixV1 {
         context: &impl ContextProvider,
         //TODO explore this
         // write_back: [&mut DataTypeValue],
-    ) -> Box<[Option<OwnedDataType>]> {
-        vec![].into_boxed_slice()
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
+        Ok(vec![].into_boxed_slice())
     }
 
     /* fn get_sockets(
@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    nodes::{
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        SocketInterface, StaticNodeMetadata,
+    },
+    property::{Property, PropertyMetadata},
+    type_system::{
+        OwnedDataType, Reference, TypeRef,
+        data_types::{DataType, DataTypeKind, Mesh, TypeDescriptor},
+    },
+};
+
+/// Every subdivision quadruples the faces so larger values would freeze the editor.
+const MAX_SUBDIVISIONS: i64 = 6;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IcosphereV1 {
+    radius: Property<f32>,
+    subdivisions: Property<i64>,
+}
+impl Default for IcosphereV1 {
+    fn default() -> Self {
+        Self {
+            radius: Property::new(1.0),
+            subdivisions: Property::new(2),
+        }
+    }
+}
+
+impl PropertyInterface for IcosphereV1 {
+    fn try_set_property(
+        &mut self,
+        property: &str,
+        value: OwnedDataType,
+    ) -> Result<(), SetPropertyError> {
+        match property {
+            "radius" => {
+                self.radius
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "subdivisions" => {
+                self.subdivisions
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+
+            _ => Err(SetPropertyError::NotFound),
+        }
+    }
+
+    fn try_get_property(&self, property: &str) -> Result<TypeRef, PropertyNotFound> {
+        match property {
+            "radius" => Ok(self.radius.get_literal_value().to_data_type_ref()),
+            "subdivisions" => Ok(self.subdivisions.get_literal_value().to_data_type_ref()),
+            _ => Err(PropertyNotFound),
+        }
+    }
+
+    fn get_properties(&self) -> Box<[PropertyMetadata]> {
+        let default = Self::default();
+
+        let info = PropertyMetadata {
+            name: "radius".into(),
+            r#type: default.radius.get_type(),
+            default: default.radius.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Distance of the surface from the center.".into(),
+        };
+        let info2 = PropertyMetadata {
+            name: "subdivisions".into(),
+            r#type: default.subdivisions.get_type(),
+            default: default
+                .subdivisions
+                .get_literal_value()
+                .to_data_type_value(),
+            configuration: None,
+            documentation: format!(
+                "How often the faces of the icosahedron are split, at most {MAX_SUBDIVISIONS}."
+            ),
+        };
+
+        Box::new([info, info2])
+    }
+
+    fn set_property_external(
+        &mut self,
+        index: u8,
+        reference: Reference,
+    ) -> Result<(), SetPropertyError> {
+        match index {
+            0 => self.radius.set_external(reference),
+            1 => self.subdivisions.set_external(reference),
+            _ => return Err(SetPropertyError::WrongIndex),
+        }
+        Ok(())
+    }
+}
+
+impl Node for IcosphereV1 {
+    fn compute(
+        &self,
+        _input_sockets: Option<&[Reference]>,
+        context: &impl ContextProvider,
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
+        let radius = *self.radius.get_value(context);
+        let subdivisions = *self.subdivisions.get_value(context);
+
+        let surface = Mesh::icosphere(radius, subdivisions.clamp(0, MAX_SUBDIVISIONS) as usize);
+
+        Ok(Box::new([Some(OwnedDataType::new(surface))]))
+    }
+
+    fn node_metadata(&self) -> StaticNodeMetadata {
+        StaticNodeMetadata { color: "#15803d" }
+    }
+}
+impl SocketInterface for IcosphereV1 {
+    fn get_input_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::default()
+    }
+
+    fn get_output_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::new([TypeDescriptor {
+            r#type: DataTypeKind::Mesh,
+            mutable: false,
+        }])
+    }
+}
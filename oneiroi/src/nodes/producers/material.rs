@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     nodes::{
-        ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
         SocketInterface, StaticNodeMetadata,
     },
     property::{Property, PropertyMetadata},
@@ -88,7 +88,10 @@ impl PropertyInterface for MaterialV1 {
         index: u8,
         reference: Reference,
     ) -> Result<(), SetPropertyError> {
-        todo!()
+        match index {
+            0 => Ok(self.albedo.set_external(reference)),
+            _ => Err(SetPropertyError::WrongIndex),
+        }
     }
 
     /* fn try_get_property_script(&self, property: &str) -> Result<String, PropertyNotFound> {
@@ -119,12 +122,14 @@ impl Node for MaterialV1 {
         context: &impl ContextProvider,
         //TODO explore this
         // write_back: [&mut DataTypeValue],
-    ) -> Box<[Option<OwnedDataType>]> {
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
         let mut material = Material::default();
 
         let albedo = self.albedo.get_value(context);
 
-        Box::new([Some(OwnedDataType::Material(Box::new(material)))])
+        Ok(Box::new([Some(OwnedDataType::Material(Box::new(
+            material,
+        )))]))
     }
 
     /* fn get_sockets(
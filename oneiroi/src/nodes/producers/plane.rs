@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    nodes::{
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        SocketInterface, StaticNodeMetadata,
+    },
+    property::{Property, PropertyMetadata},
+    type_system::{
+        OwnedDataType, Reference, TypeRef,
+        data_types::{DataType, DataTypeKind, Mesh, TypeDescriptor},
+    },
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PlaneV1 {
+    width: Property<f32>,
+    depth: Property<f32>,
+}
+impl Default for PlaneV1 {
+    fn default() -> Self {
+        Self {
+            width: Property::new(1.0),
+            depth: Property::new(1.0),
+        }
+    }
+}
+
+impl PropertyInterface for PlaneV1 {
+    fn try_set_property(
+        &mut self,
+        property: &str,
+        value: OwnedDataType,
+    ) -> Result<(), SetPropertyError> {
+        match property {
+            "width" => {
+                self.width
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "depth" => {
+                self.depth
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+
+            _ => Err(SetPropertyError::NotFound),
+        }
+    }
+
+    fn try_get_property(&self, property: &str) -> Result<TypeRef, PropertyNotFound> {
+        match property {
+            "width" => Ok(self.width.get_literal_value().to_data_type_ref()),
+            "depth" => Ok(self.depth.get_literal_value().to_data_type_ref()),
+            _ => Err(PropertyNotFound),
+        }
+    }
+
+    fn get_properties(&self) -> Box<[PropertyMetadata]> {
+        let default = Self::default();
+
+        let info = PropertyMetadata {
+            name: "width".into(),
+            r#type: default.width.get_type(),
+            default: default.width.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Size along the X axis.".into(),
+        };
+        let info2 = PropertyMetadata {
+            name: "depth".into(),
+            r#type: default.depth.get_type(),
+            default: default.depth.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Size along the Z axis.".into(),
+        };
+
+        Box::new([info, info2])
+    }
+
+    fn set_property_external(
+        &mut self,
+        index: u8,
+        reference: Reference,
+    ) -> Result<(), SetPropertyError> {
+        match index {
+            0 => self.width.set_external(reference),
+            1 => self.depth.set_external(reference),
+            _ => return Err(SetPropertyError::WrongIndex),
+        }
+        Ok(())
+    }
+}
+
+impl Node for PlaneV1 {
+    fn compute(
+        &self,
+        _input_sockets: Option<&[Reference]>,
+        context: &impl ContextProvider,
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
+        let width = *self.width.get_value(context);
+        let depth = *self.depth.get_value(context);
+
+        let surface = Mesh::grid(width, depth, 1, 1);
+
+        Ok(Box::new([Some(OwnedDataType::new(surface))]))
+    }
+
+    fn node_metadata(&self) -> StaticNodeMetadata {
+        StaticNodeMetadata { color: "#15803d" }
+    }
+}
+impl SocketInterface for PlaneV1 {
+    fn get_input_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::default()
+    }
+
+    fn get_output_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::new([TypeDescriptor {
+            r#type: DataTypeKind::Mesh,
+            mutable: false,
+        }])
+    }
+}
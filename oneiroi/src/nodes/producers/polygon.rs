@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     nodes::{
-        ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
         SocketInterface, StaticNodeMetadata,
     },
     property::PropertyMetadata,
@@ -134,10 +134,10 @@ impl Node for PolygonV1 {
         context: &impl ContextProvider,
         //TODO explore this
         // write_back: [&mut DataTypeValue],
-    ) -> Box<[Option<OwnedDataType>]> {
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
         let mut poly = Outline::new();
 
-        Box::new([Some(OwnedDataType::new(poly))])
+        Ok(Box::new([Some(OwnedDataType::new(poly))]))
     }
 
     /* fn get_sockets(
@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    nodes::{
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        SocketInterface, StaticNodeMetadata,
+    },
+    property::{Property, PropertyMetadata},
+    type_system::{
+        OwnedDataType, Reference, TypeRef,
+        data_types::{DataType, DataTypeKind, Mesh, TypeDescriptor},
+    },
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SphereV1 {
+    radius: Property<f32>,
+    segments: Property<i64>,
+    rings: Property<i64>,
+}
+impl Default for SphereV1 {
+    fn default() -> Self {
+        Self {
+            radius: Property::new(1.0),
+            segments: Property::new(16),
+            rings: Property::new(8),
+        }
+    }
+}
+
+impl PropertyInterface for SphereV1 {
+    fn try_set_property(
+        &mut self,
+        property: &str,
+        value: OwnedDataType,
+    ) -> Result<(), SetPropertyError> {
+        match property {
+            "radius" => {
+                self.radius
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "segments" => {
+                self.segments
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "rings" => {
+                self.rings
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+
+            _ => Err(SetPropertyError::NotFound),
+        }
+    }
+
+    fn try_get_property(&self, property: &str) -> Result<TypeRef, PropertyNotFound> {
+        match property {
+            "radius" => Ok(self.radius.get_literal_value().to_data_type_ref()),
+            "segments" => Ok(self.segments.get_literal_value().to_data_type_ref()),
+            "rings" => Ok(self.rings.get_literal_value().to_data_type_ref()),
+            _ => Err(PropertyNotFound),
+        }
+    }
+
+    fn get_properties(&self) -> Box<[PropertyMetadata]> {
+        let default = Self::default();
+
+        let info = PropertyMetadata {
+            name: "radius".into(),
+            r#type: default.radius.get_type(),
+            default: default.radius.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Distance of the surface from the center.".into(),
+        };
+        let info2 = PropertyMetadata {
+            name: "segments".into(),
+            r#type: default.segments.get_type(),
+            default: default.segments.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Amount of quads around the Y axis, at least 3.".into(),
+        };
+        let info3 = PropertyMetadata {
+            name: "rings".into(),
+            r#type: default.rings.get_type(),
+            default: default.rings.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Amount of rows from pole to pole, at least 2.".into(),
+        };
+
+        Box::new([info, info2, info3])
+    }
+
+    fn set_property_external(
+        &mut self,
+        index: u8,
+        reference: Reference,
+    ) -> Result<(), SetPropertyError> {
+        match index {
+            0 => self.radius.set_external(reference),
+            1 => self.segments.set_external(reference),
+            2 => self.rings.set_external(reference),
+            _ => return Err(SetPropertyError::WrongIndex),
+        }
+        Ok(())
+    }
+}
+
+impl Node for SphereV1 {
+    fn compute(
+        &self,
+        _input_sockets: Option<&[Reference]>,
+        context: &impl ContextProvider,
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
+        let radius = *self.radius.get_value(context);
+        let segments = *self.segments.get_value(context);
+        let rings = *self.rings.get_value(context);
+
+        let surface = Mesh::uv_sphere(radius, segments.max(0) as usize, rings.max(0) as usize);
+
+        Ok(Box::new([Some(OwnedDataType::new(surface))]))
+    }
+
+    fn node_metadata(&self) -> StaticNodeMetadata {
+        StaticNodeMetadata { color: "#15803d" }
+    }
+}
+impl SocketInterface for SphereV1 {
+    fn get_input_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::default()
+    }
+
+    fn get_output_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::new([TypeDescriptor {
+            r#type: DataTypeKind::Mesh,
+            mutable: false,
+        }])
+    }
+}
@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+
+use rustc_hash::FxBuildHasher;
+
+use super::{BoxV1, CylinderV1};
+use crate::asset::NodeIndex;
+use crate::nodes::Node;
+use crate::type_system::OwnedDataType;
+use crate::type_system::data_types::Mesh;
+
+type Context = HashMap<NodeIndex, Box<[OwnedDataType]>, FxBuildHasher>;
+
+fn compute(node: &impl Node) -> Mesh {
+    let output = node.compute(None, &Context::default()).unwrap();
+    output[0].clone().unwrap().dispatch().unwrap()
+}
+
+#[test]
+fn box_shows_the_whole_texture_on_every_side() {
+    let mesh = compute(&BoxV1::default());
+    let uvs = mesh.get_index_mesh_buffers(false).uvs;
+    assert!(uvs.len() > 8);
+    for face in mesh.face_handles() {
+        let uvs = [0, 1, 2].map(|corner| mesh.uv(face, corner).unwrap());
+        assert!(uvs[0] != uvs[1] && uvs[1] != uvs[2] && uvs[0] != uvs[2]);
+    }
+    assert!(
+        uvs.iter()
+            .all(|uv| uv.x.abs() < 1e-6 || (uv.x - 1.0).abs() < 1e-6)
+    );
+    assert!(
+        uvs.iter()
+            .all(|uv| uv.y.abs() < 1e-6 || (uv.y - 1.0).abs() < 1e-6)
+    );
+}
+
+#[test]
+fn cylinder_wraps_the_texture_around() {
+    let mesh = compute(&CylinderV1::default());
+    let uvs = mesh.get_index_mesh_buffers(false).uvs;
+    // The points on the seam exist twice.
+    assert_eq!(uvs.len(), 6 * 2 + 2);
+    let max = uvs.iter().fold(uvs[0], |max, uv| max.max(*uv));
+    let min = uvs.iter().fold(uvs[0], |min, uv| min.min(*uv));
+    assert!(min.abs_diff_eq(glam::Vec2::ZERO, 1e-6), "{min}");
+    assert!(max.abs_diff_eq(glam::Vec2::ONE, 1e-6), "{max}");
+}
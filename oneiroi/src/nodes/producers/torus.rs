@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    nodes::{
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        SocketInterface, StaticNodeMetadata,
+    },
+    property::{Property, PropertyMetadata},
+    type_system::{
+        OwnedDataType, Reference, TypeRef,
+        data_types::{DataType, DataTypeKind, Mesh, TypeDescriptor},
+    },
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TorusV1 {
+    radius: Property<f32>,
+    tube_radius: Property<f32>,
+    segments: Property<i64>,
+    tube_segments: Property<i64>,
+}
+impl Default for TorusV1 {
+    fn default() -> Self {
+        Self {
+            radius: Property::new(1.0),
+            tube_radius: Property::new(0.25),
+            segments: Property::new(24),
+            tube_segments: Property::new(12),
+        }
+    }
+}
+
+impl PropertyInterface for TorusV1 {
+    fn try_set_property(
+        &mut self,
+        property: &str,
+        value: OwnedDataType,
+    ) -> Result<(), SetPropertyError> {
+        match property {
+            "radius" => {
+                self.radius
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "tube_radius" => {
+                self.tube_radius
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "segments" => {
+                self.segments
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "tube_segments" => {
+                self.tube_segments
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+
+            _ => Err(SetPropertyError::NotFound),
+        }
+    }
+
+    fn try_get_property(&self, property: &str) -> Result<TypeRef, PropertyNotFound> {
+        match property {
+            "radius" => Ok(self.radius.get_literal_value().to_data_type_ref()),
+            "tube_radius" => Ok(self.tube_radius.get_literal_value().to_data_type_ref()),
+            "segments" => Ok(self.segments.get_literal_value().to_data_type_ref()),
+            "tube_segments" => Ok(self.tube_segments.get_literal_value().to_data_type_ref()),
+            _ => Err(PropertyNotFound),
+        }
+    }
+
+    fn get_properties(&self) -> Box<[PropertyMetadata]> {
+        let default = Self::default();
+
+        let info = PropertyMetadata {
+            name: "radius".into(),
+            r#type: default.radius.get_type(),
+            default: default.radius.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Distance from the center to the middle of the tube.".into(),
+        };
+        let info2 = PropertyMetadata {
+            name: "tube_radius".into(),
+            r#type: default.tube_radius.get_type(),
+            default: default.tube_radius.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Radius of the tube.".into(),
+        };
+        let info3 = PropertyMetadata {
+            name: "segments".into(),
+            r#type: default.segments.get_type(),
+            default: default.segments.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Amount of segments around the Y axis, at least 3.".into(),
+        };
+        let info4 = PropertyMetadata {
+            name: "tube_segments".into(),
+            r#type: default.tube_segments.get_type(),
+            default: default
+                .tube_segments
+                .get_literal_value()
+                .to_data_type_value(),
+            configuration: None,
+            documentation: "Amount of segments around the tube, at least 3.".into(),
+        };
+
+        Box::new([info, info2, info3, info4])
+    }
+
+    fn set_property_external(
+        &mut self,
+        index: u8,
+        reference: Reference,
+    ) -> Result<(), SetPropertyError> {
+        match index {
+            0 => self.radius.set_external(reference),
+            1 => self.tube_radius.set_external(reference),
+            2 => self.segments.set_external(reference),
+            3 => self.tube_segments.set_external(reference),
+            _ => return Err(SetPropertyError::WrongIndex),
+        }
+        Ok(())
+    }
+}
+
+impl Node for TorusV1 {
+    fn compute(
+        &self,
+        _input_sockets: Option<&[Reference]>,
+        context: &impl ContextProvider,
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
+        let radius = *self.radius.get_value(context);
+        let tube_radius = *self.tube_radius.get_value(context);
+        let segments = *self.segments.get_value(context);
+        let tube_segments = *self.tube_segments.get_value(context);
+
+        let surface = Mesh::torus(
+            radius,
+            tube_radius,
+            segments.max(0) as usize,
+            tube_segments.max(0) as usize,
+        );
+
+        Ok(Box::new([Some(OwnedDataType::new(surface))]))
+    }
+
+    fn node_metadata(&self) -> StaticNodeMetadata {
+        StaticNodeMetadata { color: "#15803d" }
+    }
+}
+impl SocketInterface for TorusV1 {
+    fn get_input_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::default()
+    }
+
+    fn get_output_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::new([TypeDescriptor {
+            r#type: DataTypeKind::Mesh,
+            mutable: false,
+        }])
+    }
+}
@@ -1,9 +1,16 @@
+mod array;
 mod create_instance;
 mod instances_from_transforms;
 mod sample_curve;
 mod sweep;
+mod transform;
 
+pub use array::ArrayV1;
 pub use create_instance::CreateInstanceV1;
 pub use instances_from_transforms::InstancesFromTransformsV1;
 pub use sample_curve::SampleCurveV1;
 pub use sweep::SweepV1;
+pub use transform::TransformV1;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,307 @@
+use std::f32::consts::TAU;
+
+use glam::{Quat, Vec3};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    nodes::{
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        SocketInterface, StaticNodeMetadata,
+    },
+    property::{Property, PropertyMetadata},
+    type_system::{
+        OwnedDataType, Reference, TypeRef,
+        data_types::{
+            Collection, CubicBezier, DataType, DataTypeKind, Float, Instance, Int, Mesh, Transform,
+            TypeDescriptor,
+        },
+        trait_types::Transformable,
+    },
+};
+
+const LINEAR: Int = 0;
+const RADIAL: Int = 1;
+const ALONG_CURVE: Int = 2;
+
+/// Repeats a Mesh in a line, around an axis or along a curve.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArrayV1 {
+    //TODO use an enum once they are supported by properties
+    mode: Property<Int>,
+    count: Property<Int>,
+    /// The distance between two copies of a linear array.
+    offset: Property<Vec3>,
+    axis: Property<Vec3>,
+    center: Property<Vec3>,
+    /// The angle in degrees a radial array is spread over.
+    angle: Property<Float>,
+    /// Outputs a Collection of Instances instead of a single merged Mesh.
+    instances: Property<bool>,
+}
+
+impl Default for ArrayV1 {
+    fn default() -> Self {
+        Self {
+            mode: Property::new(LINEAR),
+            count: Property::new(3),
+            offset: Property::new(Vec3::X * 2.),
+            axis: Property::new(Vec3::Y),
+            center: Property::new(Vec3::ZERO),
+            angle: Property::new(360.),
+            instances: Property::new(false),
+        }
+    }
+}
+
+impl ArrayV1 {
+    /// The placement of every copy relative to the original.
+    fn get_transforms(
+        &self,
+        context: &impl ContextProvider,
+        curve: Option<&CubicBezier>,
+    ) -> Vec<Transform> {
+        let count = (*self.count.get_value(context)).max(0) as usize;
+        match *self.mode.get_value(context) {
+            RADIAL => {
+                let axis = self.axis.get_value(context).normalize_or(Vec3::Y);
+                let center = *self.center.get_value(context);
+                let angle = self.angle.get_value(context).to_radians();
+                // A full circle would place the last copy on top of the first one.
+                let gaps = if (angle.abs() - TAU).abs() < 1e-4 {
+                    count
+                } else {
+                    count.saturating_sub(1).max(1)
+                };
+                let step = angle / gaps as f32;
+                (0..count)
+                    .map(|index| {
+                        Transform::from_translation(center)
+                            * Transform::from_quat(Quat::from_axis_angle(axis, step * index as f32))
+                            * Transform::from_translation(-center)
+                    })
+                    .collect()
+            }
+            ALONG_CURVE => curve
+                .map(|curve| curve.sample_evenly(count).into_vec())
+                .unwrap_or_default(),
+            _ => {
+                let offset = *self.offset.get_value(context);
+                (0..count)
+                    .map(|index| Transform::from_translation(offset * index as f32))
+                    .collect()
+            }
+        }
+    }
+}
+
+impl Node for ArrayV1 {
+    fn compute(
+        &self,
+        input_sockets: Option<&[Reference]>,
+        context: &impl ContextProvider,
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
+        let input_sockets = input_sockets.unwrap();
+        let curve = input_sockets
+            .get(1)
+            .map(|curve| context.get_reference(*curve));
+        let curve: Option<&CubicBezier> = curve.map(|curve| curve.dispatch_ref().unwrap());
+        let transforms = self.get_transforms(context, curve);
+
+        if *self.instances.get_value(context) {
+            let mut instances = Collection::new(DataTypeKind::Instance);
+            for transform in transforms {
+                instances.push(OwnedDataType::new(Instance::new(
+                    transform,
+                    input_sockets[0],
+                )));
+            }
+            return Ok(Box::new([Some(OwnedDataType::new(instances))]));
+        }
+
+        let input = context.get_reference(input_sockets[0]);
+        let input: &Mesh = input.dispatch_ref().unwrap();
+        let copies = transforms
+            .iter()
+            .map(|transform| {
+                let mut copy = input.clone();
+                copy.transform(transform);
+                copy
+            })
+            .collect::<Vec<_>>();
+        let new_surface = Mesh::join(&copies);
+
+        Ok(Box::new([Some(OwnedDataType::Mesh(Box::new(new_surface)))]))
+    }
+
+    fn node_metadata(&self) -> StaticNodeMetadata {
+        StaticNodeMetadata { color: "#4338ca" }
+    }
+}
+impl SocketInterface for ArrayV1 {
+    /// The curve is only needed when placing the copies along it.
+    fn get_input_sockets(&self) -> Box<[TypeDescriptor]> {
+        let mesh = TypeDescriptor {
+            r#type: DataTypeKind::Mesh,
+            mutable: false,
+        };
+        if *self.mode.get_literal_value() == ALONG_CURVE {
+            Box::new([
+                mesh,
+                TypeDescriptor {
+                    r#type: DataTypeKind::CubicBezier,
+                    mutable: false,
+                },
+            ])
+        } else {
+            Box::new([mesh])
+        }
+    }
+
+    fn get_output_sockets(&self) -> Box<[TypeDescriptor]> {
+        let r#type = if *self.instances.get_literal_value() {
+            DataTypeKind::Collection
+        } else {
+            DataTypeKind::Mesh
+        };
+        Box::new([TypeDescriptor {
+            r#type,
+            mutable: true,
+        }])
+    }
+}
+
+impl PropertyInterface for ArrayV1 {
+    fn try_set_property(
+        &mut self,
+        property: &str,
+        value: OwnedDataType,
+    ) -> Result<(), SetPropertyError> {
+        match property {
+            "mode" => {
+                self.mode
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "count" => {
+                self.count
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "offset" => {
+                self.offset
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "axis" => {
+                self.axis
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "center" => {
+                self.center
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "angle" => {
+                self.angle
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "instances" => {
+                self.instances
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+
+            _ => Err(SetPropertyError::NotFound),
+        }
+    }
+
+    fn try_get_property(&self, property: &str) -> Result<TypeRef, PropertyNotFound> {
+        match property {
+            "mode" => Ok(self.mode.get_literal_value().to_data_type_ref()),
+            "count" => Ok(self.count.get_literal_value().to_data_type_ref()),
+            "offset" => Ok(self.offset.get_literal_value().to_data_type_ref()),
+            "axis" => Ok(self.axis.get_literal_value().to_data_type_ref()),
+            "center" => Ok(self.center.get_literal_value().to_data_type_ref()),
+            "angle" => Ok(self.angle.get_literal_value().to_data_type_ref()),
+            "instances" => Ok(self.instances.get_literal_value().to_data_type_ref()),
+            _ => Err(PropertyNotFound),
+        }
+    }
+
+    fn get_properties(&self) -> Box<[PropertyMetadata]> {
+        let default = Self::default();
+
+        let info = PropertyMetadata {
+            name: "mode".into(),
+            r#type: default.mode.get_type(),
+            default: default.mode.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "0 = linear, 1 = radial around the axis, 2 = along the curve input."
+                .into(),
+        };
+        let info2 = PropertyMetadata {
+            name: "count".into(),
+            r#type: default.count.get_type(),
+            default: default.count.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "".into(),
+        };
+        let info3 = PropertyMetadata {
+            name: "offset".into(),
+            r#type: default.offset.get_type(),
+            default: default.offset.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "The distance between two copies of a linear array.".into(),
+        };
+        let info4 = PropertyMetadata {
+            name: "axis".into(),
+            r#type: default.axis.get_type(),
+            default: default.axis.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "The axis a radial array rotates around.".into(),
+        };
+        let info5 = PropertyMetadata {
+            name: "center".into(),
+            r#type: default.center.get_type(),
+            default: default.center.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "A point on the axis of a radial array.".into(),
+        };
+        let info6 = PropertyMetadata {
+            name: "angle".into(),
+            r#type: default.angle.get_type(),
+            default: default.angle.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "The angle in degrees a radial array is spread over.".into(),
+        };
+        let info7 = PropertyMetadata {
+            name: "instances".into(),
+            r#type: default.instances.get_type(),
+            default: default.instances.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Outputs a Collection of Instances instead of a merged Mesh.".into(),
+        };
+
+        Box::new([info, info2, info3, info4, info5, info6, info7])
+    }
+
+    fn set_property_external(
+        &mut self,
+        index: u8,
+        reference: Reference,
+    ) -> Result<(), SetPropertyError> {
+        match index {
+            0 => self.mode.set_external(reference),
+            1 => self.count.set_external(reference),
+            2 => self.offset.set_external(reference),
+            3 => self.axis.set_external(reference),
+            4 => self.center.set_external(reference),
+            5 => self.angle.set_external(reference),
+            6 => self.instances.set_external(reference),
+            _ => return Err(SetPropertyError::WrongIndex),
+        }
+        Ok(())
+    }
+}
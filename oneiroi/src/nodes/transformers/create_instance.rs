@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     nodes::{
-        ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
         SocketInterface, StaticNodeMetadata,
     },
     property::{Property, PropertyMetadata},
@@ -79,7 +79,10 @@ impl PropertyInterface for CreateInstanceV1 {
         index: u8,
         reference: Reference,
     ) -> Result<(), SetPropertyError> {
-        todo!()
+        match index {
+            0 => Ok(self.position.set_external(reference)),
+            _ => Err(SetPropertyError::WrongIndex),
+        }
     }
 
     /* fn try_get_property_script(&self, property: &str) -> Result<String, PropertyNotFound> {
@@ -94,14 +97,14 @@ impl Node for CreateInstanceV1 {
         context: &impl ContextProvider,
         //TODO explore this
         // write_back: [&mut DataTypeValue],
-    ) -> Box<[Option<OwnedDataType>]> {
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
         //let input = input_sockets.unwrap()[0];
         let position = *self.position.get_value(context);
         let new_instance = Instance::new(
             Affine3A::from_translation(position),
             input_sockets.unwrap()[0],
         );
-        Box::new([Some(OwnedDataType::new(new_instance))])
+        Ok(Box::new([Some(OwnedDataType::new(new_instance))]))
     }
 
     fn node_metadata(&self) -> crate::nodes::StaticNodeMetadata {
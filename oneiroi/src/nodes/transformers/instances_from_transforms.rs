@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     nodes::{
-        ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
         SocketInterface, StaticNodeMetadata,
     },
     property::{Property, PropertyMetadata},
@@ -88,7 +88,10 @@ impl PropertyInterface for InstancesFromTransformsV1 {
         index: u8,
         reference: Reference,
     ) -> Result<(), SetPropertyError> {
-        todo!()
+        match index {
+            0 => Ok(self.transform.set_external(reference)),
+            _ => Err(SetPropertyError::WrongIndex),
+        }
     }
 
     /* fn try_get_property_script(&self, property: &str) -> Result<String, PropertyNotFound> {
@@ -116,7 +119,7 @@ impl Node for InstancesFromTransformsV1 {
         context: &impl ContextProvider,
         //TODO explore this
         // write_back: [&mut DataTypeValue],
-    ) -> Box<[Option<OwnedDataType>]> {
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
         let inputs = input_sockets.unwrap();
         //TODO get points
         let points = context.get_reference(inputs[0]);
@@ -134,7 +137,7 @@ impl Node for InstancesFromTransformsV1 {
             ))));
             println!("Instance TF: {transform}");
         }
-        Box::new([Some(OwnedDataType::new(instances))])
+        Ok(Box::new([Some(OwnedDataType::new(instances))]))
     }
 
     /* fn get_sockets(
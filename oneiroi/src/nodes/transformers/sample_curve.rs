@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     nodes::{
-        ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
         SocketInterface, StaticNodeMetadata,
     },
     property::{Property, PropertyMetadata},
@@ -170,7 +170,7 @@ impl Node for SampleCurveV1 {
         &self,
         input_sockets: Option<&[Reference]>,
         context: &impl ContextProvider,
-    ) -> Box<[Option<OwnedDataType>]> {
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
         let curve = context.get_reference(input_sockets.unwrap()[0]);
         let curve: &CubicBezier = curve.dispatch_ref().unwrap();
 
@@ -194,7 +194,7 @@ impl Node for SampleCurveV1 {
             collection.push(OwnedDataType::Transform(Box::new(transform)));
         }
 
-        Box::new([Some(OwnedDataType::new(collection))])
+        Ok(Box::new([Some(OwnedDataType::new(collection))]))
     }
 
     /* fn get_sockets(
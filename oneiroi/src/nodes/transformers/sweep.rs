@@ -1,9 +1,11 @@
-use glam::{Vec3, Vec4, Vec4Swizzles};
+use std::collections::HashMap;
+
+use glam::{Vec2, Vec3};
 use serde::{Deserialize, Serialize};
 
 use crate::{
     nodes::{
-        ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
         SocketInterface, SocketMetadata, StaticNodeMetadata,
     },
     property::{Property, PropertyMetadata},
@@ -138,7 +140,7 @@ impl Node for SweepV1 {
         &self,
         input_sockets: Option<&[Reference]>,
         context: &impl ContextProvider,
-    ) -> Box<[Option<OwnedDataType>]> {
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
         let curve: &CubicBezier = context
             .get_reference(input_sockets.unwrap()[0])
             .dispatch_ref()
@@ -164,9 +166,26 @@ impl Node for SweepV1 {
 
         let len = transforms.len();
 
+        // U follows the length of the outline and V the length of the curve.
+        let mut outline_length = 0.0;
+        let outline_distances = std::iter::once(0.0)
+            .chain(poly_points.windows(2).map(|pair| {
+                outline_length += pair[0].distance(pair[1]);
+                outline_length
+            }))
+            .collect::<Box<_>>();
+        let mut curve_length = 0.0;
+        let mut uvs = HashMap::new();
+
         //For each transform extrude the prev ring to the new transform
-        for (index, transform) in transforms.into_iter().enumerate() {
+        for (index, transform) in transforms.iter().enumerate() {
+            if index > 0 {
+                curve_length += transform
+                    .translation
+                    .distance(transforms[index - 1].translation);
+            }
             for (index, point) in inserted_points.iter().copied().enumerate() {
+                uvs.insert(point, Vec2::new(outline_distances[index], curve_length));
                 /* println!(
                     "{}, Point {point:?}",
                     transform.transform_point3(poly_points[index])
@@ -183,13 +202,19 @@ impl Node for SweepV1 {
             //current_edges =
         }
 
+        let corner_uvs = new_mesh
+            .face_handles()
+            .flat_map(|face| new_mesh.points_in_face(face).map(|point| uvs[&point]))
+            .collect::<Vec<_>>();
+        new_mesh.set_uvs(corner_uvs);
+
         //println!("{new_mesh:?}");
 
         // Potentialle take into account hard/smooth vertices.
 
         //Somehow get last curve position and seal the sweep
 
-        Box::new([Some(OwnedDataType::new(new_mesh))])
+        Ok(Box::new([Some(OwnedDataType::new(new_mesh))]))
     }
 
     fn node_metadata(&self) -> StaticNodeMetadata {
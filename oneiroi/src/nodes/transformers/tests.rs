@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use glam::Vec3;
+use rustc_hash::FxBuildHasher;
+
+use super::{ArrayV1, TransformV1};
+use crate::asset::NodeIndex;
+use crate::nodes::{ComputeError, Node, PropertyInterface, SocketInterface};
+use crate::type_system::data_types::{Collection, CubicBezier, DataTypeKind, Instance, Mesh};
+use crate::type_system::trait_types::{MeshMut0D, MeshMut2D};
+use crate::type_system::{OwnedDataType, Reference};
+
+type Context = HashMap<NodeIndex, Box<[OwnedDataType]>, FxBuildHasher>;
+
+fn triangle() -> Mesh {
+    let mut mesh = Mesh::default();
+    let points = mesh.add_points([Vec3::ZERO, Vec3::X, Vec3::Y]);
+    mesh.add_tri([points[0], points[1], points[2]]);
+    mesh
+}
+
+fn context(inputs: impl IntoIterator<Item = OwnedDataType>) -> (Context, Box<[Reference]>) {
+    let mut context = Context::default();
+    let references = inputs
+        .into_iter()
+        .enumerate()
+        .map(|(index, input)| {
+            let node = NodeIndex::new(index + 1);
+            context.insert(node, Box::new([input]));
+            Reference::Standard { node, socket: 0 }
+        })
+        .collect();
+    (context, references)
+}
+
+fn set(array: &mut ArrayV1, property: &str, value: OwnedDataType) {
+    array.try_set_property(property, value).unwrap();
+}
+
+#[test]
+fn linear_array_merges_copies() {
+    let (context, inputs) = context([OwnedDataType::new(triangle())]);
+    let mut array = ArrayV1::default();
+    set(&mut array, "count", OwnedDataType::Int(4));
+    set(&mut array, "offset", OwnedDataType::Vec3(Vec3::Z));
+
+    let output = array.compute(Some(&inputs), &context).unwrap();
+    let mesh: Mesh = output[0].clone().unwrap().dispatch().unwrap();
+    assert_eq!(mesh.point_handles().count(), 12);
+    assert_eq!(mesh.face_handles().count(), 4);
+    assert_eq!(
+        mesh.position(mesh.point_handles().last().unwrap()),
+        Vec3::new(0., 1., 3.)
+    );
+}
+
+#[test]
+fn radial_array_outputs_instances() {
+    let (context, inputs) = context([OwnedDataType::new(triangle())]);
+    let mut array = ArrayV1::default();
+    set(&mut array, "mode", OwnedDataType::Int(1));
+    set(&mut array, "count", OwnedDataType::Int(4));
+    set(
+        &mut array,
+        "center",
+        OwnedDataType::Vec3(Vec3::new(-1., 0., 0.)),
+    );
+    set(&mut array, "instances", OwnedDataType::Bool(true));
+    assert_eq!(
+        array.get_output_sockets()[0].get_type(),
+        DataTypeKind::Collection
+    );
+
+    let output = array.compute(Some(&inputs), &context).unwrap();
+    let instances: Collection = output[0].clone().unwrap().dispatch().unwrap();
+    // A full circle doesn't repeat the first copy at its end.
+    let positions = instances
+        .iterate()
+        .map(|instance| {
+            let instance: Instance = instance.clone().dispatch().unwrap();
+            assert_eq!(instance.get_reference(), inputs[0]);
+            instance.get_transform().transform_point3(Vec3::ZERO)
+        })
+        .collect::<Vec<_>>();
+    let expected = [
+        Vec3::ZERO,
+        Vec3::new(-1., 0., -1.),
+        Vec3::new(-2., 0., 0.),
+        Vec3::new(-1., 0., 1.),
+    ];
+    for (position, expected) in positions.iter().zip(expected) {
+        assert!(
+            position.distance(expected) < 1e-5,
+            "{position} != {expected}"
+        );
+    }
+}
+
+#[test]
+fn array_along_curve_spans_the_whole_curve() {
+    let curve = CubicBezier::with_points(vec![Vec3::ZERO, Vec3::X, Vec3::X * 2., Vec3::X * 3.]);
+    let (context, inputs) = context([OwnedDataType::new(triangle()), OwnedDataType::new(curve)]);
+    let mut array = ArrayV1::default();
+    set(&mut array, "mode", OwnedDataType::Int(2));
+    set(&mut array, "count", OwnedDataType::Int(4));
+    set(&mut array, "instances", OwnedDataType::Bool(true));
+    assert_eq!(array.get_input_sockets().len(), 2);
+
+    let output = array.compute(Some(&inputs), &context).unwrap();
+    let instances: Collection = output[0].clone().unwrap().dispatch().unwrap();
+    assert_eq!(instances.length(), 4);
+    for (index, instance) in instances.iterate().enumerate() {
+        let instance: Instance = instance.clone().dispatch().unwrap();
+        let position = instance.get_transform().transform_point3(Vec3::ZERO);
+        assert!(
+            (position - Vec3::X * index as f32).length() < 1e-3,
+            "{position}"
+        );
+    }
+}
+
+#[test]
+fn transform_rejects_untransformable_inputs() {
+    let mut transform = TransformV1::default();
+    transform
+        .try_set_property("translation", OwnedDataType::Vec3(Vec3::Z))
+        .unwrap();
+
+    let (meshes, inputs) = context([OwnedDataType::new(triangle())]);
+    let output = transform.compute(Some(&inputs), &meshes).unwrap();
+    let mesh: Mesh = output[0].clone().unwrap().dispatch().unwrap();
+    assert!(
+        mesh.point_handles()
+            .all(|point| mesh.position(point).z == 1.)
+    );
+
+    let (invalid, inputs) = context([OwnedDataType::Int(1)]);
+    assert!(matches!(
+        transform.compute(Some(&inputs), &invalid),
+        Err(ComputeError::UnsupportedInput {
+            found: DataTypeKind::Int,
+            ..
+        })
+    ));
+}
@@ -0,0 +1,227 @@
+use glam::{EulerRot, Quat, Vec3};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    nodes::{
+        ComputeError, ContextProvider, Node, PropertyInterface, PropertyNotFound, SetPropertyError,
+        SocketInterface, StaticNodeMetadata,
+    },
+    property::{Property, PropertyMetadata},
+    type_system::{
+        OwnedDataType, Reference, TypeRef,
+        data_types::{DataType, DataTypeKind, Float, Transform, TypeDescriptor},
+    },
+};
+
+/// Moves, rotates and scales any geometry including Collections of it.
+/// Rotation and scale happen around the pivot.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TransformV1 {
+    translation: Property<Vec3>,
+    /// Euler angles in degrees applied in the order X, Y, Z.
+    rotation: Property<Vec3>,
+    /// A quaternion given by axis and angle in degrees, applied after the Euler angles.
+    axis: Property<Vec3>,
+    angle: Property<Float>,
+    scale: Property<Vec3>,
+    pivot: Property<Vec3>,
+}
+
+impl Default for TransformV1 {
+    fn default() -> Self {
+        Self {
+            translation: Property::new(Vec3::ZERO),
+            rotation: Property::new(Vec3::ZERO),
+            axis: Property::new(Vec3::Y),
+            angle: Property::new(0.),
+            scale: Property::new(Vec3::ONE),
+            pivot: Property::new(Vec3::ZERO),
+        }
+    }
+}
+
+impl TransformV1 {
+    fn get_transform(&self, context: &impl ContextProvider) -> Transform {
+        let euler = self.rotation.get_value(context);
+        let euler = Quat::from_euler(
+            EulerRot::XYZ,
+            euler.x.to_radians(),
+            euler.y.to_radians(),
+            euler.z.to_radians(),
+        );
+        let axis = self.axis.get_value(context).normalize_or(Vec3::Y);
+        let quaternion = Quat::from_axis_angle(axis, self.angle.get_value(context).to_radians());
+
+        let pivot = *self.pivot.get_value(context);
+        Transform::from_translation(*self.translation.get_value(context) + pivot)
+            * Transform::from_scale_rotation_translation(
+                *self.scale.get_value(context),
+                quaternion * euler,
+                Vec3::ZERO,
+            )
+            * Transform::from_translation(-pivot)
+    }
+}
+
+impl Node for TransformV1 {
+    fn compute(
+        &self,
+        input_sockets: Option<&[Reference]>,
+        context: &impl ContextProvider,
+    ) -> Result<Box<[Option<OwnedDataType>]>, ComputeError> {
+        let input = context.get_reference(input_sockets.unwrap()[0]);
+        let mut output = OwnedDataType::from(input);
+
+        let found = output.get_data_type();
+        let Some(value) = output.as_transformable_mut() else {
+            return Err(ComputeError::UnsupportedInput {
+                node: "Transform",
+                found,
+            });
+        };
+        value.transform(&self.get_transform(context));
+
+        Ok(Box::new([Some(output)]))
+    }
+
+    fn node_metadata(&self) -> StaticNodeMetadata {
+        StaticNodeMetadata { color: "#4338ca" }
+    }
+}
+impl SocketInterface for TransformV1 {
+    /// Accepts Meshes, Curves, Instances, Transforms, Vec3 and Collections of them.
+    fn get_input_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::new([TypeDescriptor {
+            r#type: DataTypeKind::Omni,
+            mutable: true,
+        }])
+    }
+
+    fn get_output_sockets(&self) -> Box<[TypeDescriptor]> {
+        Box::new([TypeDescriptor {
+            r#type: DataTypeKind::Omni,
+            mutable: true,
+        }])
+    }
+}
+
+impl PropertyInterface for TransformV1 {
+    fn try_set_property(
+        &mut self,
+        property: &str,
+        value: OwnedDataType,
+    ) -> Result<(), SetPropertyError> {
+        match property {
+            "translation" => {
+                self.translation
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "rotation" => {
+                self.rotation
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "axis" => {
+                self.axis
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "angle" => {
+                self.angle
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "scale" => {
+                self.scale
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+            "pivot" => {
+                self.pivot
+                    .set_value(value.dispatch().expect("TODO error handling"));
+                Ok(())
+            }
+
+            _ => Err(SetPropertyError::NotFound),
+        }
+    }
+
+    fn try_get_property(&self, property: &str) -> Result<TypeRef, PropertyNotFound> {
+        match property {
+            "translation" => Ok(self.translation.get_literal_value().to_data_type_ref()),
+            "rotation" => Ok(self.rotation.get_literal_value().to_data_type_ref()),
+            "axis" => Ok(self.axis.get_literal_value().to_data_type_ref()),
+            "angle" => Ok(self.angle.get_literal_value().to_data_type_ref()),
+            "scale" => Ok(self.scale.get_literal_value().to_data_type_ref()),
+            "pivot" => Ok(self.pivot.get_literal_value().to_data_type_ref()),
+            _ => Err(PropertyNotFound),
+        }
+    }
+
+    fn get_properties(&self) -> Box<[PropertyMetadata]> {
+        let default = Self::default();
+
+        let info = PropertyMetadata {
+            name: "translation".into(),
+            r#type: default.translation.get_type(),
+            default: default.translation.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "".into(),
+        };
+        let info2 = PropertyMetadata {
+            name: "rotation".into(),
+            r#type: default.rotation.get_type(),
+            default: default.rotation.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "Euler angles in degrees applied in the order X, Y, Z.".into(),
+        };
+        let info3 = PropertyMetadata {
+            name: "axis".into(),
+            r#type: default.axis.get_type(),
+            default: default.axis.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "The axis of the rotation applied after the Euler angles.".into(),
+        };
+        let info4 = PropertyMetadata {
+            name: "angle".into(),
+            r#type: default.angle.get_type(),
+            default: default.angle.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "The angle in degrees to rotate around the axis.".into(),
+        };
+        let info5 = PropertyMetadata {
+            name: "scale".into(),
+            r#type: default.scale.get_type(),
+            default: default.scale.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "".into(),
+        };
+        let info6 = PropertyMetadata {
+            name: "pivot".into(),
+            r#type: default.pivot.get_type(),
+            default: default.pivot.get_literal_value().to_data_type_value(),
+            configuration: None,
+            documentation: "The point rotation and scale happen around.".into(),
+        };
+
+        Box::new([info, info2, info3, info4, info5, info6])
+    }
+
+    fn set_property_external(
+        &mut self,
+        index: u8,
+        reference: Reference,
+    ) -> Result<(), SetPropertyError> {
+        match index {
+            0 => self.translation.set_external(reference),
+            1 => self.rotation.set_external(reference),
+            2 => self.axis.set_external(reference),
+            3 => self.angle.set_external(reference),
+            4 => self.scale.set_external(reference),
+            5 => self.pivot.set_external(reference),
+            _ => return Err(SetPropertyError::WrongIndex),
+        }
+        Ok(())
+    }
+}
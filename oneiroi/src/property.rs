@@ -310,25 +310,35 @@ impl PropertyInstance {
         }
     }
 
+    /// Whether an exposed property of this type can be held by an instance.
+    pub(crate) fn supports(kind: DataTypeKind) -> bool {
+        matches!(
+            kind,
+            DataTypeKind::Vec3
+                | DataTypeKind::Float
+                | DataTypeKind::Bool
+                | DataTypeKind::Int
+                | DataTypeKind::Mesh
+                | DataTypeKind::Selection
+        )
+    }
+
     pub(crate) fn new(meta: &PropertyMetadata) -> Self {
+        let default = meta.default.clone();
         match meta.r#type {
             DataTypeKind::Omni => todo!(),
-            DataTypeKind::Selection => todo!(),
-            DataTypeKind::Mesh => todo!(),
+            DataTypeKind::Selection => Self::Selection(Property::new(default.dispatch().unwrap())),
+            DataTypeKind::Mesh => Self::Mesh(Property::new(default.dispatch().unwrap())),
             DataTypeKind::Collider => todo!(),
             DataTypeKind::Curve => todo!(),
             DataTypeKind::CubicBezier => todo!(),
             DataTypeKind::Instance => todo!(),
             DataTypeKind::Material => todo!(),
             DataTypeKind::Collection => todo!(),
-            DataTypeKind::Vec3 => {
-                Self::Vec3(Property::new(meta.default.clone().dispatch().unwrap()))
-            }
-            DataTypeKind::Bool => todo!(),
-            DataTypeKind::Int => todo!(),
-            DataTypeKind::Float => {
-                Self::Float(Property::new(meta.default.clone().dispatch().unwrap()))
-            }
+            DataTypeKind::Vec3 => Self::Vec3(Property::new(default.dispatch().unwrap())),
+            DataTypeKind::Bool => Self::Bool(Property::new(default.dispatch().unwrap())),
+            DataTypeKind::Int => Self::Int(Property::new(default.dispatch().unwrap())),
+            DataTypeKind::Float => Self::Float(Property::new(default.dispatch().unwrap())),
             DataTypeKind::Color => todo!(),
             DataTypeKind::Transform => todo!(),
             DataTypeKind::Outline => todo!(),
@@ -336,14 +346,15 @@ impl PropertyInstance {
         }
     }
 
+    /// The caller is responsible to only pass values of the same type as the Property.
     pub(crate) fn set_value(&mut self, value: OwnedDataType) {
         match self {
             PropertyInstance::Vec3(property) => property.set_value(value.dispatch().unwrap()),
             PropertyInstance::Float(property) => property.set_value(value.dispatch().unwrap()),
-            PropertyInstance::Bool(property) => todo!(),
-            PropertyInstance::Int(property) => todo!(),
-            PropertyInstance::Mesh(property) => todo!(),
-            PropertyInstance::Selection(property) => todo!(),
+            PropertyInstance::Bool(property) => property.set_value(value.dispatch().unwrap()),
+            PropertyInstance::Int(property) => property.set_value(value.dispatch().unwrap()),
+            PropertyInstance::Mesh(property) => property.set_value(value.dispatch().unwrap()),
+            PropertyInstance::Selection(property) => property.set_value(value.dispatch().unwrap()),
         }
     }
 
@@ -456,15 +467,17 @@ impl<'a> From<&'a PropertyInstance> for TypeRef<'a> {
             PropertyInstance::Float(property) => {
                 TypeRef::Float(unsafe { property.get_literal_value() })
             }
-            PropertyInstance::Bool(property) => todo!(),
-            PropertyInstance::Int(property) => todo!(),
-            PropertyInstance::Mesh(property) => todo!(),
-            PropertyInstance::Selection(property) => todo!(),
+            PropertyInstance::Bool(property) => TypeRef::Bool(property.get_literal_value()),
+            PropertyInstance::Int(property) => TypeRef::Int(property.get_literal_value()),
+            PropertyInstance::Mesh(property) => TypeRef::Mesh(property.get_literal_value()),
+            PropertyInstance::Selection(property) => {
+                TypeRef::Selection(property.get_literal_value())
+            }
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PropertyMetadata {
     pub(crate) name: String,
     pub(crate) default: OwnedDataType,
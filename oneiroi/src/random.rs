@@ -0,0 +1,27 @@
+/// A small deterministic random number generator (SplitMix64).
+/// Nodes must produce the same output for the same seed on every platform,
+/// so no thread local or OS provided randomness is used.
+#[derive(Debug, Clone)]
+pub(crate) struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A uniformly distributed value in `[0, 1)`.
+    pub(crate) fn next_f32(&mut self) -> f32 {
+        // The upper 24 bits fill the mantissa exactly.
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
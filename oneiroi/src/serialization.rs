@@ -1,24 +1,264 @@
-use std::time::Instant;
+use migration::{MigrationRegistry, MigrationReport};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::asset::Asset;
+use crate::asset::server::{AssetServer, ResolveError};
+use crate::nodes::Nodes;
+
+pub mod migration;
+#[cfg(test)]
+mod test;
+
+/// The header every file starts with so older files can be migrated.
+#[derive(Serialize)]
+struct AssetFile<'a> {
+    version: u32,
+    asset: &'a Asset,
+}
 
 pub fn serialize_asset_v1(asset: &Asset) -> String {
+    let file = AssetFile {
+        version: migration::FORMAT_VERSION,
+        asset,
+    };
     match asset {
-        Asset::Editable(_) => serde_json::to_string_pretty(&asset).expect("Serialization failed"),
-        Asset::Runtime(_) => todo!(),
+        Asset::Editable(_) => serde_json::to_string_pretty(&file).expect("Serialization failed"),
+        // The runtime form is not meant to be read by humans so keep it compact.
+        Asset::Runtime(_) => serde_json::to_string(&file).expect("Serialization failed"),
+    }
+}
+
+/// Serializes the Asset with all external dependencies inlined as embedded Assets,
+/// so the file can be loaded without the library it references.
+pub fn export_asset_bundled(
+    asset: &Asset,
+    server: &mut AssetServer,
+) -> Result<String, ResolveError> {
+    let bundled = server.bundle(asset)?;
+    Ok(serialize_asset_v1(&bundled))
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AssetLoadError {
+    #[error("The file is not valid json: {0}")]
+    Syntax(#[source] serde_json::Error),
+    #[error("The file is neither a binary Asset nor valid UTF-8: {0}")]
+    Encoding(#[source] std::string::FromUtf8Error),
+    #[error("The file doesn't start with the header of a binary Asset")]
+    NotBinary,
+    #[error("The binary Asset is invalid: {0}")]
+    Binary(#[source] rmp_serde::decode::Error),
+    #[error(
+        "The file was written with format version {0} but only up to version {latest} is supported",
+        latest = migration::FORMAT_VERSION
+    )]
+    UnsupportedVersion(u32),
+    #[error("Node {node} of type {variant} at {path} is invalid: {source}")]
+    Node {
+        path: String,
+        node: usize,
+        variant: String,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("The Asset at {path} is invalid: {source}")]
+    Asset {
+        path: String,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// Loads an Asset with the default migrations.
+/// Use [`deserialize_asset_migrated`] to find out whether the file had to be upgraded.
+pub fn deserialize_asset_v1(asset: &str) -> Result<Asset, AssetLoadError> {
+    deserialize_asset_migrated(asset, &MigrationRegistry::default()).map(|(asset, _)| asset)
+}
+
+/// Loads an Asset written by any version of the format and upgrades outdated nodes
+/// with the migrations of the registry.
+pub fn deserialize_asset_migrated(
+    asset: &str,
+    registry: &MigrationRegistry,
+) -> Result<(Asset, MigrationReport), AssetLoadError> {
+    let file = serde_json::from_str(asset).map_err(AssetLoadError::Syntax)?;
+    let (content, report) = registry.migrate(file)?;
+    match Asset::deserialize(&content) {
+        Ok(asset) => Ok((asset, report)),
+        Err(source) => {
+            let path = match report.from_version {
+                migration::LEGACY_FORMAT_VERSION => "$".to_string(),
+                _ => "$.asset".to_string(),
+            };
+            Err(locate_error(&content, path, source))
+        }
     }
 }
 
-//bundle all external dependencies in subgraphs
-pub fn export_asset_bundled() {}
+/// Identifies binary Asset files. It is followed by the format version as little endian u32.
+pub const BINARY_MAGIC: [u8; 4] = *b"ONIB";
+const BINARY_HEADER_LEN: usize = BINARY_MAGIC.len() + size_of::<u32>();
+
+/// Encodes the Asset as MessagePack which is much smaller and faster to load than json,
+/// especially for runtime Assets with baked meshes. Meant for shipping, not version control.
+pub fn serialize_asset_binary(asset: &Asset) -> Vec<u8> {
+    let mut bytes = Vec::from(BINARY_MAGIC);
+    bytes.extend(migration::FORMAT_VERSION.to_le_bytes());
+    // Structs are written with their field names so the migrations can work on the content.
+    rmp_serde::encode::write_named(&mut bytes, asset).expect("Serialization failed");
+    bytes
+}
+
+pub fn is_binary_asset(bytes: &[u8]) -> bool {
+    bytes.starts_with(&BINARY_MAGIC)
+}
 
-pub fn deserialize_asset_v1(asset: &str) -> Asset {
-    let computation_instant = Instant::now();
+pub fn deserialize_asset_binary(bytes: &[u8]) -> Result<Asset, AssetLoadError> {
+    deserialize_asset_binary_migrated(bytes, &MigrationRegistry::default()).map(|(asset, _)| asset)
+}
 
-    let p: Asset = serde_json::from_str(asset).expect("Couldnt parse file into asset");
-    println!(
-        "Asset serialization took {} nanoseconds",
-        computation_instant.elapsed().as_nanos()
+/// Same as for json but files written in the current version skip the migrations entirely.
+pub fn deserialize_asset_binary_migrated(
+    bytes: &[u8],
+    registry: &MigrationRegistry,
+) -> Result<(Asset, MigrationReport), AssetLoadError> {
+    if !is_binary_asset(bytes) || bytes.len() < BINARY_HEADER_LEN {
+        return Err(AssetLoadError::NotBinary);
+    }
+    let version = u32::from_le_bytes(
+        bytes[BINARY_MAGIC.len()..BINARY_HEADER_LEN]
+            .try_into()
+            .unwrap(),
     );
-    p
+    let content = &bytes[BINARY_HEADER_LEN..];
+
+    if version == migration::FORMAT_VERSION {
+        let asset = rmp_serde::from_slice(content)
+            .map_err(|source| locate_binary_error(content, source))?;
+        return Ok((asset, MigrationReport::current()));
+    }
+
+    let content: Value = rmp_serde::from_slice(content).map_err(AssetLoadError::Binary)?;
+    let (content, report) = registry.migrate(header(version, content))?;
+    let asset = Asset::deserialize(&content)
+        .map_err(|source| locate_error(&content, "$".to_string(), source))?;
+    Ok((asset, report))
+}
+
+/// MessagePack errors don't say where they happened, so the content is read again as a plain
+/// structure to locate the invalid node the same way as for json.
+fn locate_binary_error(content: &[u8], source: rmp_serde::decode::Error) -> AssetLoadError {
+    let Ok(content) = rmp_serde::from_slice::<Value>(content) else {
+        return AssetLoadError::Binary(source);
+    };
+    match Asset::deserialize(&content) {
+        Err(source) => locate_error(&content, "$".to_string(), source),
+        Ok(_) => AssetLoadError::Binary(source),
+    }
+}
+
+/// Converts a json Asset into its binary form e.g. to keep json in version control and ship binary.
+pub fn json_to_binary(json: &str) -> Result<Vec<u8>, AssetLoadError> {
+    let (asset, _) = deserialize_asset_migrated(json, &MigrationRegistry::default())?;
+    Ok(serialize_asset_binary(&asset))
+}
+
+pub fn binary_to_json(bytes: &[u8]) -> Result<String, AssetLoadError> {
+    Ok(serialize_asset_v1(&deserialize_asset_binary(bytes)?))
+}
+
+fn header(version: u32, asset: Value) -> Value {
+    serde_json::json!({ "version": version, "asset": asset })
+}
+
+/// The untagged Asset only reports that no variant matched so search for the node
+/// which fails on its own to point at the actual problem.
+fn locate_error(asset: &Value, path: String, source: serde_json::Error) -> AssetLoadError {
+    let graph = &asset["graph"];
+    let holes = graph["node_holes"]
+        .as_array()
+        .map(|holes| holes.iter().filter_map(Value::as_u64).collect::<Vec<_>>())
+        .unwrap_or_default();
+    let graph_nodes = graph["nodes"].as_array().into_iter().flatten();
+    // Removed nodes are not stored but still occupy their index.
+    let node_indices = (0..).filter(|index| !holes.contains(index));
+    let graph_nodes = graph_nodes
+        .zip(node_indices)
+        .enumerate()
+        .map(|(position, (entry, node))| {
+            (
+                format!("{path}.graph.nodes[{position}][1]"),
+                node,
+                &entry[1],
+            )
+        });
+
+    // The runtime form only keeps the dynamic nodes in the template.
+    let template_nodes = asset["template"]["dependency_node_map"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .map(|(position, entry)| {
+            (
+                format!("{path}.template.dependency_node_map[{position}][1][1]"),
+                entry[0].as_u64().unwrap_or_default(),
+                &entry[1][1],
+            )
+        });
+
+    for (node_path, node, value) in graph_nodes.chain(template_nodes) {
+        if let Err(source) = Nodes::deserialize(value) {
+            let variant = match value {
+                Value::String(variant) => variant.clone(),
+                Value::Object(map) => map.keys().next().cloned().unwrap_or_default(),
+                _ => String::new(),
+            };
+            return AssetLoadError::Node {
+                path: node_path,
+                node: node as usize,
+                variant,
+                source,
+            };
+        }
+    }
+
+    let embedded_assets = asset["embedded_assets"].as_array().into_iter().flatten();
+    for (position, embedded_asset) in embedded_assets.enumerate() {
+        let inner = &embedded_asset["Internal"][0];
+        if !inner.is_null() && Asset::deserialize(inner).is_err() {
+            let inner_path = format!("{path}.embedded_assets[{position}].Internal[0]");
+            return locate_error(inner, inner_path, source);
+        }
+    }
+
+    AssetLoadError::Asset { path, source }
+}
+
+/// Stores a map as a list of entries instead.
+/// Json only supports string keys which can't be parsed back once buffered by untagged enums.
+pub(crate) mod map_entries {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub(crate) fn serialize<'a, M, K, V, S>(map: &'a M, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        &'a M: IntoIterator<Item = (&'a K, &'a V)>,
+        K: Serialize + 'a,
+        V: Serialize + 'a,
+        S: Serializer,
+    {
+        serializer.collect_seq(map)
+    }
+
+    pub(crate) fn deserialize<'de, M, K, V, D>(deserializer: D) -> Result<M, D::Error>
+    where
+        M: FromIterator<(K, V)>,
+        K: Deserialize<'de>,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let entries = Vec::<(K, V)>::deserialize(deserializer)?;
+        Ok(entries.into_iter().collect())
+    }
 }
@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use super::AssetLoadError;
+
+/// The version written into the header of every file.
+/// Files without a header predate the versioning and count as version 1.
+pub const FORMAT_VERSION: u32 = 2;
+pub(crate) const LEGACY_FORMAT_VERSION: u32 = 1;
+
+/// Structural changes of the file layout, the first entry upgrades version 1 to version 2.
+const FORMAT_MIGRATIONS: [fn(&mut Value); (FORMAT_VERSION - LEGACY_FORMAT_VERSION) as usize] = [
+    // Version 2 introduced the header, files without one may still store Mesh edges as a map.
+    edges_as_entries,
+];
+
+/// Meshes used to store their edges as a map before they were written as a list of entries.
+/// Json can't hold the Edge keys of that map so only Meshes without edges were ever written,
+/// but keys which are json themselves are parsed anyway.
+fn edges_as_entries(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            let is_mesh = ["faces_of_point", "points_of_face", "edges_of_face"]
+                .iter()
+                .all(|key| map.contains_key(*key));
+            if is_mesh && let Some(Value::Object(edges)) = map.get_mut("edges") {
+                let entries = std::mem::take(edges)
+                    .into_iter()
+                    .map(|(key, faces)| {
+                        let key = serde_json::from_str(&key).unwrap_or(Value::String(key));
+                        Value::Array(vec![key, faces])
+                    })
+                    .collect();
+                map.insert("edges".into(), Value::Array(entries));
+            }
+            map.values_mut().for_each(edges_as_entries);
+        }
+        Value::Array(values) => values.iter_mut().for_each(edges_as_entries),
+        _ => {}
+    }
+}
+
+/// Upgrades the serialized content of a Nodes variant into its successor
+/// e.g. a BoxV1 into a BoxV2. Returns the name of the new variant with its content.
+pub type NodeMigration = fn(Value) -> (&'static str, Value);
+
+/// Every node migration applied while loading a file, grouped by the variants involved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeUpgrade {
+    pub from: String,
+    pub to: String,
+    pub count: usize,
+}
+
+/// Describes how a file was brought up to date while loading it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationReport {
+    pub from_version: u32,
+    pub nodes: Vec<NodeUpgrade>,
+}
+
+impl MigrationReport {
+    /// The report of a file which is already in the current format.
+    pub(crate) fn current() -> Self {
+        Self {
+            from_version: FORMAT_VERSION,
+            nodes: Vec::new(),
+        }
+    }
+
+    /// Whether the file was already written in the current format.
+    pub fn is_empty(&self) -> bool {
+        self.from_version == FORMAT_VERSION && self.nodes.is_empty()
+    }
+
+    fn record(&mut self, from: String, to: String) {
+        match self
+            .nodes
+            .iter_mut()
+            .find(|upgrade| upgrade.from == from && upgrade.to == to)
+        {
+            Some(upgrade) => upgrade.count += 1,
+            None => self.nodes.push(NodeUpgrade { from, to, count: 1 }),
+        }
+    }
+}
+
+/// Holds the migration of every Nodes variant which got replaced by a newer version.
+pub struct MigrationRegistry {
+    nodes: HashMap<&'static str, NodeMigration>,
+}
+
+impl Default for MigrationRegistry {
+    /// The registry with all migrations shipped with this version of the crate.
+    /// Whenever a node variant gets replaced its migration has to be registered here.
+    fn default() -> Self {
+        Self {
+            nodes: HashMap::new(),
+        }
+    }
+}
+
+impl MigrationRegistry {
+    /// Registers the migration for the given variant. Migrations are chained
+    /// so a BoxV1 is upgraded through BoxV2 if that one has a migration as well.
+    pub fn register(&mut self, variant: &'static str, migration: NodeMigration) {
+        self.nodes.insert(variant, migration);
+    }
+
+    /// Reads the header and brings the content up to the current format.
+    pub(crate) fn migrate(&self, file: Value) -> Result<(Value, MigrationReport), AssetLoadError> {
+        let (version, mut content) = split_header(file)?;
+        if version > FORMAT_VERSION {
+            return Err(AssetLoadError::UnsupportedVersion(version));
+        }
+
+        for migration in &FORMAT_MIGRATIONS[(version - LEGACY_FORMAT_VERSION) as usize..] {
+            migration(&mut content);
+        }
+
+        let mut report = MigrationReport {
+            from_version: version,
+            nodes: Vec::new(),
+        };
+        self.migrate_nodes(&mut content, &mut report);
+        Ok((content, report))
+    }
+
+    /// Nodes are stored externally tagged so a node whose single key is a registered variant
+    /// is outdated. This also covers the nodes of embedded and runtime Assets.
+    fn migrate_nodes(&self, asset: &mut Value, report: &mut MigrationReport) {
+        for_each_node(asset, &mut |node| {
+            let Value::Object(map) = node else {
+                return;
+            };
+            let is_outdated = map.len() == 1
+                && map
+                    .keys()
+                    .next()
+                    .is_some_and(|variant| self.nodes.contains_key(variant.as_str()));
+            if is_outdated {
+                let (variant, content) = std::mem::take(map).into_iter().next().unwrap();
+                let (variant, content) = self.upgrade(variant, content, report);
+                map.insert(variant, content);
+            }
+        });
+    }
+
+    fn upgrade(
+        &self,
+        original: String,
+        mut content: Value,
+        report: &mut MigrationReport,
+    ) -> (String, Value) {
+        let mut variant = original.clone();
+        // Bounded by the amount of migrations so a faulty registration can't loop forever.
+        for _ in 0..self.nodes.len() {
+            let Some(migration) = self.nodes.get(variant.as_str()) else {
+                break;
+            };
+            let (next, next_content) = migration(content);
+            variant = next.to_string();
+            content = next_content;
+        }
+        report.record(original, variant.clone());
+        (variant, content)
+    }
+}
+
+/// Visits every serialized node of the Asset, which are the graph nodes of the editable form
+/// and the template nodes of the runtime form, including those of internal embedded Assets.
+fn for_each_node(asset: &mut Value, visit: &mut impl FnMut(&mut Value)) {
+    // Indexing mutably would insert the missing keys so the values are looked up with pointers.
+    let graph_nodes = asset
+        .pointer_mut("/graph/nodes")
+        .and_then(Value::as_array_mut);
+    // Each graph node is stored together with its editor metadata.
+    for entry in graph_nodes.into_iter().flatten() {
+        if let Some(node) = entry.get_mut(1) {
+            visit(node);
+        }
+    }
+    let template_nodes = asset
+        .pointer_mut("/template/dependency_node_map")
+        .and_then(Value::as_array_mut);
+    // Each template node is stored together with its index and dependencies.
+    for entry in template_nodes.into_iter().flatten() {
+        if let Some(node) = entry.pointer_mut("/1/1") {
+            visit(node);
+        }
+    }
+    let embedded_assets = asset
+        .pointer_mut("/embedded_assets")
+        .and_then(Value::as_array_mut);
+    for embedded_asset in embedded_assets.into_iter().flatten() {
+        if let Some(inner) = embedded_asset.pointer_mut("/Internal/0") {
+            for_each_node(inner, visit);
+        }
+    }
+}
+
+fn split_header(file: Value) -> Result<(u32, Value), AssetLoadError> {
+    match file {
+        Value::Object(mut map) if map.len() == 2 && map.contains_key("asset") => {
+            let version = map.get("version").and_then(Value::as_u64).unwrap_or(0);
+            let version = u32::try_from(version).unwrap_or(u32::MAX);
+            if version < LEGACY_FORMAT_VERSION {
+                return Err(AssetLoadError::UnsupportedVersion(version));
+            }
+            Ok((version, map.remove("asset").unwrap()))
+        }
+        legacy => Ok((LEGACY_FORMAT_VERSION, legacy)),
+    }
+}
@@ -0,0 +1,192 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::migration::{FORMAT_VERSION, MigrationRegistry, NodeUpgrade};
+use super::{
+    AssetLoadError, BINARY_MAGIC, binary_to_json, deserialize_asset_binary,
+    deserialize_asset_binary_migrated, deserialize_asset_migrated, deserialize_asset_v1,
+    json_to_binary, serialize_asset_v1,
+};
+use crate::asset::Asset;
+use crate::asset::editable::EditableAsset;
+use crate::type_system::data_types::Mesh;
+
+/// Serializes the default Asset the way files were written before the header existed.
+fn legacy_file() -> String {
+    serde_json::to_string(&Asset::Editable(EditableAsset::default())).unwrap()
+}
+
+#[test]
+pub fn files_are_written_with_header() {
+    let file: Value = serde_json::from_str(&serialize_asset_v1(&Asset::default())).unwrap();
+    assert_eq!(file["version"], FORMAT_VERSION);
+
+    let (_, report) =
+        deserialize_asset_migrated(&file.to_string(), &MigrationRegistry::default()).unwrap();
+    assert!(report.is_empty());
+}
+
+#[test]
+pub fn legacy_files_are_migrated() {
+    let (asset, report) =
+        deserialize_asset_migrated(&legacy_file(), &MigrationRegistry::default()).unwrap();
+    assert!(matches!(asset, Asset::Editable(_)));
+    assert_eq!(report.from_version, 1);
+    assert!(report.nodes.is_empty());
+}
+
+/// Replaces every Mesh with an empty one storing its edges the way they were before the header.
+fn with_legacy_meshes(value: &mut Value) {
+    match value {
+        Value::Object(map) if map.contains_key("points_of_face") => {
+            *value = serde_json::to_value(Mesh::default()).unwrap();
+            value["edges"] = Value::Object(Default::default());
+        }
+        Value::Object(map) => map.values_mut().for_each(with_legacy_meshes),
+        Value::Array(values) => values.iter_mut().for_each(with_legacy_meshes),
+        _ => {}
+    }
+}
+
+#[test]
+pub fn legacy_mesh_edges_are_migrated() {
+    let runtime = Asset::Runtime(Asset::default().to_runtime());
+    let mut file = serde_json::to_value(&runtime).unwrap();
+    with_legacy_meshes(&mut file);
+    assert!(file.to_string().contains("\"edges\":{}"));
+    assert!(Asset::deserialize(&file).is_err());
+
+    let (asset, report) =
+        deserialize_asset_migrated(&file.to_string(), &MigrationRegistry::default()).unwrap();
+    assert_eq!(report.from_version, 1);
+    // Once migrated the file is written in the current encoding and loads without changes.
+    let (_, report) =
+        deserialize_asset_migrated(&serialize_asset_v1(&asset), &MigrationRegistry::default())
+            .unwrap();
+    assert!(report.is_empty());
+}
+
+#[test]
+pub fn outdated_nodes_are_upgraded() {
+    // Pretend the Box node used to be stored as a BoxV0 which a chain of migrations upgrades.
+    let file = legacy_file().replace("\"BoxV1\"", "\"BoxV0\"");
+    let mut registry = MigrationRegistry::default();
+    registry.register("BoxV0", |content| ("BoxLegacy", content));
+    registry.register("BoxLegacy", |content| ("BoxV1", content));
+
+    let (asset, report) = deserialize_asset_migrated(&file, &registry).unwrap();
+    assert_eq!(
+        report.nodes,
+        vec![NodeUpgrade {
+            from: "BoxV0".into(),
+            to: "BoxV1".into(),
+            count: 1
+        }]
+    );
+    let upgraded = serialize_asset_v1(&asset);
+    assert!(upgraded.contains("BoxV1") && !upgraded.contains("BoxV0"));
+}
+
+#[test]
+pub fn only_nodes_are_upgraded() {
+    // The baked Meshes of a runtime Asset are tagged like a node would be.
+    let runtime = Asset::Runtime(Asset::default().to_runtime());
+    let file = serde_json::to_string(&runtime).unwrap();
+    assert!(file.contains("{\"Mesh\":"));
+    let mut registry = MigrationRegistry::default();
+    registry.register("Mesh", |content| ("MeshV2", content));
+
+    let (_, report) = deserialize_asset_migrated(&file, &registry).unwrap();
+    assert!(report.nodes.is_empty());
+}
+
+#[test]
+pub fn newer_versions_are_rejected() {
+    let file = format!(
+        "{{\"version\":{},\"asset\":{}}}",
+        FORMAT_VERSION + 1,
+        legacy_file()
+    );
+    assert!(deserialize_asset_migrated(&file, &MigrationRegistry::default()).is_err());
+}
+
+#[test]
+pub fn invalid_nodes_are_located() {
+    let mut file: Value = serde_json::from_str(&serialize_asset_v1(&Asset::default())).unwrap();
+    file["asset"]["graph"]["nodes"][2][1]["BoxV1"]["size"] = "large".into();
+
+    match deserialize_asset_v1(&file.to_string()) {
+        Err(AssetLoadError::Node {
+            path,
+            node,
+            variant,
+            ..
+        }) => {
+            assert_eq!(path, "$.asset.graph.nodes[2][1]");
+            assert_eq!(node, 2);
+            assert_eq!(variant, "BoxV1");
+        }
+        other => panic!("Expected the Box node to be invalid but got {other:?}"),
+    }
+
+    // Nodes added by a newer version are unknown variants.
+    let file = legacy_file().replace("\"BoxV1\"", "\"BoxV9\"");
+    assert!(matches!(
+        deserialize_asset_v1(&file),
+        Err(AssetLoadError::Node { variant, .. }) if variant == "BoxV9"
+    ));
+
+    assert!(matches!(
+        deserialize_asset_v1("{\"version\":"),
+        Err(AssetLoadError::Syntax(_))
+    ));
+}
+
+#[test]
+pub fn binary_roundtrips_with_json() {
+    let editable = Asset::default();
+    let runtime = Asset::Runtime(editable.to_runtime());
+
+    for asset in [editable, runtime] {
+        let json = serialize_asset_v1(&asset);
+        let binary = json_to_binary(&json).unwrap();
+        assert!(binary.starts_with(&BINARY_MAGIC));
+        assert!(binary.len() < json.len());
+
+        assert_eq!(binary_to_json(&binary).unwrap(), json);
+        let loaded = deserialize_asset_binary(&binary).unwrap();
+        assert_eq!(
+            std::mem::discriminant(&loaded),
+            std::mem::discriminant(&asset)
+        );
+    }
+
+    // Older binary files go through the same migrations as json.
+    let mut outdated = json_to_binary(&legacy_file()).unwrap();
+    outdated[BINARY_MAGIC.len()..][..4].copy_from_slice(&1u32.to_le_bytes());
+    let (_, report) =
+        deserialize_asset_binary_migrated(&outdated, &MigrationRegistry::default()).unwrap();
+    assert_eq!(report.from_version, 1);
+
+    assert!(matches!(
+        deserialize_asset_binary(legacy_file().as_bytes()),
+        Err(AssetLoadError::NotBinary)
+    ));
+}
+
+#[test]
+pub fn invalid_binary_nodes_are_located() {
+    let mut asset = serde_json::to_value(Asset::default()).unwrap();
+    asset["graph"]["nodes"][2][1]["BoxV1"]["size"] = "large".into();
+    let mut binary = Vec::from(BINARY_MAGIC);
+    binary.extend(FORMAT_VERSION.to_le_bytes());
+    rmp_serde::encode::write_named(&mut binary, &asset).unwrap();
+
+    match deserialize_asset_binary(&binary) {
+        Err(AssetLoadError::Node { path, node, .. }) => {
+            assert_eq!(path, "$.graph.nodes[2][1]");
+            assert_eq!(node, 2);
+        }
+        other => panic!("Expected the Box node to be invalid but got {other:?}"),
+    }
+}
@@ -22,10 +22,14 @@ pub use curve::CubicBezier;
 pub use curve::Curve;
 pub use instance::Instance;
 pub use material::Material;
-pub use mesh::{IndexedMeshBuffers, Mesh};
+pub use mesh::{
+    AttributeBuffer, AttributeBufferValues, AttributeDomain, IndexedMeshBuffers, Mesh,
+    TopologyIssue,
+};
+pub(crate) use mesh::{BooleanMode, NormalWeighting, UvProjection, box_uv};
 pub use outline::Outline;
 pub use primitives::{Color, Float, Int, Transform, Vec3};
-pub use selection::Selection;
+pub use selection::{Selection, SelectionError};
 pub use texture::Texture;
 
 use crate::type_system::variants::{OwnedDataType, TypeRef};
@@ -141,14 +145,14 @@ pub(crate) trait DataType: Clone + Default {
     //TODO need to implement defualt string representation
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) enum DataTypeConfiguration {
     Int(),
     Float(),
 }
 
 //TODO evalutate is the fields should be private and which functions should be there
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TypeDescriptor {
     pub r#type: DataTypeKind,
     pub mutable: bool,
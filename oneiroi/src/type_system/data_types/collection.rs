@@ -1,10 +1,11 @@
 use serde::{Deserialize, Serialize};
 
+use crate::type_system::trait_types::Transformable;
 use crate::type_system::variants::{OwnedDataType, TypeRef};
 
-use super::{DataType, DataTypeKind};
+use super::{DataType, DataTypeKind, Transform};
 
-#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
 pub struct Collection {
     r#type: DataTypeKind,
     data: Vec<OwnedDataType>,
@@ -44,6 +45,29 @@ impl Collection {
     pub fn length(&self) -> usize {
         self.data.len()
     }
+
+    /// All values with nested Collections replaced by their values.
+    pub fn flatten(&self) -> Vec<&OwnedDataType> {
+        let mut values = Vec::with_capacity(self.data.len());
+        for value in &self.data {
+            match value {
+                OwnedDataType::Collection(collection) => values.extend(collection.flatten()),
+                value => values.push(value),
+            }
+        }
+        values
+    }
+}
+
+/// Values which can't be transformed, e.g. Floats, are left unchanged.
+impl Transformable for Collection {
+    fn transform(&mut self, transform: &Transform) {
+        for value in &mut self.data {
+            if let Some(value) = value.as_transformable_mut() {
+                value.transform(transform);
+            }
+        }
+    }
 }
 
 impl DataType for Collection {
@@ -5,43 +5,41 @@ use crate::{
     ImVec,
     type_system::{
         data_types::{DataType, DataTypeKind, Transform, Vec3},
-        variants::{TypeRef, OwnedDataType},
+        trait_types::Transformable,
+        variants::{OwnedDataType, TypeRef},
     },
 };
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct Curve {
     control_points: ImVec<Vec4>,
     knot_vector: ImVec<f32>,
     degree: usize,
 }
 
-impl Curve {
-    pub(crate) fn new(
-        control_points: impl IntoIterator<Item = Vec4>,
-        knot_vector: impl IntoIterator<Item = f32>,
-        degree: usize,
-    ) -> Self {
-        Self {
-            degree,
-            control_points: ImVec::from_iter(control_points),
-            knot_vector: ImVec::from_iter(knot_vector),
+impl Transformable for Curve {
+    /// The weights in the w component of the control points stay unchanged.
+    fn transform(&mut self, transform: &Transform) {
+        for point in self.control_points.iter_mut() {
+            *point = transform.transform_point3(point.truncate()).extend(point.w);
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct CubicBezier {
     ctrl_points: Vec<Vec3>,
 }
 
-impl CubicBezier {
-    pub(crate) fn new() -> Self {
-        Self {
-            ctrl_points: Vec::new(),
+impl Transformable for CubicBezier {
+    fn transform(&mut self, transform: &Transform) {
+        for point in &mut self.ctrl_points {
+            *point = transform.transform_point3(*point);
         }
     }
+}
 
+impl CubicBezier {
     pub fn with_points(points: Vec<Vec3>) -> Self {
         Self {
             ctrl_points: points,
@@ -136,6 +134,57 @@ impl CubicBezier {
         unsafe { transforms.assume_init() }
     }
 
+    /// Places the Transforms at equal distances along the whole curve,
+    /// the first one at the start and the last one at the end.
+    pub(crate) fn sample_evenly(&self, count: usize) -> Box<[Transform]> {
+        const STEPS: usize = 15;
+        let arcs = self.num_arcs();
+        let steps = arcs * STEPS;
+        if count == 0 || steps == 0 {
+            return Box::default();
+        }
+        let progress = |step: usize| (arcs * step) as f32 / steps as f32;
+
+        // The length of the curve up to each step.
+        let mut lengths = Vec::with_capacity(steps + 1);
+        lengths.push(0.0);
+        let mut previous = self.sample(0.0);
+        for step in 1..=steps {
+            let position = self.sample(progress(step));
+            lengths.push(lengths[step - 1] + (position - previous).length());
+            previous = position;
+        }
+        let curve_length = lengths[steps];
+
+        (0..count)
+            .map(|index| {
+                let distance = if count == 1 {
+                    0.0
+                } else {
+                    curve_length * index as f32 / (count - 1) as f32
+                };
+                let step = lengths
+                    .partition_point(|length| *length < distance)
+                    .clamp(1, steps);
+                let (start, end) = (lengths[step - 1], lengths[step]);
+                let fraction = if end > start {
+                    (distance - start) / (end - start)
+                } else {
+                    0.0
+                };
+                let t = progress(step - 1).lerp(progress(step), fraction);
+
+                let position = self.sample(t);
+                let tangent = self.tangent(t);
+                if tangent == Vec3::ZERO {
+                    Transform::from_translation(position)
+                } else {
+                    frame_to_affine(position, tangent, Vec3::Y)
+                }
+            })
+            .collect()
+    }
+
     pub(crate) fn get_flattenend_segment_lengths(&self, steps: usize) -> Box<[(f32, f32)]> {
         let arcs = self.num_arcs();
         let samples = self.multi_sample(arcs * steps);
@@ -2,13 +2,14 @@ use serde::{Deserialize, Serialize};
 
 use crate::type_system::{
     data_types::Transform,
+    trait_types::Transformable,
     variants::{TypeRef, OwnedDataType},
     reference::Reference,
 };
 
 use super::{DataType, DataTypeKind};
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Instance {
     #[serde(skip)]
     reference: Reference,
@@ -42,6 +43,12 @@ impl Instance {
     } */
 }
 
+impl Transformable for Instance {
+    fn transform(&mut self, transform: &Transform) {
+        self.transform = *transform * self.transform;
+    }
+}
+
 impl DataType for Instance {
     const DATA_TYPE_TYPE: DataTypeKind = DataTypeKind::Instance;
 
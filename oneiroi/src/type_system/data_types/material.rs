@@ -5,7 +5,7 @@ use crate::{
     type_system::variants::{TypeRef, OwnedDataType},
 };
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct Material {
     albedo: Color,
 }
@@ -2,7 +2,7 @@ use std::fmt::Debug;
 
 use std::num::NonZeroU32;
 
-use glam::Vec3;
+use glam::{Vec2, Vec3, Vec4};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
@@ -13,8 +13,30 @@ use crate::type_system::variants::{OwnedDataType, TypeRef};
 use crate::type_system::reference::Reference;
 use crate::{ImHashMap, ImHashSet, ImVec};
 
+use attributes::Attribute;
+
+mod attributes;
+mod bevel;
+mod boolean;
+mod join;
+mod mirror;
+mod sampling;
+mod shading;
+mod shapes;
+mod subdivide;
+mod tangents;
+mod transform;
+mod uv;
+mod validate;
+
+pub(crate) use attributes::Element;
+pub use attributes::{AttributeBuffer, AttributeBufferValues, AttributeDomain};
+pub(crate) use boolean::BooleanMode;
+pub(crate) use shading::NormalWeighting;
+pub(crate) use uv::{UV_ATTRIBUTE, UvProjection, box_uv};
+pub use validate::TopologyIssue;
 #[cfg(test)]
-mod tests;
+pub(super) mod tests;
 
 #[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, Serialize, Deserialize, PartialOrd, Ord)]
 pub struct PointHandle(NonZeroU32);
@@ -37,7 +59,7 @@ impl PointHandle {
 /// An Edge id is 8 byte wide and consists of two points.
 /// To be a valid Edge the smaller PointHandle always needs to be at the front of the tuple.
 /// This is done because Edges are in a Mesh are undirected hence a convention needs to be created.
-#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, Serialize, Deserialize, PartialOrd, Ord)]
 pub struct Edge(PointHandle, PointHandle);
 
 impl Edge {
@@ -64,6 +86,11 @@ impl Edge {
         Edge(points.0, points.1)
     }
 
+    #[inline]
+    pub(crate) fn points(&self) -> [PointHandle; 2] {
+        [self.0, self.1]
+    }
+
     /// Returns the Point handle which is not the input handle
     /// SAFETY: must supply valid point handle
     #[inline]
@@ -116,6 +143,16 @@ pub struct IndexedMeshBuffers {
     pub normals: Vec<Vec3>,
 
     pub indices: Vec<u32>,
+
+    /// Empty if the Mesh has no UVs, otherwise one for every vertex.
+    pub uvs: Vec<Vec2>,
+
+    /// Empty if the Mesh has no UVs or they weren't requested, otherwise one for every vertex.
+    /// The handedness of the bitangent is stored in w.
+    pub tangents: Vec<Vec4>,
+
+    /// The other point, face and corner attributes with a value for every vertex.
+    pub attributes: Vec<AttributeBuffer>,
 }
 
 trait FacesOfEdge {
@@ -136,13 +173,14 @@ impl FacesOfEdge for ImHashMap<Edge, [Option<FaceHandle>; 2]> {
 /// - Always maintain a correct topolgy without exceptions.
 /// - Use structural stahring to allow for efficient mutation.
 ///   Therefore it separates the connectivity from the data and uses a data-oriented approach.
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Mesh {
     //Stores only connectivity information!
     //edges_of_point: Vector<SmallVec<EdgeHandle, 3>>,
     faces_of_point: ImVec<SmallVec<FaceHandle, 3>>,
 
     //This is a HashMap to satisfy the querying needs in light of smooth shading.
+    #[serde(with = "crate::serialization::map_entries")]
     edges: ImHashMap<Edge, [Option<FaceHandle>; 2]>,
 
     points_of_face: ImVec<[PointHandle; 3]>,
@@ -160,9 +198,16 @@ pub struct Mesh {
     data_points_position: ImVec<Vec3>,
     //data_faces_normal: ImVec<Vec3>,
     data_hard_edge: ImHashSet<Edge>,
-    //data_faces_material: ImHashMap<FaceHandle, u8>,
+    // Faces which don't use the material of the whole Mesh e.g. after combining two Meshes.
+    #[serde(default, with = "crate::serialization::map_entries")]
+    data_faces_material: ImHashMap<FaceHandle, Option<Reference>>,
+    // Named layers of custom values for the points, edges, faces or corners.
+    #[serde(default)]
+    data_attributes: ImVec<Attribute>,
+    // How the faces around a point are weighted when smoothing its normal.
+    #[serde(default)]
+    normal_weighting: NormalWeighting,
 
-    // TODO allow for multible surfaces and materials
     material: Option<Reference>,
 }
 
@@ -214,6 +259,7 @@ impl Mesh {
             self.data_points_position.len() == self.faces_of_point.len(),
             "The Index of data_points_position is not equal to points length!"
         );
+        self.grow_attributes();
 
         let new_len = self.faces_of_point.len() + 1;
 
@@ -238,6 +284,57 @@ impl Mesh {
         }
     }
 
+    pub(crate) fn point_handles(&self) -> impl Iterator<Item = PointHandle> + use<> {
+        (1..=self.faces_of_point.len()).map(PointHandle::new)
+    }
+
+    pub(crate) fn face_handles(&self) -> impl Iterator<Item = FaceHandle> + use<> {
+        (1..=self.points_of_face.len()).map(FaceHandle::new)
+    }
+
+    /// All Edges sorted by their points.
+    pub(crate) fn edge_handles(&self) -> Vec<Edge> {
+        let mut edges = self.edges.keys().copied().collect::<Vec<_>>();
+        edges.sort();
+        edges
+    }
+
+    pub(crate) fn faces_in_point(&self, point: PointHandle) -> &[FaceHandle] {
+        &self.faces_of_point[point.idx()]
+    }
+
+    /// The one or two faces the Edge belongs to.
+    pub(crate) fn faces_in_edge(&self, edge: Edge) -> impl Iterator<Item = FaceHandle> + use<> {
+        self.edges
+            .get(&edge)
+            .copied()
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+    }
+
+    /// Every Edge connected to the point, Edges shared by two faces are returned twice.
+    pub(crate) fn edges_in_point(&self, point: PointHandle) -> impl Iterator<Item = Edge> + '_ {
+        self.faces_in_point(point)
+            .iter()
+            .flat_map(|face| self.edges_in_face(*face))
+            .filter(move |edge| edge.0 == point || edge.1 == point)
+    }
+
+    pub(crate) fn face_normal(&self, face: FaceHandle) -> Vec3 {
+        self.calc_face_normal(face).normalize_or_zero()
+    }
+
+    /// Whether both Meshes are unchanged clones of each other.
+    /// Only compares the pointers of the shared data so it is not exhaustive for small Meshes.
+    pub(crate) fn is_same_mesh(&self, other: &Mesh) -> bool {
+        self.points_of_face.ptr_eq(&other.points_of_face)
+            && self
+                .data_points_position
+                .ptr_eq(&other.data_points_position)
+            && self.edges.ptr_eq(&other.edges)
+    }
+
     /// Calculates the face normal to use later.
     /// The returned normal is not normalized.
     fn calc_face_normal(&self, face: FaceHandle) -> Vec3 {
@@ -255,21 +352,13 @@ impl Mesh {
 
     /// Calculates the Point Normals of the Mesh which can be used to render it.
     fn calculate_point_normals(&self) -> Vec<Vec3> {
-        // First calculate the unnormalized Face normals.
-        // Preallocate for every Face
-        let mut face_normals = Box::new_uninit_slice(self.points_of_face.len());
-        for face in 1..=self.points_of_face.len() {
-            face_normals[face - 1].write(self.calc_face_normal(FaceHandle::new(face)));
-        }
-        let face_normals = unsafe { face_normals.assume_init() };
-
         // Preallocate for every Point.
         let mut normals = Vec::with_capacity(self.faces_of_point.len());
-        for point in &self.faces_of_point {
-            // Summate the normals of the adjacent Faces of Point.
+        for point in self.point_handles() {
+            // Summate the weighted normals of the adjacent Faces of Point.
             let mut normal = Vec3::ZERO;
-            for face in point {
-                normal += face_normals[face.idx()];
+            for face in self.faces_in_point(point) {
+                normal += self.weighted_face_normal(*face, point);
             }
             normals.push(normal.normalize_or_zero());
         }
@@ -281,32 +370,58 @@ impl Mesh {
         self.data_hard_edge.insert(edge);
     }
 
+    /// Whether the Edge is hard and has a face on both sides whose normals are not smoothed.
     fn has_normal_contention(&self, edge: Edge) -> bool {
-        self.edges.is_full(edge) //TODO
+        self.data_hard_edge.contains(&edge) && self.edges.is_full(edge)
     }
 
     /// Returns the Buffers which can be used to render the Mesh.
+    /// Tangents are only needed for normal mapping so they are only calculated if requested.
     /// TODO only return positions and indices most likely everything else on demand.
-    pub fn get_index_mesh_buffers(&self) -> IndexedMeshBuffers {
-        for hard_edge in self.data_hard_edge.iter() {
-            if self.has_normal_contention(*hard_edge) {}
-        }
+    pub fn get_index_mesh_buffers(&self, tangents: bool) -> IndexedMeshBuffers {
+        // Points are split into multiple vertices across hard Edges
+        // and where their face or corner attributes differ.
+        let groups = self.smoothing_groups();
+        let (vertices, indices) = self.buffer_vertices(groups.as_deref());
 
-        let positions: Vec<Vec3> = self
-            .data_points_position
+        let positions: Vec<Vec3> = vertices
             .iter()
-            .copied()
+            .map(|(point, _)| self.position(*point))
             .collect::<Vec<_>>();
 
-        let normals: Vec<Vec3> = self.calculate_point_normals();
+        let normals: Vec<Vec3> = match groups {
+            Some(groups) => {
+                let corner_normals = self.calculate_corner_normals(&groups);
+                vertices
+                    .iter()
+                    .map(|(_, corner)| corner_normals[corner.unwrap()])
+                    .collect()
+            }
+            None => {
+                let point_normals = self.calculate_point_normals();
+                vertices
+                    .iter()
+                    .map(|(point, _)| point_normals[point.idx()])
+                    .collect()
+            }
+        };
 
-        let indices = self
-            .points_of_face
-            .clone()
-            .into_iter()
-            .flatten()
-            .map(|h| h.get())
-            .collect::<Vec<_>>();
+        let mut attributes = self.attribute_buffers(&vertices);
+        let uvs = match attributes
+            .iter()
+            .position(|attribute| attribute.name == UV_ATTRIBUTE)
+        {
+            Some(index) => match attributes.remove(index).values {
+                AttributeBufferValues::Vec3(uvs) => uvs.into_iter().map(Vec3::truncate).collect(),
+                _ => Vec::new(),
+            },
+            None => Vec::new(),
+        };
+        let tangents = if tangents && !uvs.is_empty() {
+            Self::calculate_tangents(&positions, &normals, &uvs, &indices)
+        } else {
+            Vec::new()
+        };
 
         println!(
             "Pos: {positions:?}, Nor: {normals:?}, Ind: {indices:?}, Lengths: {},{},{}",
@@ -319,6 +434,9 @@ impl Mesh {
             positions,
             normals,
             indices,
+            uvs,
+            tangents,
+            attributes,
         }
     }
 
@@ -327,11 +445,42 @@ impl Mesh {
     pub(crate) fn set_material(&mut self, material: Reference) {
         //println!("Why are we not setting the materiall?");
         self.material = Some(material);
+        self.data_faces_material.clear();
     }
 
     pub fn get_material_ref(&self) -> Option<Reference> {
         self.material
     }
+
+    /// The material of the face which falls back to the material of the whole Mesh.
+    pub fn get_face_material_ref(&self, face: FaceHandle) -> Option<Reference> {
+        self.data_faces_material
+            .get(&face)
+            .copied()
+            .unwrap_or(self.material)
+    }
+
+    pub(crate) fn set_face_material(&mut self, face: FaceHandle, material: Option<Reference>) {
+        if material == self.material {
+            self.data_faces_material.remove(&face);
+        } else {
+            self.data_faces_material.insert(face, material);
+        }
+    }
+
+    /// Every material used by at least one face in the order of their first use.
+    pub fn get_material_refs(&self) -> Vec<Reference> {
+        let mut materials = Vec::new();
+        for material in self
+            .face_handles()
+            .filter_map(|face| self.get_face_material_ref(face))
+        {
+            if !materials.contains(&material) {
+                materials.push(material);
+            }
+        }
+        materials
+    }
 }
 
 impl DataType for Mesh {
@@ -354,7 +503,13 @@ impl DataType for Mesh {
     }
 
     fn intrinsic_attributes() -> Option<Box<[ArributeMetadata]>> {
-        None
+        Some(
+            vec![ArributeMetadata {
+                name: "position".to_string(),
+                r#type: DataTypeKind::Vec3,
+            }]
+            .into_boxed_slice(),
+        )
     }
 
     fn to_data_type_value(&self) -> OwnedDataType {
@@ -394,6 +549,7 @@ impl MeshMut0D for Mesh {
             self.data_points_position.len() == self.faces_of_point.len(),
             "The Index of data_points_position is not equal to points length!"
         );
+        self.grow_attributes();
 
         PointHandle::new(self.faces_of_point.len())
     }
@@ -405,9 +561,8 @@ impl MeshMut1D for Mesh {
     // Should be sorted already -> smaller index of point in least significant bits
     unsafe fn add_edge_unchecked(&mut self, points: (PointHandle, PointHandle)) -> Edge {
         let new_handle = Edge(points.0, points.1);
-        if self.edges.insert(new_handle, [None; 2]).is_some() {
-            //self.faces_of_edge.push_back([None; 2]);
-        }
+        // Edges shared with existing faces must keep them.
+        self.edges.entry(new_handle).or_insert([None; 2]);
 
         //TODO self.edges_in_point
 
@@ -458,6 +613,7 @@ impl MeshMut2D for Mesh {
             self.edges_of_face.len() == self.points_of_face.len(),
             "The length of edges_in_face and points_in_face must be equal!"
         );
+        self.grow_attributes();
 
         new_face
     }
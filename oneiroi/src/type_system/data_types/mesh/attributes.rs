@@ -0,0 +1,783 @@
+use std::collections::{HashMap, HashSet};
+
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+use super::{Edge, FaceHandle, Mesh, PointHandle};
+use crate::type_system::data_types::{ArributeMetadata, Color, DataType, DataTypeKind, Float, Int};
+use crate::type_system::trait_types::MeshMut0D;
+use crate::{ImHashMap, ImVec};
+
+/// Which kind of element of a Mesh an attribute stores its values for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AttributeDomain {
+    Point,
+    Edge,
+    Face,
+    /// Every point of every face, which allows for discontinuities like UV seams.
+    Corner,
+}
+
+/// A single element of a Mesh.
+/// Corners are the points of a face in its winding order, indexed from 0 to 2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Element {
+    Point(PointHandle),
+    Edge(Edge),
+    Face(FaceHandle),
+    Corner(FaceHandle, usize),
+}
+
+impl Element {
+    pub(crate) fn domain(&self) -> AttributeDomain {
+        match self {
+            Element::Point(_) => AttributeDomain::Point,
+            Element::Edge(_) => AttributeDomain::Edge,
+            Element::Face(_) => AttributeDomain::Face,
+            Element::Corner(_, _) => AttributeDomain::Corner,
+        }
+    }
+
+    fn key(&self) -> LayerKey {
+        match *self {
+            Element::Point(point) => LayerKey::Index(point.idx()),
+            Element::Edge(edge) => LayerKey::Edge(edge),
+            Element::Face(face) => LayerKey::Index(face.idx()),
+            Element::Corner(face, corner) => {
+                debug_assert!(corner < 3, "A triangle only has three corners");
+                LayerKey::Index(face.idx() * 3 + corner)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum AttributeError {
+    NotFound,
+    WrongType,
+    WrongDomain,
+}
+
+#[derive(Clone, Copy)]
+enum LayerKey {
+    Index(usize),
+    Edge(Edge),
+}
+
+/// The values of one attribute.
+/// Points, faces and corners are stored in the order of their handles,
+/// Edges have no order so only the ones differing from the default are stored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct AttributeLayer<T: Clone> {
+    default: T,
+    values: ImVec<T>,
+    #[serde(with = "crate::serialization::map_entries")]
+    edges: ImHashMap<Edge, T>,
+}
+
+impl<T: Copy> AttributeLayer<T> {
+    fn new(default: T, len: usize) -> Self {
+        Self {
+            default,
+            values: std::iter::repeat_n(default, len).collect(),
+            edges: ImHashMap::default(),
+        }
+    }
+
+    fn get(&self, key: LayerKey) -> T {
+        match key {
+            LayerKey::Index(index) => self.values[index],
+            LayerKey::Edge(edge) => self.edges.get(&edge).copied().unwrap_or(self.default),
+        }
+    }
+
+    fn set(&mut self, key: LayerKey, value: T) {
+        match key {
+            LayerKey::Index(index) => self.values[index] = value,
+            LayerKey::Edge(edge) => {
+                self.edges.insert(edge, value);
+            }
+        }
+    }
+
+    fn grow(&mut self, len: usize) {
+        while self.values.len() < len {
+            self.values.push_back(self.default);
+        }
+    }
+}
+
+impl<T: AttributeType> AttributeLayer<T> {
+    fn into_values(self) -> AttributeValues {
+        T::into_values(self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) enum AttributeValues {
+    Float(AttributeLayer<Float>),
+    Int(AttributeLayer<Int>),
+    Bool(AttributeLayer<bool>),
+    Vec3(AttributeLayer<Vec3>),
+    Color(AttributeLayer<Color>),
+}
+
+/// Runs the same code for the layer of any type.
+macro_rules! with_layer {
+    ($values:expr, $layer:ident => $body:expr) => {
+        match $values {
+            AttributeValues::Float($layer) => $body,
+            AttributeValues::Int($layer) => $body,
+            AttributeValues::Bool($layer) => $body,
+            AttributeValues::Vec3($layer) => $body,
+            AttributeValues::Color($layer) => $body,
+        }
+    };
+}
+
+impl AttributeValues {
+    fn kind(&self) -> DataTypeKind {
+        match self {
+            AttributeValues::Float(_) => DataTypeKind::Float,
+            AttributeValues::Int(_) => DataTypeKind::Int,
+            AttributeValues::Bool(_) => DataTypeKind::Bool,
+            AttributeValues::Vec3(_) => DataTypeKind::Vec3,
+            AttributeValues::Color(_) => DataTypeKind::Color,
+        }
+    }
+
+    /// A layer of the same type with the same default for `len` elements.
+    fn empty_like(&self, len: usize) -> Self {
+        with_layer!(self, layer => AttributeLayer::new(layer.default, len).into_values())
+    }
+
+    fn copy_value(&mut self, other: &AttributeValues, from: LayerKey, to: LayerKey) {
+        match (self, other) {
+            (AttributeValues::Float(layer), AttributeValues::Float(other)) => {
+                layer.set(to, other.get(from))
+            }
+            (AttributeValues::Int(layer), AttributeValues::Int(other)) => {
+                layer.set(to, other.get(from))
+            }
+            (AttributeValues::Bool(layer), AttributeValues::Bool(other)) => {
+                layer.set(to, other.get(from))
+            }
+            (AttributeValues::Vec3(layer), AttributeValues::Vec3(other)) => {
+                layer.set(to, other.get(from))
+            }
+            (AttributeValues::Color(layer), AttributeValues::Color(other)) => {
+                layer.set(to, other.get(from))
+            }
+            _ => {}
+        }
+    }
+
+    fn interpolate_value(
+        &mut self,
+        other: &AttributeValues,
+        from: &[(LayerKey, f32)],
+        to: LayerKey,
+    ) {
+        fn blend<T: AttributeType>(
+            layer: &mut AttributeLayer<T>,
+            other: &AttributeLayer<T>,
+            from: &[(LayerKey, f32)],
+            to: LayerKey,
+        ) {
+            let values = from
+                .iter()
+                .map(|(key, weight)| (other.get(*key), *weight))
+                .collect::<Vec<_>>();
+            layer.set(to, T::blend(&values));
+        }
+        match (self, other) {
+            (AttributeValues::Float(layer), AttributeValues::Float(other)) => {
+                blend(layer, other, from, to)
+            }
+            (AttributeValues::Int(layer), AttributeValues::Int(other)) => {
+                blend(layer, other, from, to)
+            }
+            (AttributeValues::Bool(layer), AttributeValues::Bool(other)) => {
+                blend(layer, other, from, to)
+            }
+            (AttributeValues::Vec3(layer), AttributeValues::Vec3(other)) => {
+                blend(layer, other, from, to)
+            }
+            (AttributeValues::Color(layer), AttributeValues::Color(other)) => {
+                blend(layer, other, from, to)
+            }
+            _ => {}
+        }
+    }
+
+    /// The values of the elements in the given order.
+    fn gather(&self, indices: impl Iterator<Item = usize>) -> AttributeBufferValues {
+        match self {
+            AttributeValues::Float(layer) => {
+                AttributeBufferValues::Float(indices.map(|index| layer.values[index]).collect())
+            }
+            AttributeValues::Int(layer) => {
+                AttributeBufferValues::Int(indices.map(|index| layer.values[index]).collect())
+            }
+            AttributeValues::Bool(layer) => {
+                AttributeBufferValues::Bool(indices.map(|index| layer.values[index]).collect())
+            }
+            AttributeValues::Vec3(layer) => {
+                AttributeBufferValues::Vec3(indices.map(|index| layer.values[index]).collect())
+            }
+            AttributeValues::Color(layer) => {
+                AttributeBufferValues::Color(indices.map(|index| layer.values[index]).collect())
+            }
+        }
+    }
+}
+
+/// A DataType which can be stored for the elements of a Mesh.
+pub(crate) trait AttributeType: DataType + Copy {
+    fn layer(values: &AttributeValues) -> Option<&AttributeLayer<Self>>;
+
+    fn layer_mut(values: &mut AttributeValues) -> Option<&mut AttributeLayer<Self>>;
+
+    fn into_values(layer: AttributeLayer<Self>) -> AttributeValues;
+
+    /// The raw bits of the value, equal bits mean the vertices can be shared.
+    fn bits(&self) -> [u32; 4];
+
+    /// The weighted average of the values, whose weights add up to one.
+    /// Types which can't be averaged take the value with the largest weight.
+    fn blend(values: &[(Self, f32)]) -> Self {
+        values
+            .iter()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(value, _)| *value)
+            .unwrap_or_default()
+    }
+}
+
+impl AttributeType for Float {
+    fn layer(values: &AttributeValues) -> Option<&AttributeLayer<Self>> {
+        match values {
+            AttributeValues::Float(layer) => Some(layer),
+            _ => None,
+        }
+    }
+
+    fn layer_mut(values: &mut AttributeValues) -> Option<&mut AttributeLayer<Self>> {
+        match values {
+            AttributeValues::Float(layer) => Some(layer),
+            _ => None,
+        }
+    }
+
+    fn into_values(layer: AttributeLayer<Self>) -> AttributeValues {
+        AttributeValues::Float(layer)
+    }
+
+    fn bits(&self) -> [u32; 4] {
+        [self.to_bits(), 0, 0, 0]
+    }
+
+    fn blend(values: &[(Self, f32)]) -> Self {
+        values.iter().map(|(value, weight)| value * weight).sum()
+    }
+}
+
+impl AttributeType for Int {
+    fn layer(values: &AttributeValues) -> Option<&AttributeLayer<Self>> {
+        match values {
+            AttributeValues::Int(layer) => Some(layer),
+            _ => None,
+        }
+    }
+
+    fn layer_mut(values: &mut AttributeValues) -> Option<&mut AttributeLayer<Self>> {
+        match values {
+            AttributeValues::Int(layer) => Some(layer),
+            _ => None,
+        }
+    }
+
+    fn into_values(layer: AttributeLayer<Self>) -> AttributeValues {
+        AttributeValues::Int(layer)
+    }
+
+    fn bits(&self) -> [u32; 4] {
+        [*self as u32, (*self >> 32) as u32, 0, 0]
+    }
+}
+
+impl AttributeType for bool {
+    fn layer(values: &AttributeValues) -> Option<&AttributeLayer<Self>> {
+        match values {
+            AttributeValues::Bool(layer) => Some(layer),
+            _ => None,
+        }
+    }
+
+    fn layer_mut(values: &mut AttributeValues) -> Option<&mut AttributeLayer<Self>> {
+        match values {
+            AttributeValues::Bool(layer) => Some(layer),
+            _ => None,
+        }
+    }
+
+    fn into_values(layer: AttributeLayer<Self>) -> AttributeValues {
+        AttributeValues::Bool(layer)
+    }
+
+    fn bits(&self) -> [u32; 4] {
+        [*self as u32, 0, 0, 0]
+    }
+}
+
+impl AttributeType for Vec3 {
+    fn layer(values: &AttributeValues) -> Option<&AttributeLayer<Self>> {
+        match values {
+            AttributeValues::Vec3(layer) => Some(layer),
+            _ => None,
+        }
+    }
+
+    fn layer_mut(values: &mut AttributeValues) -> Option<&mut AttributeLayer<Self>> {
+        match values {
+            AttributeValues::Vec3(layer) => Some(layer),
+            _ => None,
+        }
+    }
+
+    fn into_values(layer: AttributeLayer<Self>) -> AttributeValues {
+        AttributeValues::Vec3(layer)
+    }
+
+    fn bits(&self) -> [u32; 4] {
+        [self.x.to_bits(), self.y.to_bits(), self.z.to_bits(), 0]
+    }
+
+    fn blend(values: &[(Self, f32)]) -> Self {
+        values.iter().map(|(value, weight)| *value * *weight).sum()
+    }
+}
+
+impl AttributeType for Color {
+    fn layer(values: &AttributeValues) -> Option<&AttributeLayer<Self>> {
+        match values {
+            AttributeValues::Color(layer) => Some(layer),
+            _ => None,
+        }
+    }
+
+    fn layer_mut(values: &mut AttributeValues) -> Option<&mut AttributeLayer<Self>> {
+        match values {
+            AttributeValues::Color(layer) => Some(layer),
+            _ => None,
+        }
+    }
+
+    fn into_values(layer: AttributeLayer<Self>) -> AttributeValues {
+        AttributeValues::Color(layer)
+    }
+
+    fn bits(&self) -> [u32; 4] {
+        self.to_rgba().map(f32::to_bits)
+    }
+
+    fn blend(values: &[(Self, f32)]) -> Self {
+        let mut rgba = [0.0; 4];
+        for (value, weight) in values {
+            for (component, value) in rgba.iter_mut().zip(value.to_rgba()) {
+                *component += value * weight;
+            }
+        }
+        Color::from_srgb(rgba)
+    }
+}
+
+/// A named layer of values for all elements of one domain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Attribute {
+    name: String,
+    domain: AttributeDomain,
+    values: AttributeValues,
+}
+
+/// The values of an attribute for every vertex of the IndexedMeshBuffers.
+#[derive(Debug, Clone)]
+pub enum AttributeBufferValues {
+    Float(Vec<Float>),
+    Int(Vec<Int>),
+    Bool(Vec<bool>),
+    Vec3(Vec<Vec3>),
+    Color(Vec<Color>),
+}
+
+#[derive(Debug, Clone)]
+pub struct AttributeBuffer {
+    pub name: String,
+    pub values: AttributeBufferValues,
+}
+
+impl Mesh {
+    fn domain_len(&self, domain: AttributeDomain) -> usize {
+        match domain {
+            AttributeDomain::Point => self.faces_of_point.len(),
+            AttributeDomain::Edge => 0,
+            AttributeDomain::Face => self.points_of_face.len(),
+            AttributeDomain::Corner => self.points_of_face.len() * 3,
+        }
+    }
+
+    /// Fills the attributes with their default for newly added elements.
+    pub(super) fn grow_attributes(&mut self) {
+        if self.data_attributes.is_empty() {
+            return;
+        }
+        let lens = [
+            AttributeDomain::Point,
+            AttributeDomain::Face,
+            AttributeDomain::Corner,
+        ]
+        .map(|domain| (domain, self.domain_len(domain)));
+        for attribute in self.data_attributes.iter_mut() {
+            let Some((_, len)) = lens.iter().find(|(domain, _)| *domain == attribute.domain) else {
+                continue;
+            };
+            with_layer!(&mut attribute.values, layer => layer.grow(*len));
+        }
+    }
+
+    /// Swaps the last two corners of the face to follow a flipped winding order.
+    pub(super) fn flip_corner_attributes(&mut self, face: FaceHandle) {
+        for attribute in self.data_attributes.iter_mut() {
+            if attribute.domain == AttributeDomain::Corner {
+                let first = face.idx() * 3;
+                with_layer!(&mut attribute.values, layer => layer.values.swap(first + 1, first + 2));
+            }
+        }
+    }
+
+    fn find_attribute(&self, name: &str) -> Option<&Attribute> {
+        self.data_attributes
+            .iter()
+            .find(|attribute| attribute.name == name)
+    }
+
+    fn find_attribute_mut(&mut self, name: &str) -> Option<&mut Attribute> {
+        self.data_attributes
+            .iter_mut()
+            .find(|attribute| attribute.name == name)
+    }
+
+    /// Adds a new attribute with the default value for every element of the domain.
+    /// An existing attribute with the same name gets replaced.
+    pub(crate) fn add_attribute<T: AttributeType>(
+        &mut self,
+        name: &str,
+        domain: AttributeDomain,
+        default: T,
+    ) {
+        let attribute = Attribute {
+            name: name.to_owned(),
+            domain,
+            values: AttributeLayer::new(default, self.domain_len(domain)).into_values(),
+        };
+        match self.find_attribute_mut(name) {
+            Some(existing) => *existing = attribute,
+            None => self.data_attributes.push_back(attribute),
+        }
+    }
+
+    /// Returns whether the attribute existed.
+    pub(crate) fn remove_attribute(&mut self, name: &str) -> bool {
+        let len = self.data_attributes.len();
+        self.data_attributes
+            .retain(|attribute| attribute.name != name);
+        self.data_attributes.len() != len
+    }
+
+    /// All values of the attribute in the order of the elements.
+    /// Edge attributes have no order so they are not available as a whole.
+    pub(crate) fn attribute_values<T: AttributeType>(
+        &self,
+        name: &str,
+    ) -> Result<&ImVec<T>, AttributeError> {
+        let attribute = self.find_attribute(name).ok_or(AttributeError::NotFound)?;
+        if attribute.domain == AttributeDomain::Edge {
+            return Err(AttributeError::WrongDomain);
+        }
+        let layer = T::layer(&attribute.values).ok_or(AttributeError::WrongType)?;
+        Ok(&layer.values)
+    }
+
+    pub(crate) fn attribute<T: AttributeType>(
+        &self,
+        name: &str,
+        element: Element,
+    ) -> Result<T, AttributeError> {
+        let attribute = self.find_attribute(name).ok_or(AttributeError::NotFound)?;
+        if attribute.domain != element.domain() {
+            return Err(AttributeError::WrongDomain);
+        }
+        let layer = T::layer(&attribute.values).ok_or(AttributeError::WrongType)?;
+        Ok(layer.get(element.key()))
+    }
+
+    pub(crate) fn set_attribute<T: AttributeType>(
+        &mut self,
+        name: &str,
+        element: Element,
+        value: T,
+    ) -> Result<(), AttributeError> {
+        let attribute = self
+            .find_attribute_mut(name)
+            .ok_or(AttributeError::NotFound)?;
+        if attribute.domain != element.domain() {
+            return Err(AttributeError::WrongDomain);
+        }
+        let layer = T::layer_mut(&mut attribute.values).ok_or(AttributeError::WrongType)?;
+        layer.set(element.key(), value);
+        Ok(())
+    }
+
+    /// Copies the values of all attributes of an element in the other Mesh to an element of this one.
+    /// Missing attributes are added, ones with the same name but another type are skipped.
+    pub(crate) fn copy_attributes(&mut self, other: &Mesh, from: Element, to: Element) {
+        debug_assert_eq!(from.domain(), to.domain());
+        for attribute in other.data_attributes.iter() {
+            if attribute.domain != from.domain() {
+                continue;
+            }
+            if let Some(target) = self.matching_attribute(attribute) {
+                target
+                    .values
+                    .copy_value(&attribute.values, from.key(), to.key());
+            }
+        }
+    }
+
+    /// Sets the attributes of an element to the weighted average of elements in the other Mesh,
+    /// e.g. of the points at both ends of an Edge for a point placed on it.
+    /// The weights have to add up to one, attributes are added the same way as when copying.
+    pub(crate) fn interpolate_attributes(
+        &mut self,
+        other: &Mesh,
+        from: &[(Element, f32)],
+        to: Element,
+    ) {
+        debug_assert!(
+            from.iter()
+                .all(|(element, _)| element.domain() == to.domain())
+        );
+        let keys = from
+            .iter()
+            .map(|(element, weight)| (element.key(), *weight))
+            .collect::<Vec<_>>();
+        for attribute in other.data_attributes.iter() {
+            if attribute.domain != to.domain() {
+                continue;
+            }
+            if let Some(target) = self.matching_attribute(attribute) {
+                target
+                    .values
+                    .interpolate_value(&attribute.values, &keys, to.key());
+            }
+        }
+    }
+
+    /// Sets the point or corner attributes of an element to the values at the position
+    /// inside a face of the other Mesh, interpolated between the corners of that face.
+    pub(crate) fn sample_attributes(
+        &mut self,
+        other: &Mesh,
+        face: FaceHandle,
+        position: Vec3,
+        to: Element,
+    ) {
+        let weights = other.barycentric(face, position);
+        let points = other.points_in_face(face);
+        let from = (0..3)
+            .map(|corner| match to {
+                Element::Point(_) => (Element::Point(points[corner]), weights[corner]),
+                Element::Corner(_, _) => (Element::Corner(face, corner), weights[corner]),
+                Element::Edge(_) | Element::Face(_) => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        self.interpolate_attributes(other, &from, to);
+    }
+
+    /// The weights of the corners of the face for the position projected onto it.
+    /// Positions outside of the face are moved onto its border.
+    fn barycentric(&self, face: FaceHandle, position: Vec3) -> [f32; 3] {
+        let [a, b, c] = self.points_in_face(face).map(|point| self.position(point));
+        let normal = (b - a).cross(c - a);
+        let area = normal.length_squared();
+        if area <= f32::EPSILON * f32::EPSILON {
+            return [1.0, 0.0, 0.0];
+        }
+        let weights = [(b, c), (c, a), (a, b)]
+            .map(|(next, previous)| (next - position).cross(previous - position).dot(normal) / area)
+            .map(|weight| weight.max(0.0));
+        let sum = weights.iter().sum::<f32>();
+        if sum <= f32::EPSILON {
+            return [1.0, 0.0, 0.0];
+        }
+        weights.map(|weight| weight / sum)
+    }
+
+    /// The attribute of this Mesh with the name and domain of the other one, added if missing.
+    /// Returns None if an attribute with the same name but another domain exists.
+    fn matching_attribute(&mut self, attribute: &Attribute) -> Option<&mut Attribute> {
+        if self.find_attribute(&attribute.name).is_none() {
+            let len = self.domain_len(attribute.domain);
+            self.data_attributes.push_back(Attribute {
+                name: attribute.name.clone(),
+                domain: attribute.domain,
+                values: attribute.values.empty_like(len),
+            });
+        }
+        self.find_attribute_mut(&attribute.name)
+            .filter(|target| target.domain == attribute.domain)
+    }
+
+    /// Copies the attributes of every element in the other Mesh to the element it became in this one.
+    /// Elements which were removed are skipped.
+    pub(crate) fn copy_all_attributes(
+        &mut self,
+        other: &Mesh,
+        point: impl Fn(PointHandle) -> Option<PointHandle>,
+        face: impl Fn(FaceHandle) -> Option<FaceHandle>,
+    ) {
+        if other.data_attributes.is_empty() {
+            return;
+        }
+        for old in other.point_handles() {
+            let Some(new) = point(old) else {
+                continue;
+            };
+            self.copy_attributes(other, Element::Point(old), Element::Point(new));
+        }
+        for old in other.face_handles() {
+            let Some(new) = face(old) else {
+                continue;
+            };
+            self.copy_attributes(other, Element::Face(old), Element::Face(new));
+            for corner in 0..3 {
+                self.copy_attributes(
+                    other,
+                    Element::Corner(old, corner),
+                    Element::Corner(new, corner),
+                );
+            }
+        }
+        let edges = other
+            .data_attributes
+            .iter()
+            .flat_map(|attribute| {
+                with_layer!(&attribute.values, layer => layer.edges.keys().copied().collect::<Vec<_>>())
+            })
+            .collect::<HashSet<_>>();
+        for old in edges {
+            let (Some(first), Some(second)) = (point(old.0), point(old.1)) else {
+                continue;
+            };
+            if first == second {
+                continue;
+            }
+            let new = Edge::new((first, second));
+            self.copy_attributes(other, Element::Edge(old), Element::Edge(new));
+        }
+    }
+
+    /// The name, type and domain of every attribute in the order they were added.
+    pub(crate) fn attributes_metadata(&self) -> Box<[(AttributeDomain, ArributeMetadata)]> {
+        self.data_attributes
+            .iter()
+            .map(|attribute| {
+                let metadata = ArributeMetadata {
+                    name: attribute.name.clone(),
+                    r#type: attribute.values.kind(),
+                };
+                (attribute.domain, metadata)
+            })
+            .collect()
+    }
+
+    /// The vertices to render as the point and the corner they are taken from.
+    /// Points are only split when face or corner attributes differ between their faces
+    /// or when their corners belong to different smoothing groups.
+    pub(super) fn buffer_vertices(
+        &self,
+        groups: Option<&[u32]>,
+    ) -> (Vec<(PointHandle, Option<usize>)>, Vec<u32>) {
+        let split = groups.is_some()
+            || self.data_attributes.iter().any(|attribute| {
+                matches!(
+                    attribute.domain,
+                    AttributeDomain::Face | AttributeDomain::Corner
+                )
+            });
+        if !split {
+            let vertices = self.point_handles().map(|point| (point, None)).collect();
+            let indices = self
+                .points_of_face
+                .iter()
+                .flatten()
+                .map(|point| point.get())
+                .collect();
+            return (vertices, indices);
+        }
+
+        let mut vertices = Vec::new();
+        let mut indices = Vec::with_capacity(self.points_of_face.len() * 3);
+        let mut shared = HashMap::new();
+        for (face, points) in self.points_of_face.iter().enumerate() {
+            for (corner, point) in points.iter().enumerate() {
+                let corner = face * 3 + corner;
+                let key = self
+                    .data_attributes
+                    .iter()
+                    .map(|attribute| {
+                        let index = match attribute.domain {
+                            AttributeDomain::Face => face,
+                            AttributeDomain::Corner => corner,
+                            AttributeDomain::Point | AttributeDomain::Edge => return [0; 4],
+                        };
+                        with_layer!(&attribute.values, layer => layer.values[index].bits())
+                    })
+                    .collect::<Vec<_>>();
+                let group = groups.map_or(0, |groups| groups[corner]);
+                let index = *shared.entry((*point, group, key)).or_insert_with(|| {
+                    vertices.push((*point, Some(corner)));
+                    vertices.len() as u32 - 1
+                });
+                indices.push(index);
+            }
+        }
+        (vertices, indices)
+    }
+
+    /// The values of all point, face and corner attributes for the vertices.
+    pub(super) fn attribute_buffers(
+        &self,
+        vertices: &[(PointHandle, Option<usize>)],
+    ) -> Vec<AttributeBuffer> {
+        self.data_attributes
+            .iter()
+            .filter_map(|attribute| {
+                let index = |(point, corner): &(PointHandle, Option<usize>)| match attribute.domain
+                {
+                    AttributeDomain::Point => point.idx(),
+                    AttributeDomain::Face => corner.unwrap() / 3,
+                    AttributeDomain::Corner => corner.unwrap(),
+                    AttributeDomain::Edge => unreachable!(),
+                };
+                if attribute.domain == AttributeDomain::Edge {
+                    return None;
+                }
+                Some(AttributeBuffer {
+                    name: attribute.name.clone(),
+                    values: attribute.values.gather(vertices.iter().map(index)),
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,553 @@
+use std::collections::HashMap;
+
+use glam::Vec3;
+
+use super::{Edge, Element, FaceHandle, Mesh, PointHandle};
+use crate::type_system::trait_types::{MeshMut0D, MeshMut2D};
+
+/// Edges between faces which are closer to coplanar than this are not beveled
+/// since the bevel would have no visible effect, e.g. the diagonals of a triangulated quad.
+const FLAT_EDGE_COS: f32 = 0.9998;
+
+/// How the faces of one sector around a beveled point get their new corner.
+/// A sector is a fan of faces around the point which is bounded by selected or boundary edges.
+enum SectorCorner {
+    /// The corner stays at the original point.
+    Keep,
+    /// All faces of the sector share a new point offset into the sector.
+    Moved(Vec3),
+    /// The sector is only bounded by a single selected Edge, so the two faces next to it
+    /// slide their corners along their other Edge, splitting the faces behind it.
+    Split([(FaceHandle, Edge); 2]),
+}
+
+/// The points around a beveled point which have to be closed with new faces.
+enum Hole {
+    /// The Edge chains of all strips ending at the point form a closed polygon,
+    /// otherwise the hole is filled like a Fan.
+    Cycle,
+    /// The polygon is closed by the original point in the middle of the split faces.
+    Fan,
+}
+
+impl Mesh {
+    /// Bevels the given Edges with the width measured perpendicular to the Edge.
+    /// Every segment adds a row of faces which follow a rounded profile towards the original Edge.
+    ///
+    /// Only Edges between two faces which are not coplanar can be beveled, others are ignored.
+    /// Returns a new Mesh since every face touching a beveled point has to be rebuilt.
+    /// Attributes of new points and corners are sampled from the original face they lie in.
+    pub(crate) fn bevel_edges(
+        &self,
+        edges: impl IntoIterator<Item = Edge>,
+        width: f32,
+        segments: u32,
+    ) -> Mesh {
+        let mut selected = edges
+            .into_iter()
+            .filter(|edge| self.is_bevelable(*edge))
+            .collect::<Vec<_>>();
+        // Sorted to create the new points in a deterministic order.
+        selected.sort();
+        selected.dedup();
+
+        if selected.is_empty() || width <= 0.0 {
+            return self.clone();
+        }
+
+        let mut bevel = Bevel {
+            source: self,
+            selected: &selected,
+            width,
+            segments: segments.max(1),
+            mesh: Mesh {
+                material: self.material,
+                normal_weighting: self.normal_weighting,
+                ..Default::default()
+            },
+            points: HashMap::new(),
+            corners: HashMap::new(),
+            splits: HashMap::new(),
+            profiles: HashMap::new(),
+            holes: Vec::new(),
+            origins: HashMap::new(),
+            rebuilt: HashMap::new(),
+        };
+
+        let mut beveled_points = selected
+            .iter()
+            .flat_map(|edge| [edge.0, edge.1])
+            .collect::<Vec<_>>();
+        beveled_points.sort();
+        beveled_points.dedup();
+        for point in beveled_points {
+            bevel.split_point(point);
+        }
+
+        for face in 1..=self.points_of_face.len() {
+            bevel.rebuild_face(FaceHandle::new(face));
+        }
+        let mut chains: HashMap<PointHandle, Vec<Vec<PointHandle>>> = HashMap::new();
+        for edge in &selected {
+            bevel.add_strip(*edge, &mut chains);
+        }
+        for (point, hole) in std::mem::take(&mut bevel.holes) {
+            bevel.fill_hole(point, hole, chains.remove(&point).unwrap_or_default());
+        }
+        bevel.transfer_attributes();
+
+        // Hard edges stay hard as long as both of their points survived unchanged.
+        for edge in self.data_hard_edge.iter() {
+            if let (Some(a), Some(b)) = (bevel.points.get(&edge.0), bevel.points.get(&edge.1)) {
+                let new_edge = Edge::new((*a, *b));
+                if bevel.mesh.edges.contains_key(&new_edge) {
+                    bevel.mesh.set_edge_hard(new_edge);
+                }
+            }
+        }
+
+        bevel.mesh
+    }
+
+    fn is_bevelable(&self, edge: Edge) -> bool {
+        let Some([Some(face1), Some(face2)]) = self.edges.get(&edge) else {
+            return false;
+        };
+        let normal1 = self.calc_face_normal(*face1).normalize_or_zero();
+        let normal2 = self.calc_face_normal(*face2).normalize_or_zero();
+        normal1.dot(normal2) < FLAT_EDGE_COS
+    }
+
+    fn is_boundary(&self, edge: Edge) -> bool {
+        self.edges
+            .get(&edge)
+            .is_some_and(|faces| faces[1].is_none())
+    }
+
+    /// The two Edges of the face which contain the point.
+    fn edges_at_point(&self, face: FaceHandle, point: PointHandle) -> [Edge; 2] {
+        let mut edges = self
+            .edges_in_face(face)
+            .into_iter()
+            .filter(|edge| edge.0 == point || edge.1 == point);
+        [edges.next().unwrap(), edges.next().unwrap()]
+    }
+
+    /// The direction from the point inside the plane of the face which is perpendicular to the Edge.
+    fn inward_direction(&self, face: FaceHandle, edge: Edge, point: PointHandle) -> Vec3 {
+        let origin = self.position(point);
+        let along = (self.position(edge.not_point(point)) - origin).normalize_or_zero();
+        let third = self
+            .points_in_face(face)
+            .into_iter()
+            .find(|p| *p != edge.0 && *p != edge.1)
+            .unwrap();
+        let towards = self.position(third) - origin;
+        (towards - along * towards.dot(along)).normalize_or_zero()
+    }
+
+    /// Moves a point along the Edge until it is the width away from the beveled Edge.
+    /// It is kept within the first half of the Edge so splits from both ends can't cross.
+    fn slide_along(&self, point: PointHandle, edge: Edge, beveled: Edge, width: f32) -> Vec3 {
+        let origin = self.position(point);
+        let offset = self.position(edge.not_point(point)) - origin;
+        let beveled = (self.position(beveled.not_point(point)) - origin).normalize_or_zero();
+        let sin = offset.normalize_or_zero().cross(beveled).length();
+        let distance = if sin > f32::EPSILON {
+            width / sin
+        } else {
+            f32::INFINITY
+        };
+        origin + offset.normalize_or_zero() * distance.min(offset.length() * 0.5)
+    }
+}
+
+struct Bevel<'a> {
+    source: &'a Mesh,
+    selected: &'a [Edge],
+    width: f32,
+    segments: u32,
+
+    mesh: Mesh,
+    // The original points which are still used by the new Mesh.
+    points: HashMap<PointHandle, PointHandle>,
+    // Corners of faces which got replaced, indexed by the original point and face.
+    corners: HashMap<(PointHandle, FaceHandle), PointHandle>,
+    // Points inserted on original Edges, ordered from the first point of the Edge.
+    splits: HashMap<Edge, Vec<(f32, PointHandle)>>,
+    // The interior points of the rounded profile between two corners.
+    profiles: HashMap<(PointHandle, PointHandle), Box<[PointHandle]>>,
+    holes: Vec<(PointHandle, Hole)>,
+    // The original face every new point lies in, its attributes are sampled from it.
+    origins: HashMap<PointHandle, FaceHandle>,
+    // The new faces which are a part of an original face.
+    rebuilt: HashMap<FaceHandle, FaceHandle>,
+}
+
+impl Bevel<'_> {
+    fn is_selected(&self, edge: Edge) -> bool {
+        self.selected.binary_search(&edge).is_ok()
+    }
+
+    fn add_point(&mut self, position: Vec3, origin: FaceHandle) -> PointHandle {
+        let point = self.mesh.add_point(position);
+        self.origins.insert(point, origin);
+        point
+    }
+
+    fn original(&mut self, point: PointHandle) -> PointHandle {
+        if let Some(new_point) = self.points.get(&point) {
+            return *new_point;
+        }
+        let origin = self.source.faces_of_point[point.idx()][0];
+        let new_point = self.add_point(self.source.position(point), origin);
+        self.points.insert(point, new_point);
+        new_point
+    }
+
+    fn corner(&mut self, point: PointHandle, face: FaceHandle) -> PointHandle {
+        match self.corners.get(&(point, face)) {
+            Some(corner) => *corner,
+            None => self.original(point),
+        }
+    }
+
+    /// Groups the faces around the point into sectors separated by selected Edges
+    /// and decides where the corners of each sector end up.
+    fn split_point(&mut self, point: PointHandle) {
+        let source = self.source;
+        let faces = source.faces_of_point[point.idx()].clone();
+
+        // Union find over the faces which share an unselected Edge at the point.
+        let mut parents = (0..faces.len()).collect::<Vec<_>>();
+        let mut faces_of_edge: HashMap<Edge, usize> = HashMap::new();
+        let mut cuts: Vec<Vec<(FaceHandle, Edge)>> = vec![Vec::new(); faces.len()];
+        for (index, face) in faces.iter().enumerate() {
+            for edge in source.edges_at_point(*face, point) {
+                if self.is_selected(edge) || source.is_boundary(edge) {
+                    cuts[index].push((*face, edge));
+                } else if let Some(other) = faces_of_edge.insert(edge, index) {
+                    let (a, b) = (find(&parents, other), find(&parents, index));
+                    parents[a] = b;
+                }
+            }
+        }
+        let roots = (0..faces.len())
+            .map(|index| find(&parents, index))
+            .collect::<Vec<_>>();
+
+        let mut sectors: Vec<(usize, Vec<(FaceHandle, Edge)>)> = Vec::new();
+        for (index, root) in roots.iter().enumerate() {
+            match sectors.iter_mut().find(|(r, _)| r == root) {
+                Some((_, sector_cuts)) => sector_cuts.append(&mut cuts[index]),
+                None => sectors.push((*root, std::mem::take(&mut cuts[index]))),
+            }
+        }
+
+        let is_closed = sectors
+            .iter()
+            .flat_map(|(_, cuts)| cuts)
+            .all(|(_, edge)| !source.is_boundary(*edge));
+        let mut moved = 0;
+        let mut split = false;
+        for (root, cuts) in &sectors {
+            let corner = self.sector_corner(point, cuts);
+            match corner {
+                SectorCorner::Keep => {}
+                SectorCorner::Moved(position) => {
+                    moved += 1;
+                    let sector = faces
+                        .iter()
+                        .zip(&roots)
+                        .filter(|(_, r)| *r == root)
+                        .map(|(face, _)| *face)
+                        .collect::<Vec<_>>();
+                    let new_point = self.add_point(position, sector[0]);
+                    for face in sector {
+                        self.corners.insert((point, face), new_point);
+                    }
+                }
+                SectorCorner::Split(sides) => {
+                    split = true;
+                    for (face, beveled) in sides {
+                        let [a, b] = source.edges_at_point(face, point);
+                        let other = if a == beveled { b } else { a };
+                        let position = source.slide_along(point, other, beveled, self.width);
+                        let new_point = self.add_point(position, face);
+                        self.corners.insert((point, face), new_point);
+
+                        let t = (position - source.position(other.0)).length();
+                        let splits = self.splits.entry(other).or_default();
+                        splits.push((t, new_point));
+                        splits.sort_by(|a, b| a.0.total_cmp(&b.0));
+                    }
+                }
+            }
+        }
+
+        if split {
+            self.holes.push((point, Hole::Fan));
+        } else if is_closed && moved >= 3 {
+            self.holes.push((point, Hole::Cycle));
+        }
+    }
+
+    fn sector_corner(&self, point: PointHandle, cuts: &[(FaceHandle, Edge)]) -> SectorCorner {
+        let source = self.source;
+        let [(face_a, edge_a), (face_b, edge_b)] = cuts else {
+            // Points where more than two sectors meet in a single fan are not manifold
+            // so the bevel tapers off towards them instead.
+            return SectorCorner::Keep;
+        };
+        let origin = source.position(point);
+        match (self.is_selected(*edge_a), self.is_selected(*edge_b)) {
+            _ if edge_a == edge_b => SectorCorner::Split([cuts[0], cuts[1]]),
+            (true, true) => {
+                // The point which has the width as distance to both Edges.
+                let normal_a = source.inward_direction(*face_a, *edge_a, point);
+                let normal_b = source.inward_direction(*face_b, *edge_b, point);
+                let offset = (normal_a + normal_b) / (1.0 + normal_a.dot(normal_b)).max(0.1);
+                SectorCorner::Moved(origin + offset * self.width)
+            }
+            (true, false) => {
+                SectorCorner::Moved(source.slide_along(point, *edge_b, *edge_a, self.width))
+            }
+            (false, true) => {
+                SectorCorner::Moved(source.slide_along(point, *edge_a, *edge_b, self.width))
+            }
+            (false, false) => SectorCorner::Keep,
+        }
+    }
+
+    /// Adds the face again with its replaced corners and the points which split its Edges.
+    fn rebuild_face(&mut self, face: FaceHandle) {
+        let points = self.source.points_in_face(face);
+        let first_face = self.mesh.points_of_face.len() + 1;
+
+        let mut polygon = Vec::with_capacity(6);
+        // The position of the last split point inside the polygon.
+        let mut split = None;
+        for (index, point) in points.into_iter().enumerate() {
+            let corner = self.corner(point, face);
+            polygon.push(corner);
+
+            let next = points[(index + 1) % 3];
+            let next_corner = self.corner(next, face);
+            let edge = Edge::new((point, next));
+            if let Some(splits) = self.splits.get(&edge) {
+                let splits = splits
+                    .iter()
+                    .map(|(_, split)| *split)
+                    .filter(|split| *split != corner && *split != next_corner);
+                let before = polygon.len();
+                if edge.0 == point {
+                    polygon.extend(splits);
+                } else {
+                    polygon.extend(splits.rev());
+                }
+                if polygon.len() > before {
+                    split = Some(polygon.len() - 1);
+                }
+            }
+        }
+
+        match polygon.len() {
+            3 => {
+                self.mesh.add_tri([polygon[0], polygon[1], polygon[2]]);
+            }
+            // A single split point, fan from the corner opposite of it.
+            4 => {
+                let split = split.unwrap();
+                let apex = (split + 2) % 4;
+                self.mesh
+                    .add_tri([polygon[apex], polygon[(apex + 1) % 4], polygon[split]]);
+                self.mesh
+                    .add_tri([polygon[apex], polygon[split], polygon[(split + 1) % 4]]);
+            }
+            _ => self.fill_polygon(&polygon),
+        }
+
+        let material = self.source.get_face_material_ref(face);
+        for new_face in first_face..=self.mesh.points_of_face.len() {
+            let new_face = FaceHandle::new(new_face);
+            self.mesh.set_face_material(new_face, material);
+            self.rebuilt.insert(new_face, face);
+        }
+    }
+
+    /// Connects the corners of the two faces of the Edge with rows of faces.
+    fn add_strip(&mut self, edge: Edge, chains: &mut HashMap<PointHandle, Vec<Vec<PointHandle>>>) {
+        let source = self.source;
+        let [Some(face1), Some(face2)] = source.edges[&edge] else {
+            unreachable!()
+        };
+        // The strip has to run against the winding of the face containing the Edge from a to b.
+        let (a, b) = (edge.0, edge.1);
+        let points = source.points_in_face(face1);
+        let is_forward = (0..3).any(|i| points[i] == a && points[(i + 1) % 3] == b);
+        let (face1, face2) = if is_forward {
+            (face1, face2)
+        } else {
+            (face2, face1)
+        };
+
+        let profile_a = self.profile(a, face1, face2);
+        let profile_b = self.profile(b, face1, face2);
+        for i in 0..self.segments as usize {
+            let (a0, a1) = (profile_a[i], profile_a[i + 1]);
+            let (b0, b1) = (profile_b[i], profile_b[i + 1]);
+            if a0 != a1 {
+                self.mesh.add_tri([a0, a1, b1]);
+            }
+            if b0 != b1 {
+                self.mesh.add_tri([a0, b1, b0]);
+            }
+        }
+
+        chains
+            .entry(a)
+            .or_default()
+            .push(profile_a.into_iter().rev().collect());
+        chains.entry(b).or_default().push(profile_b);
+    }
+
+    /// The points from the corner of the first face to the corner of the second face
+    /// following a quadratic curve with the original point as control point.
+    fn profile(
+        &mut self,
+        point: PointHandle,
+        face1: FaceHandle,
+        face2: FaceHandle,
+    ) -> Vec<PointHandle> {
+        let start = self.corner(point, face1);
+        let end = self.corner(point, face2);
+        let segments = self.segments as usize;
+        if start == end {
+            return vec![start; segments + 1];
+        }
+
+        let interior = if let Some(interior) = self.profiles.get(&(start, end)) {
+            interior.to_vec()
+        } else if let Some(interior) = self.profiles.get(&(end, start)) {
+            interior.iter().rev().copied().collect()
+        } else {
+            let control = self.source.position(point);
+            let (from, to) = (self.mesh.position(start), self.mesh.position(end));
+            let interior = (1..segments)
+                .map(|i| {
+                    let t = i as f32 / segments as f32;
+                    let position =
+                        from * (1.0 - t) * (1.0 - t) + control * 2.0 * t * (1.0 - t) + to * t * t;
+                    // Each half of the profile lies on the side of one of the faces.
+                    let origin = if t <= 0.5 { face1 } else { face2 };
+                    self.add_point(position, origin)
+                })
+                .collect::<Box<_>>();
+            self.profiles.insert((start, end), interior.clone());
+            interior.into_vec()
+        };
+
+        let mut profile = Vec::with_capacity(segments + 1);
+        profile.push(start);
+        profile.extend(interior);
+        profile.push(end);
+        profile
+    }
+
+    fn fill_hole(&mut self, point: PointHandle, hole: Hole, chains: Vec<Vec<PointHandle>>) {
+        match hole {
+            Hole::Fan => {
+                let apex = self.original(point);
+                for chain in chains {
+                    for (a, b) in chain.iter().zip(chain.iter().skip(1)) {
+                        self.mesh.add_tri([apex, *a, *b]);
+                    }
+                }
+            }
+            Hole::Cycle => match link_chains(chains.clone()) {
+                Some(polygon) => self.fill_polygon(&polygon),
+                // The chains form several loops where surfaces only touch in the point,
+                // those are closed by the original point instead.
+                None => self.fill_hole(point, Hole::Fan, chains),
+            },
+        }
+    }
+
+    /// Samples the attributes of all new elements from the original faces.
+    /// Faces which are part of an original face are sampled from it entirely,
+    /// the faces of strips and holes take each corner from the face its point lies in.
+    fn transfer_attributes(&mut self) {
+        let source = self.source;
+        if source.data_attributes.is_empty() {
+            return;
+        }
+        for point in self.mesh.point_handles().collect::<Vec<_>>() {
+            let position = self.mesh.position(point);
+            self.mesh.sample_attributes(
+                source,
+                self.origins[&point],
+                position,
+                Element::Point(point),
+            );
+        }
+        for face in self.mesh.face_handles().collect::<Vec<_>>() {
+            let points = self.mesh.points_in_face(face);
+            let rebuilt = self.rebuilt.get(&face).copied();
+            let origin = rebuilt.unwrap_or(self.origins[&points[0]]);
+            self.mesh
+                .copy_attributes(source, Element::Face(origin), Element::Face(face));
+            for (corner, point) in points.into_iter().enumerate() {
+                let origin = rebuilt.unwrap_or(self.origins[&point]);
+                let position = self.mesh.position(point);
+                self.mesh.sample_attributes(
+                    source,
+                    origin,
+                    position,
+                    Element::Corner(face, corner),
+                );
+            }
+        }
+    }
+
+    /// Triangulates a convex polygon, larger ones get a new point in their center.
+    fn fill_polygon(&mut self, polygon: &[PointHandle]) {
+        if let [a, b, c] = polygon {
+            self.mesh.add_tri([*a, *b, *c]);
+            return;
+        }
+        let center = polygon
+            .iter()
+            .map(|point| self.mesh.position(*point))
+            .sum::<Vec3>()
+            / polygon.len() as f32;
+        let center = self.add_point(center, self.origins[&polygon[0]]);
+        for (index, point) in polygon.iter().enumerate() {
+            let next = polygon[(index + 1) % polygon.len()];
+            self.mesh.add_tri([center, *point, next]);
+        }
+    }
+}
+
+/// Links the chains of all strips ending at a point into one closed polygon.
+fn link_chains(mut chains: Vec<Vec<PointHandle>>) -> Option<Vec<PointHandle>> {
+    let mut polygon = chains.pop()?;
+    while let Some(next) = chains
+        .iter()
+        .position(|chain| chain.first() == polygon.last())
+    {
+        let next = chains.swap_remove(next);
+        polygon.extend(&next[1..]);
+    }
+    if !chains.is_empty() || polygon.first() != polygon.last() {
+        return None;
+    }
+    polygon.pop();
+    Some(polygon)
+}
+
+fn find(parents: &[usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        index = parents[index];
+    }
+    index
+}
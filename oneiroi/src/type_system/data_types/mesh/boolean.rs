@@ -0,0 +1,621 @@
+use std::collections::HashMap;
+use std::f32::consts::PI;
+
+use glam::{IVec3, Vec3};
+
+use super::{Element, FaceHandle, Mesh};
+use crate::type_system::trait_types::{MeshMut0D, MeshMut2D};
+
+/// How the volumes enclosed by two Meshes are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BooleanMode {
+    Union,
+    /// Removes the volume of the second Mesh from the first one.
+    Difference,
+    Intersection,
+}
+
+impl BooleanMode {
+    /// The mode as stored in the integer property of the BooleanV1 node.
+    /// Returns None for values which don't name a mode.
+    pub(crate) fn from_index(index: i64) -> Option<Self> {
+        match index {
+            0 => Some(BooleanMode::Union),
+            1 => Some(BooleanMode::Difference),
+            2 => Some(BooleanMode::Intersection),
+            _ => None,
+        }
+    }
+}
+
+/// Where a piece of a face lies relative to the volume of the other Mesh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Outside,
+    Inside,
+    /// On the surface of the other Mesh which points in the same direction.
+    Coplanar,
+    /// On the surface of the other Mesh which points in the opposite direction.
+    Opposite,
+}
+
+impl BooleanMode {
+    /// Whether a piece is part of the result and if its winding has to be flipped.
+    /// Coplanar surfaces are only kept once, using the piece of the first Mesh.
+    fn keep(self, first: bool, side: Side) -> Option<bool> {
+        let keep = match (self, first, side) {
+            (BooleanMode::Union, _, Side::Outside) => true,
+            (BooleanMode::Intersection, _, Side::Inside) => true,
+            (BooleanMode::Union | BooleanMode::Intersection, true, Side::Coplanar) => true,
+            (BooleanMode::Difference, true, Side::Outside | Side::Opposite) => true,
+            (BooleanMode::Difference, false, Side::Inside) => return Some(true),
+            _ => false,
+        };
+        keep.then_some(false)
+    }
+}
+
+type Triangle = [Vec3; 3];
+
+impl Mesh {
+    /// Combines the volumes enclosed by both Meshes.
+    /// Faces are split along the curves where the surfaces cross and every piece
+    /// is kept or dropped depending on whether it lies inside the other Mesh.
+    ///
+    /// Both Meshes have to be closed for the result to be closed.
+    /// Every face keeps the material of the Mesh it came from,
+    /// attributes are sampled from the face each piece was cut from.
+    pub(crate) fn boolean(&self, other: &Mesh, mode: BooleanMode) -> Mesh {
+        let first = self.triangles();
+        let second = other.triangles();
+
+        let (min, max) = first
+            .iter()
+            .chain(&second)
+            .flatten()
+            .fold((Vec3::MAX, Vec3::MIN), |(min, max), point| {
+                (min.min(*point), max.max(*point))
+            });
+        // All tolerances scale with the size of the Meshes.
+        let epsilon = 1e-5 * (max - min).length().max(1.0);
+
+        let mut cuts_first = vec![Vec::new(); first.len()];
+        let mut cuts_second = vec![Vec::new(); second.len()];
+        let mut coplanar_first = vec![false; first.len()];
+        let mut coplanar_second = vec![false; second.len()];
+        let bounds_second = second
+            .iter()
+            .map(|triangle| bounds(triangle, epsilon))
+            .collect::<Vec<_>>();
+        for (i, a) in first.iter().enumerate() {
+            let bounds_a = bounds(a, epsilon);
+            for (j, b) in second.iter().enumerate() {
+                let bounds_b = bounds_second[j];
+                if bounds_a.0.cmpgt(bounds_b.1).any() || bounds_b.0.cmpgt(bounds_a.1).any() {
+                    continue;
+                }
+                match intersect(a, b, epsilon) {
+                    Intersection::None => {}
+                    Intersection::Segment(segment) => {
+                        cuts_first[i].push(segment);
+                        cuts_second[j].push(segment);
+                    }
+                    Intersection::Coplanar => {
+                        cuts_first[i].extend(edges(b));
+                        cuts_second[j].extend(edges(a));
+                        coplanar_first[i] = true;
+                        coplanar_second[j] = true;
+                    }
+                }
+            }
+        }
+
+        let mut result = Boolean {
+            points: Welder::new(epsilon),
+            polygons: Vec::new(),
+            epsilon,
+        };
+        let sources = [
+            (self, &first, &second, cuts_first, coplanar_first, true),
+            (other, &second, &first, cuts_second, coplanar_second, false),
+        ];
+        for (_, triangles, opposite_triangles, cuts, coplanar, is_first) in sources {
+            for (index, triangle) in triangles.iter().enumerate() {
+                let face = FaceHandle::new(index + 1);
+                let normal = triangle_normal(triangle);
+                for piece in cut(triangle, normal, &cuts[index], epsilon) {
+                    let side =
+                        classify(&piece, normal, opposite_triangles, coplanar[index], epsilon);
+                    let Some(flip) = mode.keep(is_first, side) else {
+                        continue;
+                    };
+                    result.add_polygon(piece, flip, (is_first, face));
+                }
+            }
+        }
+
+        result.build(self, other)
+    }
+
+    fn triangles(&self) -> Vec<Triangle> {
+        self.face_handles()
+            .map(|face| self.points_in_face(face).map(|point| self.position(point)))
+            .collect()
+    }
+}
+
+fn bounds(triangle: &Triangle, epsilon: f32) -> (Vec3, Vec3) {
+    let min = triangle[0].min(triangle[1]).min(triangle[2]);
+    let max = triangle[0].max(triangle[1]).max(triangle[2]);
+    (min - epsilon, max + epsilon)
+}
+
+fn edges(triangle: &Triangle) -> [[Vec3; 2]; 3] {
+    [
+        [triangle[0], triangle[1]],
+        [triangle[1], triangle[2]],
+        [triangle[2], triangle[0]],
+    ]
+}
+
+fn triangle_normal(triangle: &Triangle) -> Vec3 {
+    (triangle[1] - triangle[0])
+        .cross(triangle[2] - triangle[0])
+        .normalize_or_zero()
+}
+
+enum Intersection {
+    None,
+    Segment([Vec3; 2]),
+    Coplanar,
+}
+
+fn intersect(a: &Triangle, b: &Triangle, epsilon: f32) -> Intersection {
+    let normal_a = triangle_normal(a);
+    let normal_b = triangle_normal(b);
+    if normal_a == Vec3::ZERO || normal_b == Vec3::ZERO {
+        return Intersection::None;
+    }
+
+    let direction = normal_a.cross(normal_b);
+    if direction.length() < 1e-4 {
+        let distance = normal_b.dot(a[0] - b[0]).abs();
+        return if distance <= epsilon {
+            Intersection::Coplanar
+        } else {
+            Intersection::None
+        };
+    }
+    let direction = direction.normalize();
+
+    let (Some(on_b), Some(on_a)) = (
+        plane_crossing(a, normal_b, b[0], epsilon),
+        plane_crossing(b, normal_a, a[0], epsilon),
+    ) else {
+        return Intersection::None;
+    };
+
+    // Both segments lie on the line where the planes meet, their overlap is the intersection.
+    let sort = |segment: [Vec3; 2]| {
+        if direction.dot(segment[0]) <= direction.dot(segment[1]) {
+            segment
+        } else {
+            [segment[1], segment[0]]
+        }
+    };
+    let (on_b, on_a) = (sort(on_b), sort(on_a));
+    let start = if direction.dot(on_b[0]) >= direction.dot(on_a[0]) {
+        on_b[0]
+    } else {
+        on_a[0]
+    };
+    let end = if direction.dot(on_b[1]) <= direction.dot(on_a[1]) {
+        on_b[1]
+    } else {
+        on_a[1]
+    };
+    if direction.dot(end - start) <= epsilon {
+        return Intersection::None;
+    }
+    Intersection::Segment([start, end])
+}
+
+/// The segment where the triangle crosses the plane.
+fn plane_crossing(
+    triangle: &Triangle,
+    normal: Vec3,
+    origin: Vec3,
+    epsilon: f32,
+) -> Option<[Vec3; 2]> {
+    let distances = triangle.map(|point| normal.dot(point - origin));
+    if distances.iter().all(|distance| *distance > epsilon)
+        || distances.iter().all(|distance| *distance < -epsilon)
+    {
+        return None;
+    }
+
+    let mut points: Vec<Vec3> = Vec::with_capacity(3);
+    let mut push = |point: Vec3| {
+        if points.iter().all(|other| other.distance(point) > epsilon) {
+            points.push(point);
+        }
+    };
+    for (index, distance) in distances.iter().enumerate() {
+        if distance.abs() <= epsilon {
+            push(triangle[index]);
+        }
+    }
+    for index in 0..3 {
+        let next = (index + 1) % 3;
+        let (a, b) = (distances[index], distances[next]);
+        if (a > epsilon && b < -epsilon) || (a < -epsilon && b > epsilon) {
+            push(triangle[index].lerp(triangle[next], a / (a - b)));
+        }
+    }
+
+    // A single point only touches the plane.
+    match points[..] {
+        [a, b] => Some([a, b]),
+        _ => None,
+    }
+}
+
+/// Splits the triangle into convex pieces along the lines of all cuts which cross a piece.
+/// Cuts are extended to the border of the piece they cross, the resulting T-junctions
+/// are resolved once all pieces are known.
+fn cut(triangle: &Triangle, normal: Vec3, cuts: &[[Vec3; 2]], epsilon: f32) -> Vec<Vec<Vec3>> {
+    let mut pieces = vec![triangle.to_vec()];
+    for [start, end] in cuts {
+        let plane = normal.cross(*end - *start).normalize_or_zero();
+        if plane == Vec3::ZERO {
+            continue;
+        }
+        pieces = pieces
+            .into_iter()
+            .flat_map(|piece| {
+                if !crosses(&piece, normal, *start, *end, epsilon) {
+                    return vec![piece];
+                }
+                split(piece, plane, *start, epsilon)
+            })
+            .collect();
+    }
+    pieces
+}
+
+/// Whether a part of the segment lies inside the convex polygon and not only on its border.
+fn crosses(polygon: &[Vec3], normal: Vec3, start: Vec3, end: Vec3, epsilon: f32) -> bool {
+    let direction = end - start;
+    let (mut from, mut to) = (0.0f32, 1.0f32);
+    for (index, point) in polygon.iter().enumerate() {
+        let next = polygon[(index + 1) % polygon.len()];
+        let inwards = normal.cross(next - *point).normalize_or_zero();
+        let distance = inwards.dot(start - *point);
+        let speed = inwards.dot(direction);
+        if speed.abs() < f32::EPSILON {
+            if distance < epsilon {
+                return false;
+            }
+        } else if speed > 0.0 {
+            from = from.max((epsilon - distance) / speed);
+        } else {
+            to = to.min((epsilon - distance) / speed);
+        }
+    }
+    (to - from) * direction.length() > epsilon
+}
+
+fn split(polygon: Vec<Vec3>, plane: Vec3, origin: Vec3, epsilon: f32) -> Vec<Vec<Vec3>> {
+    let sides = polygon
+        .iter()
+        .map(|point| {
+            let distance = plane.dot(*point - origin);
+            if distance > epsilon {
+                1
+            } else if distance < -epsilon {
+                -1
+            } else {
+                0
+            }
+        })
+        .collect::<Vec<i32>>();
+
+    let (mut front, mut back) = (Vec::new(), Vec::new());
+    for (index, point) in polygon.iter().enumerate() {
+        let next = (index + 1) % polygon.len();
+        if sides[index] >= 0 {
+            front.push(*point);
+        }
+        if sides[index] <= 0 {
+            back.push(*point);
+        }
+        if sides[index] * sides[next] < 0 {
+            let a = plane.dot(*point - origin);
+            let b = plane.dot(polygon[next] - origin);
+            let crossing = point.lerp(polygon[next], a / (a - b));
+            front.push(crossing);
+            back.push(crossing);
+        }
+    }
+
+    if front.len() < 3 || back.len() < 3 {
+        return vec![polygon];
+    }
+    vec![front, back]
+}
+
+/// Uses the generalized winding number which is robust for slightly open Meshes.
+fn is_inside(point: Vec3, triangles: &[Triangle]) -> bool {
+    let solid_angle = triangles
+        .iter()
+        .map(|triangle| {
+            let [a, b, c] = triangle.map(|corner| corner - point);
+            let (la, lb, lc) = (a.length(), b.length(), c.length());
+            let numerator = a.dot(b.cross(c));
+            let denominator = la * lb * lc + a.dot(b) * lc + b.dot(c) * la + c.dot(a) * lb;
+            2.0 * numerator.atan2(denominator)
+        })
+        .sum::<f32>();
+    solid_angle / (4.0 * PI) > 0.5
+}
+
+fn classify(
+    piece: &[Vec3],
+    normal: Vec3,
+    triangles: &[Triangle],
+    coplanar: bool,
+    epsilon: f32,
+) -> Side {
+    let center = piece.iter().sum::<Vec3>() / piece.len() as f32;
+    if !coplanar {
+        return if is_inside(center, triangles) {
+            Side::Inside
+        } else {
+            Side::Outside
+        };
+    }
+
+    // Pieces on the surface of the other Mesh are told apart by probing both of their sides.
+    let offset = normal * epsilon * 100.0;
+    match (
+        is_inside(center + offset, triangles),
+        is_inside(center - offset, triangles),
+    ) {
+        (false, false) => Side::Outside,
+        (true, true) => Side::Inside,
+        (false, true) => Side::Coplanar,
+        (true, false) => Side::Opposite,
+    }
+}
+
+/// Merges points closer than the tolerance so pieces of both Meshes share their points.
+struct Welder {
+    positions: Vec<Vec3>,
+    cells: HashMap<IVec3, Vec<usize>>,
+    epsilon: f32,
+}
+
+impl Welder {
+    fn new(epsilon: f32) -> Self {
+        Self {
+            positions: Vec::new(),
+            cells: HashMap::new(),
+            epsilon,
+        }
+    }
+
+    fn cell(&self, position: Vec3) -> IVec3 {
+        (position / (self.epsilon * 4.0)).floor().as_ivec3()
+    }
+
+    fn insert(&mut self, position: Vec3) -> usize {
+        let cell = self.cell(position);
+        for x in -1..=1 {
+            for y in -1..=1 {
+                for z in -1..=1 {
+                    let Some(indices) = self.cells.get(&(cell + IVec3::new(x, y, z))) else {
+                        continue;
+                    };
+                    if let Some(index) = indices
+                        .iter()
+                        .find(|index| self.positions[**index].distance(position) <= self.epsilon)
+                    {
+                        return *index;
+                    }
+                }
+            }
+        }
+
+        let index = self.positions.len();
+        self.positions.push(position);
+        self.cells.entry(cell).or_default().push(index);
+        index
+    }
+}
+
+/// The face a piece was cut from and whether it belongs to the first Mesh.
+type Source = (bool, FaceHandle);
+
+struct Boolean {
+    points: Welder,
+    polygons: Vec<(Vec<usize>, Source)>,
+    epsilon: f32,
+}
+
+impl Boolean {
+    fn add_polygon(&mut self, piece: Vec<Vec3>, flip: bool, source: Source) {
+        let mut polygon = piece
+            .into_iter()
+            .map(|point| self.points.insert(point))
+            .collect::<Vec<_>>();
+        polygon.dedup();
+        while polygon.len() > 1 && polygon.first() == polygon.last() {
+            polygon.pop();
+        }
+        if polygon.len() < 3 {
+            return;
+        }
+        if flip {
+            polygon.reverse();
+        }
+        self.polygons.push((polygon, source));
+    }
+
+    /// Inserts every used point lying on an Edge of a polygon into it,
+    /// so neighbouring pieces which were cut differently share their Edges.
+    fn resolve_t_junctions(&mut self) {
+        let mut used = self
+            .polygons
+            .iter()
+            .flat_map(|(polygon, _)| polygon.iter().copied())
+            .collect::<Vec<_>>();
+        used.sort_by(|a, b| {
+            self.points.positions[*a]
+                .x
+                .total_cmp(&self.points.positions[*b].x)
+        });
+        used.dedup();
+        let used_x = used
+            .iter()
+            .map(|index| self.points.positions[*index].x)
+            .collect::<Vec<_>>();
+
+        let positions = &self.points.positions;
+        let epsilon = self.epsilon;
+        for (polygon, _) in &mut self.polygons {
+            let mut resolved = Vec::with_capacity(polygon.len());
+            for (index, point) in polygon.iter().enumerate() {
+                let next = polygon[(index + 1) % polygon.len()];
+                resolved.push(*point);
+
+                let (start, end) = (positions[*point], positions[next]);
+                let direction = end - start;
+                let length_squared = direction.length_squared();
+                let from = used_x.partition_point(|x| *x < start.x.min(end.x) - epsilon);
+                let to = used_x.partition_point(|x| *x <= start.x.max(end.x) + epsilon);
+                let mut between = used[from..to]
+                    .iter()
+                    .filter(|other| **other != *point && **other != next)
+                    .filter_map(|other| {
+                        let position = positions[*other];
+                        let t = direction.dot(position - start) / length_squared;
+                        let on_edge = t > 0.0
+                            && t < 1.0
+                            && (start + direction * t).distance(position) <= epsilon;
+                        on_edge.then_some((t, *other))
+                    })
+                    .collect::<Vec<_>>();
+                between.sort_by(|a, b| a.0.total_cmp(&b.0));
+                resolved.extend(between.into_iter().map(|(_, other)| other));
+            }
+            *polygon = resolved;
+        }
+    }
+
+    fn build(mut self, first: &Mesh, second: &Mesh) -> Mesh {
+        self.resolve_t_junctions();
+
+        let mut triangles = Vec::new();
+        for (polygon, source) in std::mem::take(&mut self.polygons) {
+            for triangle in self.triangulate(&polygon) {
+                triangles.push((triangle, source));
+            }
+        }
+
+        let mut mesh = Mesh {
+            material: first.material.or(second.material),
+            normal_weighting: first.normal_weighting,
+            ..Default::default()
+        };
+        let mut handles = HashMap::new();
+        for (triangle, (is_first, source_face)) in triangles {
+            if triangle[0] == triangle[1]
+                || triangle[1] == triangle[2]
+                || triangle[0] == triangle[2]
+            {
+                continue;
+            }
+            let source = if is_first { first } else { second };
+            let points = triangle.map(|index| {
+                *handles.entry(index).or_insert_with(|| {
+                    let position = self.points.positions[index];
+                    let point = mesh.add_point(position);
+                    if !source.data_attributes.is_empty() {
+                        mesh.sample_attributes(
+                            source,
+                            source_face,
+                            position,
+                            Element::Point(point),
+                        );
+                    }
+                    point
+                })
+            });
+            let face = mesh.add_tri(points);
+            mesh.set_face_material(face, source.get_face_material_ref(source_face));
+            if source.data_attributes.is_empty() {
+                continue;
+            }
+            mesh.copy_attributes(source, Element::Face(source_face), Element::Face(face));
+            for (corner, point) in points.into_iter().enumerate() {
+                let position = mesh.position(point);
+                mesh.sample_attributes(
+                    source,
+                    source_face,
+                    position,
+                    Element::Corner(face, corner),
+                );
+            }
+        }
+        mesh
+    }
+
+    /// Triangulates a convex polygon whose Edges may contain collinear points.
+    /// Fans from a corner whose neighbours are corners as well so no triangle is degenerate,
+    /// otherwise a new point in the center is used.
+    fn triangulate(&mut self, polygon: &[usize]) -> Vec<[usize; 3]> {
+        let len = polygon.len();
+        if len == 3 {
+            return vec![[polygon[0], polygon[1], polygon[2]]];
+        }
+
+        let position = |index: usize| self.points.positions[polygon[index % len]];
+        let is_corner = |index: usize| {
+            let (previous, point, next) = (
+                position(index + len - 1),
+                position(index),
+                position(index + 1),
+            );
+            let base = next - previous;
+            (point - previous).cross(base).length() > self.epsilon * base.length()
+        };
+        let corners = (0..len).map(is_corner).collect::<Vec<_>>();
+        let apex = (0..len).find(|index| {
+            corners[(index + len - 1) % len] && corners[*index] && corners[(index + 1) % len]
+        });
+
+        match apex {
+            Some(apex) => (1..len - 1)
+                .map(|offset| {
+                    [
+                        polygon[apex],
+                        polygon[(apex + offset) % len],
+                        polygon[(apex + offset + 1) % len],
+                    ]
+                })
+                .collect(),
+            None => {
+                let center = self.points.positions.len();
+                let center_position = polygon
+                    .iter()
+                    .map(|index| self.points.positions[*index])
+                    .sum::<Vec3>()
+                    / len as f32;
+                self.points.positions.push(center_position);
+                (0..len)
+                    .map(|index| [center, polygon[index], polygon[(index + 1) % len]])
+                    .collect()
+            }
+        }
+    }
+}
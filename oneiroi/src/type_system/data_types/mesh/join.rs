@@ -0,0 +1,48 @@
+use super::{Edge, FaceHandle, Mesh, PointHandle};
+use crate::type_system::trait_types::MeshMut2D;
+
+impl Mesh {
+    /// Combines the Meshes into one without connecting them.
+    /// The first material found becomes the material of the whole Mesh,
+    /// faces using another one keep it as their own.
+    /// The normals are weighted like the ones of the first Mesh.
+    pub(crate) fn join<'a>(meshes: impl IntoIterator<Item = &'a Mesh>) -> Mesh {
+        let meshes = meshes.into_iter().collect::<Vec<_>>();
+        let mut joined = Mesh {
+            material: meshes.iter().find_map(|mesh| mesh.material),
+            normal_weighting: meshes
+                .first()
+                .map(|mesh| mesh.normal_weighting)
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+        for mesh in meshes {
+            joined.append(mesh);
+        }
+        joined
+    }
+
+    /// Appends all points and faces of the other Mesh, offsetting their handles.
+    pub(crate) fn append(&mut self, other: &Mesh) {
+        let offset = self.data_points_position.len();
+        let moved = |point: PointHandle| PointHandle::new(offset + point.idx() + 1);
+
+        self.add_points(other.data_points_position.iter().copied());
+        for (index, points) in other.points_of_face.iter().enumerate() {
+            let face = self.add_tri(points.map(moved));
+            self.set_face_material(
+                face,
+                other.get_face_material_ref(FaceHandle::new(index + 1)),
+            );
+        }
+        for edge in other.data_hard_edge.iter() {
+            self.set_edge_hard(Edge::new((moved(edge.0), moved(edge.1))));
+        }
+        let face_offset = self.points_of_face.len() - other.points_of_face.len();
+        self.copy_all_attributes(
+            other,
+            |point| Some(moved(point)),
+            |face| Some(FaceHandle::new(face_offset + face.idx() + 1)),
+        );
+    }
+}
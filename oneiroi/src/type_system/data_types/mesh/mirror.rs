@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use glam::{Mat3A, Vec3, Vec3A};
+
+use super::{Edge, Mesh};
+use crate::type_system::data_types::Transform;
+use crate::type_system::trait_types::{MeshMut0D, MeshMut2D, Transformable};
+
+/// How far a point may be from the mirror plane to be welded.
+const WELD_DISTANCE: f32 = 1e-5;
+
+impl Mesh {
+    /// Adds a mirrored copy of the Mesh on the other side of the plane.
+    /// The plane is given by its normal and its distance from the origin along it.
+    ///
+    /// When welding, points on the plane are shared by both halves
+    /// and faces lying in the plane are not duplicated.
+    pub(crate) fn mirror(&self, normal: Vec3, offset: f32, weld: bool) -> Mesh {
+        let normal = normal.normalize_or(Vec3::X);
+        let reflection = Transform {
+            matrix3: Mat3A::IDENTITY
+                - Mat3A::from_cols(
+                    Vec3A::from(normal * 2.0 * normal.x),
+                    Vec3A::from(normal * 2.0 * normal.y),
+                    Vec3A::from(normal * 2.0 * normal.z),
+                ),
+            translation: Vec3A::from(normal * 2.0 * offset),
+        };
+        // Transforming with a negative determinant flips the winding order as well.
+        let mut mirrored = self.clone();
+        mirrored.transform(&reflection);
+
+        let mut result = self.clone();
+        // The handle of each mirrored point and whether it got welded to the original one.
+        let mut points = HashMap::new();
+        for point in mirrored.point_handles() {
+            let on_plane = (normal.dot(self.position(point)) - offset).abs() <= WELD_DISTANCE;
+            let handle = if weld && on_plane {
+                (point, true)
+            } else {
+                (result.add_point(mirrored.position(point)), false)
+            };
+            points.insert(point, handle);
+        }
+
+        let mut faces = HashMap::new();
+        for face in mirrored.face_handles() {
+            let face_points = mirrored.points_in_face(face).map(|point| points[&point]);
+            // The face lies in the plane on top of the original one.
+            if face_points.iter().all(|(_, welded)| *welded) {
+                continue;
+            }
+            let new_points = face_points.map(|(point, _)| point);
+            let new_face = result.add_tri(new_points);
+            result.set_face_material(new_face, mirrored.get_face_material_ref(face));
+            faces.insert(face, new_face);
+        }
+
+        for edge in mirrored.data_hard_edge.iter() {
+            result.set_edge_hard(Edge::new((points[&edge.0].0, points[&edge.1].0)));
+        }
+        result.copy_all_attributes(
+            &mirrored,
+            |point| Some(points[&point].0),
+            |face| faces.get(&face).copied(),
+        );
+
+        result
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use glam::{IVec3, Vec3};
+
+use super::{FaceHandle, Mesh};
+use crate::random::Random;
+use crate::type_system::trait_types::MeshMut0D;
+
+/// How many candidates are tried per requested point before the minimum distance gives up.
+const MAX_ATTEMPTS_PER_POINT: usize = 30;
+
+/// A point on the surface of a Mesh.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct SurfaceSample {
+    pub(crate) position: Vec3,
+    pub(crate) normal: Vec3,
+    pub(crate) face: FaceHandle,
+}
+
+impl Mesh {
+    /// Scatters points over the faces weighted by their area.
+    /// The same seed always produces the same points for the same Mesh.
+    ///
+    /// With a minimum distance candidates too close to an accepted point are rejected,
+    /// which can result in less points than requested on small surfaces.
+    pub(crate) fn sample_surface(
+        &self,
+        amount: usize,
+        seed: u64,
+        min_distance: f32,
+    ) -> Vec<SurfaceSample> {
+        // The cumulative area is used to pick a face with a single binary search.
+        let mut cumulative_area = Vec::with_capacity(self.points_of_face.len());
+        let mut total_area = 0.0;
+        for face in self.face_handles() {
+            total_area += self.calc_face_normal(face).length() * 0.5;
+            cumulative_area.push(total_area);
+        }
+        if amount == 0 || total_area <= 0.0 {
+            return Vec::new();
+        }
+
+        let mut random = Random::new(seed);
+        let mut samples = Vec::with_capacity(amount);
+        let mut grid = (min_distance > 0.0).then(|| SpatialGrid::new(min_distance));
+
+        let attempts = if grid.is_some() {
+            amount * MAX_ATTEMPTS_PER_POINT
+        } else {
+            amount
+        };
+        for _ in 0..attempts {
+            if samples.len() == amount {
+                break;
+            }
+            let target = random.next_f32() * total_area;
+            let index = cumulative_area
+                .partition_point(|area| *area <= target)
+                .min(cumulative_area.len() - 1);
+            let face = FaceHandle::new(index + 1);
+
+            // Uniform barycentric coordinates, mirrored back into the triangle.
+            let (mut u, mut v) = (random.next_f32(), random.next_f32());
+            if u + v > 1.0 {
+                (u, v) = (1.0 - u, 1.0 - v);
+            }
+            let [a, b, c] = self.points_in_face(face).map(|point| self.position(point));
+            let position = a + (b - a) * u + (c - a) * v;
+
+            if let Some(grid) = &mut grid {
+                if grid.has_neighbour(position) {
+                    continue;
+                }
+                grid.insert(position);
+            }
+            samples.push(SurfaceSample {
+                position,
+                normal: self.face_normal(face),
+                face,
+            });
+        }
+
+        samples
+    }
+}
+
+/// Buckets points into cells as large as the minimum distance,
+/// so only the surrounding cells have to be checked for neighbours.
+struct SpatialGrid {
+    min_distance: f32,
+    cells: HashMap<IVec3, Vec<Vec3>>,
+}
+
+impl SpatialGrid {
+    fn new(min_distance: f32) -> Self {
+        Self {
+            min_distance,
+            cells: HashMap::new(),
+        }
+    }
+
+    fn cell(&self, position: Vec3) -> IVec3 {
+        (position / self.min_distance).floor().as_ivec3()
+    }
+
+    fn has_neighbour(&self, position: Vec3) -> bool {
+        let cell = self.cell(position);
+        (-1..=1).any(|x| {
+            (-1..=1).any(|y| {
+                (-1..=1).any(|z| {
+                    self.cells
+                        .get(&(cell + IVec3::new(x, y, z)))
+                        .is_some_and(|points| {
+                            points
+                                .iter()
+                                .any(|point| point.distance(position) < self.min_distance)
+                        })
+                })
+            })
+        })
+    }
+
+    fn insert(&mut self, position: Vec3) {
+        self.cells
+            .entry(self.cell(position))
+            .or_default()
+            .push(position);
+    }
+}
@@ -0,0 +1,154 @@
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+use super::{FaceHandle, Mesh, PointHandle};
+use crate::type_system::trait_types::MeshMut0D;
+
+/// How much each face contributes to the normals of its points.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum NormalWeighting {
+    /// Every face counts the same.
+    Uniform,
+    /// Larger faces count more.
+    #[default]
+    Area,
+    /// Faces count by the angle of their corner at the point,
+    /// so splitting a face into more triangles doesn't change the normal.
+    Angle,
+}
+
+impl NormalWeighting {
+    /// The weighting as stored in the integer property of the SetShadingV1 node.
+    /// Returns None for values which don't name a weighting.
+    pub(crate) fn from_index(index: i64) -> Option<Self> {
+        match index {
+            0 => Some(NormalWeighting::Uniform),
+            1 => Some(NormalWeighting::Area),
+            2 => Some(NormalWeighting::Angle),
+            _ => None,
+        }
+    }
+}
+
+impl Mesh {
+    pub(crate) fn set_normal_weighting(&mut self, weighting: NormalWeighting) {
+        self.normal_weighting = weighting;
+    }
+
+    /// Marks every Edge hard whose two faces meet at an angle larger than the threshold in radians.
+    /// Edges which are already hard stay hard.
+    pub(crate) fn auto_smooth(&mut self, angle: f32) {
+        let sharp = self
+            .edge_handles()
+            .into_iter()
+            .filter(|edge| {
+                let mut normals = self.faces_in_edge(*edge).map(|face| self.face_normal(face));
+                match (normals.next(), normals.next()) {
+                    (Some(first), Some(second)) => first.angle_between(second) > angle,
+                    _ => false,
+                }
+            })
+            .collect::<Vec<_>>();
+        for edge in sharp {
+            self.set_edge_hard(edge);
+        }
+    }
+
+    /// The normal of the face scaled by how much it contributes to the normal of the point.
+    pub(super) fn weighted_face_normal(&self, face: FaceHandle, point: PointHandle) -> Vec3 {
+        match self.normal_weighting {
+            NormalWeighting::Uniform => self.face_normal(face),
+            NormalWeighting::Area => self.calc_face_normal(face),
+            NormalWeighting::Angle => {
+                let points = self.points_in_face(face);
+                let corner = points.iter().position(|other| *other == point).unwrap();
+                let position = self.position(point);
+                let next = self.position(points[(corner + 1) % 3]) - position;
+                let previous = self.position(points[(corner + 2) % 3]) - position;
+                let angle = next.angle_between(previous);
+                if angle.is_finite() {
+                    self.face_normal(face) * angle
+                } else {
+                    Vec3::ZERO
+                }
+            }
+        }
+    }
+
+    /// For every corner of every face the smoothing group it belongs to around its point.
+    /// Faces around a point share a group as long as they are connected without crossing a hard Edge.
+    /// Returns None if no hard Edge separates two faces so all corners of a point are smooth.
+    pub(super) fn smoothing_groups(&self) -> Option<Vec<u32>> {
+        if !self
+            .data_hard_edge
+            .iter()
+            .any(|edge| self.has_normal_contention(*edge))
+        {
+            return None;
+        }
+
+        let mut groups = vec![0; self.points_of_face.len() * 3];
+        for point in self.point_handles() {
+            let faces = self.faces_in_point(point);
+            // Every face starts in its own group which is merged with its smooth neighbours.
+            let mut labels = (0..faces.len()).collect::<Vec<_>>();
+            let mut changed = true;
+            while changed {
+                changed = false;
+                for (index, face) in faces.iter().enumerate() {
+                    let edges = self.edges_in_face(*face);
+                    let smooth = edges.into_iter().filter(|edge| {
+                        (edge.0 == point || edge.1 == point) && !self.has_normal_contention(*edge)
+                    });
+                    for edge in smooth {
+                        for other in self.faces_in_edge(edge) {
+                            let Some(other) = faces.iter().position(|face| *face == other) else {
+                                continue;
+                            };
+                            let label = labels[index].min(labels[other]);
+                            if labels[index] != label || labels[other] != label {
+                                labels[index] = label;
+                                labels[other] = label;
+                                changed = true;
+                            }
+                        }
+                    }
+                }
+            }
+
+            for (index, face) in faces.iter().enumerate() {
+                let corner = self
+                    .points_in_face(*face)
+                    .iter()
+                    .position(|other| *other == point)
+                    .unwrap();
+                groups[face.idx() * 3 + corner] = faces[labels[index]].idx() as u32;
+            }
+        }
+        Some(groups)
+    }
+
+    /// Calculates a normal for every corner of every face which is only smoothed
+    /// with the faces of the same smoothing group.
+    pub(super) fn calculate_corner_normals(&self, groups: &[u32]) -> Vec<Vec3> {
+        let mut normals = Vec::with_capacity(groups.len());
+        for face in self.face_handles() {
+            for (corner, point) in self.points_in_face(face).into_iter().enumerate() {
+                let group = groups[face.idx() * 3 + corner];
+                let mut normal = Vec3::ZERO;
+                for other in self.faces_in_point(point) {
+                    let other_corner = self
+                        .points_in_face(*other)
+                        .iter()
+                        .position(|other_point| *other_point == point)
+                        .unwrap();
+                    if groups[other.idx() * 3 + other_corner] == group {
+                        normal += self.weighted_face_normal(*other, point);
+                    }
+                }
+                normals.push(normal.normalize_or_zero());
+            }
+        }
+        normals
+    }
+}
@@ -0,0 +1,231 @@
+use std::f32::consts::{PI, TAU};
+
+use glam::Vec3;
+
+use super::{Mesh, PointHandle};
+use crate::type_system::trait_types::{MeshMut0D, MeshMut2D};
+
+impl Mesh {
+    /// Adds two triangles for the quad, the points have to be counter clockwise.
+    fn add_quad(&mut self, [a, b, c, d]: [PointHandle; 4]) {
+        self.add_tri([a, b, c]);
+        self.add_tri([a, c, d]);
+    }
+
+    /// A ring of points around the Y axis, counter clockwise when looked at from above.
+    fn ring(radius: f32, height: f32, segments: usize) -> impl Iterator<Item = Vec3> {
+        (0..segments).map(move |segment| {
+            let angle = TAU * segment as f32 / segments as f32;
+            Vec3::new(radius * angle.cos(), height, -radius * angle.sin())
+        })
+    }
+
+    /// Closes a ring of points with a fan, facing up or down.
+    fn add_cap(&mut self, ring: &[PointHandle], up: bool) {
+        for window in ring[1..].windows(2) {
+            if up {
+                self.add_tri([ring[0], window[0], window[1]]);
+            } else {
+                self.add_tri([ring[0], window[1], window[0]]);
+            }
+        }
+    }
+
+    /// A sphere centered on the origin made of `rings` rows of quads between the poles.
+    pub(crate) fn uv_sphere(radius: f32, segments: usize, rings: usize) -> Mesh {
+        let segments = segments.max(3);
+        let rings = rings.max(2);
+        let mut mesh = Mesh::default();
+
+        let top = mesh.add_point(Vec3::Y * radius);
+        let circles = (1..rings)
+            .map(|ring| {
+                let angle = PI * ring as f32 / rings as f32;
+                let positions = Self::ring(radius * angle.sin(), radius * angle.cos(), segments);
+                mesh.add_points(positions)
+            })
+            .collect::<Vec<_>>();
+        let bottom = mesh.add_point(Vec3::NEG_Y * radius);
+
+        for segment in 0..segments {
+            let next = (segment + 1) % segments;
+            mesh.add_tri([top, circles[0][segment], circles[0][next]]);
+            for pair in circles.windows(2) {
+                let [upper, lower] = [&pair[0], &pair[1]];
+                mesh.add_quad([upper[segment], lower[segment], lower[next], upper[next]]);
+            }
+            let last = &circles[circles.len() - 1];
+            mesh.add_tri([bottom, last[next], last[segment]]);
+        }
+        mesh
+    }
+
+    /// A sphere centered on the origin made of evenly sized triangles
+    /// by subdividing an icosahedron.
+    pub(crate) fn icosphere(radius: f32, subdivisions: usize) -> Mesh {
+        let golden = (1.0 + 5.0_f32.sqrt()) * 0.5;
+        let mut mesh = Mesh::default();
+        let points = mesh.add_points([
+            Vec3::new(-1.0, golden, 0.0),
+            Vec3::new(1.0, golden, 0.0),
+            Vec3::new(-1.0, -golden, 0.0),
+            Vec3::new(1.0, -golden, 0.0),
+            Vec3::new(0.0, -1.0, golden),
+            Vec3::new(0.0, 1.0, golden),
+            Vec3::new(0.0, -1.0, -golden),
+            Vec3::new(0.0, 1.0, -golden),
+            Vec3::new(golden, 0.0, -1.0),
+            Vec3::new(golden, 0.0, 1.0),
+            Vec3::new(-golden, 0.0, -1.0),
+            Vec3::new(-golden, 0.0, 1.0),
+        ]);
+        for [a, b, c] in [
+            [0, 11, 5],
+            [0, 5, 1],
+            [0, 1, 7],
+            [0, 7, 10],
+            [0, 10, 11],
+            [1, 5, 9],
+            [5, 11, 4],
+            [11, 10, 2],
+            [10, 7, 6],
+            [7, 1, 8],
+            [3, 9, 4],
+            [3, 4, 2],
+            [3, 2, 6],
+            [3, 6, 8],
+            [3, 8, 9],
+            [4, 9, 5],
+            [2, 4, 11],
+            [6, 2, 10],
+            [8, 6, 7],
+            [9, 8, 1],
+        ] {
+            mesh.add_tri([points[a], points[b], points[c]]);
+        }
+
+        for _ in 0..subdivisions {
+            mesh = mesh.subdivide(false);
+        }
+        for position in mesh.data_points_position.iter_mut() {
+            *position = position.normalize() * radius;
+        }
+        mesh
+    }
+
+    /// A ring around the Y axis with a circular cross section.
+    pub(crate) fn torus(
+        radius: f32,
+        tube_radius: f32,
+        segments: usize,
+        tube_segments: usize,
+    ) -> Mesh {
+        let segments = segments.max(3);
+        let tube_segments = tube_segments.max(3);
+        let mut mesh = Mesh::default();
+
+        // One circle of the cross section for every segment, starting on the outside.
+        let circles = (0..segments)
+            .map(|segment| {
+                let angle = TAU * segment as f32 / segments as f32;
+                let outwards = Vec3::new(angle.cos(), 0.0, -angle.sin());
+                let positions = (0..tube_segments).map(|tube_segment| {
+                    let angle = TAU * tube_segment as f32 / tube_segments as f32;
+                    outwards * (radius + tube_radius * angle.cos())
+                        + Vec3::Y * tube_radius * angle.sin()
+                });
+                mesh.add_points(positions)
+            })
+            .collect::<Vec<_>>();
+
+        for segment in 0..segments {
+            let [current, next] = [&circles[segment], &circles[(segment + 1) % segments]];
+            for tube_segment in 0..tube_segments {
+                let tube_next = (tube_segment + 1) % tube_segments;
+                mesh.add_quad([
+                    current[tube_segment],
+                    next[tube_segment],
+                    next[tube_next],
+                    current[tube_next],
+                ]);
+            }
+        }
+        mesh
+    }
+
+    /// A frustum standing on the origin, a radius of zero closes that side in a single point.
+    pub(crate) fn cone(
+        bottom_radius: f32,
+        top_radius: f32,
+        height: f32,
+        segments: usize,
+        has_caps: bool,
+    ) -> Mesh {
+        let segments = segments.max(3);
+        let mut mesh = Mesh::default();
+
+        let circle = |mesh: &mut Mesh, radius: f32, height: f32| {
+            if radius <= 0.0 {
+                vec![mesh.add_point(Vec3::Y * height); segments]
+            } else {
+                mesh.add_points(Self::ring(radius, height, segments))
+                    .into_vec()
+            }
+        };
+        let bottom = circle(&mut mesh, bottom_radius, 0.0);
+        let top = circle(&mut mesh, top_radius, height);
+
+        for segment in 0..segments {
+            let next = (segment + 1) % segments;
+            if top_radius <= 0.0 {
+                mesh.add_tri([top[segment], bottom[segment], bottom[next]]);
+            } else if bottom_radius <= 0.0 {
+                mesh.add_tri([top[segment], bottom[segment], top[next]]);
+            } else {
+                mesh.add_quad([top[segment], bottom[segment], bottom[next], top[next]]);
+            }
+        }
+
+        if has_caps {
+            if bottom_radius > 0.0 {
+                mesh.add_cap(&bottom, false);
+            }
+            if top_radius > 0.0 {
+                mesh.add_cap(&top, true);
+            }
+        }
+        mesh
+    }
+
+    /// A flat rectangle on the XZ plane centered on the origin and facing up,
+    /// split into quads along both axes.
+    pub(crate) fn grid(width: f32, depth: f32, columns: usize, rows: usize) -> Mesh {
+        let columns = columns.max(1);
+        let rows = rows.max(1);
+        let mut mesh = Mesh::default();
+
+        let positions = (0..=rows).flat_map(|row| {
+            (0..=columns).map(move |column| {
+                Vec3::new(
+                    width * (column as f32 / columns as f32 - 0.5),
+                    0.0,
+                    depth * (row as f32 / rows as f32 - 0.5),
+                )
+            })
+        });
+        let points = mesh.add_points(positions);
+        let point = |column: usize, row: usize| points[row * (columns + 1) + column];
+
+        for row in 0..rows {
+            for column in 0..columns {
+                mesh.add_quad([
+                    point(column, row),
+                    point(column, row + 1),
+                    point(column + 1, row + 1),
+                    point(column + 1, row),
+                ]);
+            }
+        }
+        mesh
+    }
+}
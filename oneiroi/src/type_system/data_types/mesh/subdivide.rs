@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::f32::consts::TAU;
+
+use glam::Vec3;
+
+use super::{Edge, Element, Mesh, PointHandle};
+use crate::type_system::trait_types::{MeshMut0D, MeshMut2D};
+
+impl Mesh {
+    /// Splits every face into four by adding a point on each Edge.
+    /// Smooth subdivision moves the points according to the Loop scheme,
+    /// hard and boundary Edges are kept as creases which only smooth along themselves.
+    /// Attributes of the new points and corners are interpolated from the Edge they split.
+    pub(crate) fn subdivide(&self, smooth: bool) -> Mesh {
+        let mut subdivided = Mesh {
+            material: self.material,
+            normal_weighting: self.normal_weighting,
+            ..Default::default()
+        };
+
+        let positions = self
+            .point_handles()
+            .map(|point| {
+                if smooth {
+                    self.smoothed_position(point)
+                } else {
+                    self.position(point)
+                }
+            })
+            .collect::<Vec<_>>();
+        subdivided.add_points(positions);
+
+        // Sorted so the new points are created in a deterministic order.
+        let edges = self.edge_handles();
+        let edge_positions = edges.iter().map(|edge| {
+            let [a, b] = edge.points().map(|point| self.position(point));
+            if !smooth || self.is_crease(*edge) {
+                return (a + b) * 0.5;
+            }
+            let opposite = self
+                .faces_in_edge(*edge)
+                .map(|face| {
+                    let points = self.points_in_face(face);
+                    let point = points
+                        .into_iter()
+                        .find(|point| *point != edge.0 && *point != edge.1)
+                        .unwrap();
+                    self.position(point)
+                })
+                .sum::<Vec3>();
+            (a + b) * 0.375 + opposite * 0.125
+        });
+        let edge_points = edges
+            .iter()
+            .copied()
+            .zip(subdivided.add_points(edge_positions))
+            .collect::<HashMap<Edge, PointHandle>>();
+
+        // The corners of the new faces as the corners of the original face they lie between.
+        const CORNERS: [[[usize; 2]; 3]; 4] = [
+            [[0, 0], [0, 1], [2, 0]],
+            [[1, 1], [1, 2], [0, 1]],
+            [[2, 2], [2, 0], [1, 2]],
+            [[0, 1], [1, 2], [2, 0]],
+        ];
+        for face in self.face_handles() {
+            let [a, b, c] = self.points_in_face(face);
+            let ab = edge_points[&Edge::new((a, b))];
+            let bc = edge_points[&Edge::new((b, c))];
+            let ca = edge_points[&Edge::new((c, a))];
+            let material = self.get_face_material_ref(face);
+            let faces = [[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]];
+            for (points, corners) in faces.into_iter().zip(CORNERS) {
+                let new_face = subdivided.add_tri(points);
+                subdivided.set_face_material(new_face, material);
+                if self.data_attributes.is_empty() {
+                    continue;
+                }
+                subdivided.copy_attributes(self, Element::Face(face), Element::Face(new_face));
+                for (corner, [first, second]) in corners.into_iter().enumerate() {
+                    subdivided.interpolate_attributes(
+                        self,
+                        &[
+                            (Element::Corner(face, first), 0.5),
+                            (Element::Corner(face, second), 0.5),
+                        ],
+                        Element::Corner(new_face, corner),
+                    );
+                }
+            }
+        }
+
+        if !self.data_attributes.is_empty() {
+            for point in self.point_handles() {
+                subdivided.copy_attributes(self, Element::Point(point), Element::Point(point));
+            }
+            for (edge, middle) in &edge_points {
+                subdivided.interpolate_attributes(
+                    self,
+                    &[(Element::Point(edge.0), 0.5), (Element::Point(edge.1), 0.5)],
+                    Element::Point(*middle),
+                );
+            }
+        }
+
+        for edge in self.data_hard_edge.iter() {
+            let Some(middle) = edge_points.get(edge) else {
+                continue;
+            };
+            subdivided.set_edge_hard(Edge::new((edge.0, *middle)));
+            subdivided.set_edge_hard(Edge::new((*middle, edge.1)));
+        }
+
+        subdivided
+    }
+
+    fn is_crease(&self, edge: Edge) -> bool {
+        self.data_hard_edge.contains(&edge) || self.faces_in_edge(edge).count() < 2
+    }
+
+    /// The new position of an existing point, which only depends on its neighbours.
+    fn smoothed_position(&self, point: PointHandle) -> Vec3 {
+        let mut edges = self.edges_in_point(point).collect::<Vec<_>>();
+        edges.sort();
+        edges.dedup();
+
+        let position = self.position(point);
+        let creases = edges
+            .iter()
+            .filter(|edge| self.is_crease(**edge))
+            .map(|edge| self.position(edge.not_point(point)))
+            .collect::<Vec<_>>();
+        match creases[..] {
+            [] if !edges.is_empty() => {
+                // The original weights by Loop.
+                let valence = edges.len() as f32;
+                let cos = 0.375 + 0.25 * (TAU / valence).cos();
+                let beta = (0.625 - cos * cos) / valence;
+                let neighbours = edges
+                    .iter()
+                    .map(|edge| self.position(edge.not_point(point)))
+                    .sum::<Vec3>();
+                position * (1.0 - valence * beta) + neighbours * beta
+            }
+            // A point on a crease only moves along it.
+            [a, b] => position * 0.75 + (a + b) * 0.125,
+            // Ends and junctions of creases are corners which stay in place.
+            _ => position,
+        }
+    }
+}
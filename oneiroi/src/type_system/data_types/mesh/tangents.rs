@@ -0,0 +1,65 @@
+use glam::{Vec2, Vec3, Vec4};
+
+use super::Mesh;
+
+impl Mesh {
+    /// Calculates a tangent for every vertex of the buffers following the MikkTSpace conventions.
+    /// The tangents of the faces are weighted by the angle of their corners
+    /// and made orthogonal to the normal of the vertex.
+    /// W is the sign of the bitangent, which is `normal.cross(tangent) * w`.
+    pub(super) fn calculate_tangents(
+        positions: &[Vec3],
+        normals: &[Vec3],
+        uvs: &[Vec2],
+        indices: &[u32],
+    ) -> Vec<Vec4> {
+        let mut tangents = vec![Vec3::ZERO; positions.len()];
+        let mut bitangents = vec![Vec3::ZERO; positions.len()];
+
+        for triangle in indices.chunks_exact(3) {
+            let vertices = [0, 1, 2].map(|corner| triangle[corner] as usize);
+            let [p0, p1, p2] = vertices.map(|vertex| positions[vertex]);
+            let [uv0, uv1, uv2] = vertices.map(|vertex| uvs[vertex]);
+
+            let (edge1, edge2) = (p1 - p0, p2 - p0);
+            let (delta1, delta2) = (uv1 - uv0, uv2 - uv0);
+            let determinant = delta1.perp_dot(delta2);
+            if determinant.abs() <= f32::EPSILON {
+                // Faces without an area in UV space have no direction.
+                continue;
+            }
+            let tangent = ((edge1 * delta2.y - edge2 * delta1.y) / determinant).normalize_or_zero();
+            let bitangent =
+                ((edge2 * delta1.x - edge1 * delta2.x) / determinant).normalize_or_zero();
+
+            for corner in 0..3 {
+                let vertex = vertices[corner];
+                let position = positions[vertex];
+                let next = positions[vertices[(corner + 1) % 3]] - position;
+                let previous = positions[vertices[(corner + 2) % 3]] - position;
+                let angle = next.angle_between(previous);
+                if angle.is_finite() {
+                    tangents[vertex] += tangent * angle;
+                    bitangents[vertex] += bitangent * angle;
+                }
+            }
+        }
+
+        tangents
+            .into_iter()
+            .zip(bitangents)
+            .zip(normals)
+            .map(|((tangent, bitangent), normal)| {
+                let orthogonal = (tangent - *normal * normal.dot(tangent))
+                    .try_normalize()
+                    .unwrap_or_else(|| normal.any_orthonormal_vector());
+                let sign = if normal.cross(orthogonal).dot(bitangent) < 0.0 {
+                    -1.0
+                } else {
+                    1.0
+                };
+                orthogonal.extend(sign)
+            })
+            .collect()
+    }
+}
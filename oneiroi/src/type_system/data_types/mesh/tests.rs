@@ -1,6 +1,20 @@
+use std::collections::HashSet;
+use std::f32::consts::TAU;
+
+use glam::{Vec2, Vec3};
 use insta::assert_yaml_snapshot;
 
+use super::attributes::AttributeError;
+use super::{
+    AttributeBufferValues, AttributeDomain, BooleanMode, Edge, Element, FaceHandle, Mesh,
+    NormalWeighting, PointHandle, TopologyIssue, UvProjection, box_uv,
+};
+use crate::asset::NodeIndex;
 use crate::nodes::Node;
+use crate::type_system::data_types::{Collection, DataTypeKind, Transform};
+use crate::type_system::trait_types::Transformable;
+use crate::type_system::trait_types::{MeshMut0D, MeshMut2D};
+use crate::type_system::{OwnedDataType, Reference, TypeRef};
 
 #[test]
 fn compute_cylinder() {
@@ -18,3 +32,1014 @@ fn bounding_box() {
     test.compute(None, input_sockets, context)
 }
  */
+
+/// The same layout BoxV1 produces with the default size.
+pub(crate) fn unit_box() -> Mesh {
+    let mut surface = Mesh::default();
+    let size = Vec3::splat(0.5);
+    let ftl = surface.add_point(Vec3::new(size.x, size.y, size.z));
+    let ftr = surface.add_point(Vec3::new(size.x, size.y, -size.z));
+    let fbl = surface.add_point(Vec3::new(size.x, -size.y, size.z));
+    let fbr = surface.add_point(Vec3::new(size.x, -size.y, -size.z));
+    let btl = surface.add_point(Vec3::new(-size.x, size.y, size.z));
+    let bbl = surface.add_point(Vec3::new(-size.x, -size.y, size.z));
+    let bbr = surface.add_point(Vec3::new(-size.x, -size.y, -size.z));
+    let btr = surface.add_point(Vec3::new(-size.x, size.y, -size.z));
+
+    surface.add_tri([ftr, ftl, fbr]);
+    surface.add_tri([ftl, fbl, fbr]);
+    surface.add_tri([ftr, fbr, btr]);
+    surface.add_tri([btr, fbr, bbr]);
+    surface.add_tri([btr, bbr, bbl]);
+    surface.add_tri([bbl, btl, btr]);
+    surface.add_tri([btl, bbl, ftl]);
+    surface.add_tri([fbl, ftl, bbl]);
+    surface.add_tri([btr, ftl, ftr]);
+    surface.add_tri([btr, btl, ftl]);
+    surface.add_tri([fbl, bbl, fbr]);
+    surface.add_tri([bbl, bbr, fbr]);
+    surface
+}
+
+/// Every edge is shared by exactly two faces which traverse it in opposite directions
+/// and the surface has no holes.
+fn assert_closed_manifold(mesh: &Mesh) {
+    assert_watertight(mesh);
+
+    let euler = mesh.data_points_position.len() as i64 - mesh.edges.len() as i64
+        + mesh.points_of_face.len() as i64;
+    assert_eq!(euler, 2);
+}
+
+fn assert_watertight(mesh: &Mesh) {
+    let mut directed = HashSet::new();
+    for points in mesh.points_of_face.iter() {
+        for i in 0..3 {
+            let edge = (points[i], points[(i + 1) % 3]);
+            assert!(
+                directed.insert(edge),
+                "{edge:?} is used twice in the same direction"
+            );
+        }
+    }
+    for (a, b) in &directed {
+        assert!(
+            directed.contains(&(*b, *a)),
+            "({a:?}, {b:?}) is a boundary edge"
+        );
+    }
+    assert!(mesh.edges.values().all(|faces| faces[1].is_some()));
+}
+
+fn all_edges(mesh: &Mesh) -> Vec<Edge> {
+    mesh.edges.keys().copied().collect()
+}
+
+#[test]
+fn bevel_all_box_edges() {
+    let mesh = unit_box();
+    let beveled = mesh.bevel_edges(all_edges(&mesh), 0.1, 1);
+    assert_closed_manifold(&beveled);
+
+    // Three points per corner, the six sides and the strips of the twelve sharp edges
+    // as well as a triangle in every corner. The diagonals are flat and stay untouched.
+    assert_eq!(beveled.data_points_position.len(), 24);
+    assert_eq!(beveled.points_of_face.len(), 12 + 24 + 8);
+
+    for position in beveled.data_points_position.iter() {
+        assert!(position.abs().max_element() <= 0.5 + f32::EPSILON);
+        // Each point lies on a side and is the width away from the sharp edges of that side.
+        let on_side = position
+            .abs()
+            .to_array()
+            .iter()
+            .filter(|c| **c > 0.49)
+            .count();
+        assert_eq!(on_side, 1);
+        assert!(position.abs().min_element() > 0.39 && position.abs().min_element() < 0.41);
+    }
+}
+
+#[test]
+fn bevel_single_edge_with_segments() {
+    let mesh = unit_box();
+    // The edge between the front and the top side.
+    let edge = Edge::new((PointHandle::new(1), PointHandle::new(2)));
+    let points = mesh.bevel_edges([edge], 0.2, 1).data_points_position.len();
+    for segments in 1..4 {
+        let beveled = mesh.bevel_edges([edge], 0.2, segments);
+        assert_closed_manifold(&beveled);
+        // Both ends split their neighbouring edges instead of moving the corner of the box.
+        assert!(
+            beveled
+                .data_points_position
+                .iter()
+                .any(|position| *position == Vec3::new(0.5, 0.5, 0.5))
+        );
+        // Every further segment adds a point to the profile at both ends.
+        assert_eq!(
+            beveled.data_points_position.len(),
+            points + 2 * (segments as usize - 1)
+        );
+    }
+}
+
+#[test]
+fn bevel_point_where_two_surfaces_touch() {
+    // Two three-sided pyramids touching with their tips, so six beveled Edges meet there.
+    let mut mesh = Mesh::default();
+    let tip = mesh.add_point(Vec3::ZERO);
+    let [upper, lower] = [1.0, -1.0].map(|y| {
+        [0.0, 1.0, 2.0].map(|i| {
+            let angle = i * TAU / 3.0;
+            mesh.add_point(Vec3::new(angle.cos(), y, -angle.sin()))
+        })
+    });
+    for i in 0..3 {
+        let next = (i + 1) % 3;
+        mesh.add_tri([tip, upper[next], upper[i]]);
+        mesh.add_tri([tip, lower[i], lower[next]]);
+    }
+    mesh.add_tri(upper);
+    mesh.add_tri([lower[0], lower[2], lower[1]]);
+    assert_watertight(&mesh);
+
+    for segments in 1..4 {
+        // The strips around the tip form two loops which are closed by the tip.
+        let beveled = mesh.bevel_edges(all_edges(&mesh), 0.1, segments);
+        assert_watertight(&beveled);
+        assert!(
+            beveled
+                .data_points_position
+                .iter()
+                .any(|position| *position == Vec3::ZERO)
+        );
+    }
+}
+
+#[test]
+fn bevel_ignores_flat_and_boundary_edges() {
+    let mesh = unit_box();
+    // The diagonal of the front side.
+    let diagonal = Edge::new((PointHandle::new(1), PointHandle::new(4)));
+    let beveled = mesh.bevel_edges([diagonal], 0.1, 2);
+    assert_eq!(beveled.points_of_face, mesh.points_of_face);
+
+    let mut open = Mesh::default();
+    let points = open.add_points([Vec3::ZERO, Vec3::X, Vec3::Y]);
+    open.add_tri([points[0], points[1], points[2]]);
+    let beveled = open.bevel_edges(all_edges(&open), 0.1, 1);
+    assert_eq!(beveled.points_of_face, open.points_of_face);
+}
+
+#[test]
+fn sample_surface_is_deterministic() {
+    let mesh = unit_box();
+    let samples = mesh.sample_surface(50, 7, 0.);
+    assert_eq!(samples.len(), 50);
+    assert_eq!(samples, mesh.sample_surface(50, 7, 0.));
+    assert_ne!(samples, mesh.sample_surface(50, 8, 0.));
+
+    for sample in samples {
+        // Every point lies on one of the sides of the box and faces outwards.
+        let position = sample.position;
+        assert!((position.abs().max_element() - 0.5).abs() < 1e-5);
+        assert!(position.abs().max_element() <= 0.5 + 1e-5);
+        assert!(sample.normal.dot(position) > 0.);
+        assert_eq!(sample.normal, mesh.face_normal(sample.face));
+    }
+}
+
+#[test]
+fn sample_surface_respects_min_distance() {
+    let mesh = unit_box();
+    let samples = mesh.sample_surface(1000, 3, 0.25);
+    assert!(!samples.is_empty());
+    assert!(samples.len() < 1000);
+    for (i, a) in samples.iter().enumerate() {
+        for b in &samples[i + 1..] {
+            assert!(a.position.distance(b.position) >= 0.25);
+        }
+    }
+}
+
+#[test]
+fn sample_surface_is_weighted_by_area() {
+    let mut mesh = Mesh::default();
+    let small = mesh.add_points([Vec3::ZERO, Vec3::X, Vec3::Y]);
+    mesh.add_tri([small[0], small[1], small[2]]);
+    let offset = Vec3::Z * 5.;
+    let large = mesh.add_points([offset, offset + Vec3::X * 3., offset + Vec3::Y]);
+    mesh.add_tri([large[0], large[1], large[2]]);
+
+    let samples = mesh.sample_surface(4000, 1, 0.);
+    let on_large = samples
+        .iter()
+        .filter(|sample| sample.position.z > 1.)
+        .count();
+    // The large triangle has three times the area of the small one.
+    let ratio = on_large as f32 / samples.len() as f32;
+    assert!((ratio - 0.75).abs() < 0.03, "{ratio}");
+}
+
+fn moved_box(offset: Vec3, scale: Vec3) -> Mesh {
+    let mut mesh = unit_box();
+    for position in mesh.data_points_position.iter_mut() {
+        *position = *position * scale + offset;
+    }
+    mesh
+}
+
+fn volume(mesh: &Mesh) -> f32 {
+    mesh.points_of_face
+        .iter()
+        .map(|points| {
+            let [a, b, c] = points.map(|point| mesh.position(point));
+            a.dot(b.cross(c)) / 6.0
+        })
+        .sum()
+}
+
+#[test]
+fn boolean_of_overlapping_boxes() {
+    let a = unit_box();
+    let b = moved_box(Vec3::new(0.5, 0.25, 0.3), Vec3::ONE);
+    let overlap = 0.5 * 0.75 * 0.7;
+
+    for (mode, expected) in [
+        (BooleanMode::Union, 2.0 - overlap),
+        (BooleanMode::Difference, 1.0 - overlap),
+        (BooleanMode::Intersection, overlap),
+    ] {
+        let result = a.boolean(&b, mode);
+        assert_closed_manifold(&result);
+        assert!(
+            (volume(&result) - expected).abs() < 1e-4,
+            "{mode:?} has a volume of {}",
+            volume(&result)
+        );
+    }
+}
+
+#[test]
+fn boolean_with_coplanar_faces() {
+    let a = unit_box();
+    let b = moved_box(Vec3::new(0.5, 0.0, 0.0), Vec3::ONE);
+
+    let union = a.boolean(&b, BooleanMode::Union);
+    assert_closed_manifold(&union);
+    assert!((volume(&union) - 1.5).abs() < 1e-4);
+
+    let difference = a.boolean(&b, BooleanMode::Difference);
+    assert_closed_manifold(&difference);
+    assert!((volume(&difference) - 0.5).abs() < 1e-4);
+
+    // Boxes touching with a single side have nothing in common.
+    let touching = moved_box(Vec3::new(1.0, 0.0, 0.0), Vec3::ONE);
+    assert!(
+        a.boolean(&touching, BooleanMode::Intersection)
+            .points_of_face
+            .is_empty()
+    );
+}
+
+#[test]
+fn boolean_carves_openings_into_a_wall() {
+    let wall = moved_box(Vec3::ZERO, Vec3::new(4.0, 3.0, 0.2));
+
+    // The door is flush with the bottom of the wall.
+    let door = moved_box(Vec3::new(-1.0, -0.5, 0.0), Vec3::new(1.0, 2.0, 1.0));
+    let carved = wall.boolean(&door, BooleanMode::Difference);
+    assert_closed_manifold(&carved);
+    assert!((volume(&carved) - (4.0 * 3.0 - 1.0 * 2.0) * 0.2).abs() < 1e-4);
+
+    // The window goes all the way through so the surface has the genus of a torus.
+    let window = moved_box(Vec3::new(1.0, 0.5, 0.0), Vec3::new(1.0, 1.0, 1.0));
+    let carved = carved.boolean(&window, BooleanMode::Difference);
+    assert_watertight(&carved);
+    let euler = carved.data_points_position.len() as i64 - carved.edges.len() as i64
+        + carved.points_of_face.len() as i64;
+    assert_eq!(euler, 0);
+    assert!((volume(&carved) - (4.0 * 3.0 - 2.0 - 1.0) * 0.2).abs() < 1e-4);
+}
+
+#[test]
+fn boolean_keeps_materials_of_both_meshes() {
+    let first = Reference::Standard {
+        node: NodeIndex::new(1),
+        socket: 0,
+    };
+    let second = Reference::Standard {
+        node: NodeIndex::new(2),
+        socket: 0,
+    };
+    let mut a = unit_box();
+    a.set_material(first);
+    let mut b = moved_box(Vec3::splat(0.5), Vec3::ONE);
+    b.set_material(second);
+
+    let union = a.boolean(&b, BooleanMode::Union);
+    assert_eq!(union.get_material_ref(), Some(first));
+    assert_eq!(union.get_material_refs(), vec![first, second]);
+    for face in union.face_handles() {
+        let [x, y, z] = union
+            .points_in_face(face)
+            .map(|point| union.position(point));
+        // Faces of the second box lie beyond the first one in at least one direction.
+        let from_second = (x + y + z).max_element() / 3.0 > 0.5;
+        let expected = if from_second { second } else { first };
+        assert_eq!(union.get_face_material_ref(face), Some(expected));
+    }
+}
+
+#[test]
+fn join_offsets_handles_and_keeps_data() {
+    let material = Reference::Standard {
+        node: NodeIndex::new(1),
+        socket: 0,
+    };
+    let mut first = unit_box();
+    let hard = Edge::new((PointHandle::new(1), PointHandle::new(2)));
+    first.set_edge_hard(hard);
+    let mut second = moved_box(Vec3::X * 2.0, Vec3::ONE);
+    second.set_material(material);
+    second.set_edge_hard(hard);
+
+    let joined = Mesh::join([&first, &second]);
+    assert_eq!(joined.data_points_position.len(), 16);
+    assert_eq!(joined.points_of_face.len(), 24);
+    assert_watertight(&joined);
+    assert!((volume(&joined) - 2.0).abs() < 1e-5);
+
+    let moved_hard = Edge::new((PointHandle::new(9), PointHandle::new(10)));
+    assert!(joined.data_hard_edge.contains(&hard));
+    assert!(joined.data_hard_edge.contains(&moved_hard));
+    assert_eq!(
+        joined.points_in_face(FaceHandle::new(13)),
+        second
+            .points_in_face(FaceHandle::new(1))
+            .map(|point| PointHandle::new(point.idx() + 9))
+    );
+
+    // The first material becomes the one of the whole Mesh even if it is not on the first Mesh.
+    assert_eq!(joined.get_material_ref(), Some(material));
+    assert_eq!(joined.get_face_material_ref(FaceHandle::new(1)), None);
+    assert_eq!(
+        joined.get_face_material_ref(FaceHandle::new(13)),
+        Some(material)
+    );
+}
+
+#[test]
+fn linear_subdivision_keeps_the_shape() {
+    let mesh = unit_box();
+    let subdivided = mesh.subdivide(false);
+    assert_closed_manifold(&subdivided);
+    // A point for every Edge including the diagonals.
+    assert_eq!(subdivided.data_points_position.len(), 8 + 18);
+    assert_eq!(subdivided.points_of_face.len(), 12 * 4);
+    assert!((volume(&subdivided) - 1.0).abs() < 1e-5);
+    for position in subdivided.data_points_position.iter() {
+        assert!((position.abs().max_element() - 0.5).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn smooth_subdivision_rounds_the_shape() {
+    let mesh = unit_box();
+    let subdivided = mesh.subdivide(true).subdivide(true);
+    assert_closed_manifold(&subdivided);
+    assert_eq!(subdivided.points_of_face.len(), 12 * 16);
+    let volume = volume(&subdivided);
+    assert!(volume < 0.9 && volume > 0.3, "{volume}");
+    for position in subdivided.data_points_position.iter() {
+        assert!(position.abs().max_element() < 0.5);
+    }
+}
+
+#[test]
+fn smooth_subdivision_keeps_hard_edges_as_creases() {
+    let mut mesh = unit_box();
+    for edge in all_edges(&mesh) {
+        mesh.set_edge_hard(edge);
+    }
+    // Every point is the end of more than two creases so nothing moves.
+    let smooth = mesh.subdivide(true);
+    let linear = mesh.subdivide(false);
+    assert_eq!(smooth.data_points_position, linear.data_points_position);
+    assert_eq!(smooth.data_hard_edge.len(), 18 * 2);
+
+    // The ring of sharp Edges around the top side, the diagonal has its middle in the center.
+    let mut mesh = unit_box();
+    for edge in all_edges(&mesh) {
+        let [a, b] = edge.points().map(|point| mesh.position(point));
+        let middle = (a + b) * 0.5;
+        if middle.y > 0.49 && middle.x.abs().max(middle.z.abs()) > 0.49 {
+            mesh.set_edge_hard(edge);
+        }
+    }
+    assert_eq!(mesh.data_hard_edge.len(), 4);
+    let smooth = mesh.subdivide(true);
+    assert_closed_manifold(&smooth);
+    // The crease is only smoothed along itself, so it stays flat on the top side.
+    for edge in smooth.data_hard_edge.iter() {
+        for point in edge.points() {
+            let position = smooth.position(point);
+            assert!((position.y - 0.5).abs() < 1e-6);
+            assert!(position.x.abs().max(position.z.abs()) <= 0.5);
+        }
+    }
+}
+
+#[test]
+fn transform_keeps_normals_outwards() {
+    let mut mesh = unit_box();
+    mesh.transform(&Transform::from_scale(Vec3::new(-2.0, 1.0, 1.0)));
+    assert_closed_manifold(&mesh);
+    // Mirroring flips the winding, so the volume stays positive.
+    assert!((volume(&mesh) - 2.0).abs() < 1e-5);
+    for face in mesh.face_handles() {
+        let [a, b, c] = mesh.points_in_face(face).map(|point| mesh.position(point));
+        assert!(mesh.face_normal(face).dot((a + b + c) / 3.0) > 0.0);
+    }
+}
+
+#[test]
+fn transform_collections_of_geometry() {
+    let mut collection = Collection::new(DataTypeKind::Mesh);
+    collection.push(OwnedDataType::new(unit_box()));
+    let mut value = OwnedDataType::new(collection);
+    let translation = Transform::from_translation(Vec3::Y * 3.0);
+    value
+        .as_transformable_mut()
+        .unwrap()
+        .transform(&translation);
+
+    let collection: Collection = value.dispatch().unwrap();
+    for mesh in collection.iterate() {
+        let OwnedDataType::Mesh(mesh) = mesh else {
+            unreachable!()
+        };
+        assert!(
+            mesh.data_points_position
+                .iter()
+                .all(|position| position.y > 2.0)
+        );
+    }
+
+    let mut placement = OwnedDataType::new(Transform::from_scale(Vec3::splat(2.0)));
+    placement
+        .as_transformable_mut()
+        .unwrap()
+        .transform(&translation);
+    let TypeRef::Transform(transform) = placement.to_ref() else {
+        unreachable!()
+    };
+    assert_eq!(
+        transform.transform_point3(Vec3::ONE),
+        Vec3::new(2.0, 5.0, 2.0)
+    );
+    assert!(OwnedDataType::Float(1.0).as_transformable_mut().is_none());
+}
+
+/// A box from x=0 to x=1 without the faces at x=0.
+fn open_half_box() -> Mesh {
+    let full = moved_box(Vec3::X * 0.5, Vec3::ONE);
+    let mut mesh = Mesh::default();
+    mesh.add_points(full.data_points_position.iter().copied());
+    for face in full.face_handles() {
+        let points = full.points_in_face(face);
+        if points
+            .iter()
+            .all(|point| full.position(*point).x.abs() < 1e-6)
+        {
+            continue;
+        }
+        mesh.add_tri(points);
+    }
+    mesh
+}
+
+#[test]
+fn mirror_with_weld_closes_the_mesh() {
+    let mesh = open_half_box();
+    assert_eq!(mesh.points_of_face.len(), 10);
+    let mirrored = mesh.mirror(Vec3::X, 0.0, true);
+    assert_closed_manifold(&mirrored);
+    // Only the points off the plane are duplicated.
+    assert_eq!(mirrored.data_points_position.len(), 12);
+    assert_eq!(mirrored.points_of_face.len(), 20);
+    assert!((volume(&mirrored) - 2.0).abs() < 1e-5);
+}
+
+#[test]
+fn mirror_without_weld_keeps_halves_apart() {
+    let mesh = open_half_box();
+    let mirrored = mesh.mirror(Vec3::X, 0.0, false);
+    assert_eq!(mirrored.data_points_position.len(), 16);
+    assert_eq!(mirrored.points_of_face.len(), 20);
+    assert!(mirrored.edges.values().any(|faces| faces[1].is_none()));
+    // The winding is flipped, so both halves enclose their volume the same way.
+    assert!((volume(&mirrored) - 2.0).abs() < 1e-5);
+}
+
+#[test]
+fn mirror_carries_hard_edges_across() {
+    let mut mesh = moved_box(Vec3::new(1.5, 0.0, 0.0), Vec3::ONE);
+    let hard = all_edges(&mesh)
+        .into_iter()
+        .find(|edge| {
+            edge.points()
+                .iter()
+                .all(|point| mesh.position(*point).x > 1.9 && mesh.position(*point).y > 0.4)
+        })
+        .unwrap();
+    mesh.set_edge_hard(hard);
+
+    let mirrored = mesh.mirror(Vec3::X, 0.5, true);
+    assert_eq!(mirrored.data_hard_edge.len(), 2);
+    for edge in mirrored.data_hard_edge.iter() {
+        let x = edge.points().map(|point| mirrored.position(point).x);
+        assert!(x == [2.0, 2.0] || x == [-1.0, -1.0], "{x:?}");
+    }
+    assert!((volume(&mirrored) - 2.0).abs() < 1e-5);
+}
+
+#[test]
+fn closed_shapes_are_manifold() {
+    use std::f32::consts::PI;
+
+    let sphere = Mesh::uv_sphere(1.0, 32, 16);
+    assert_closed_manifold(&sphere);
+    assert_eq!(sphere.data_points_position.len(), 32 * 15 + 2);
+    let volume_sphere = volume(&sphere);
+    assert!(
+        volume_sphere < 4.0 / 3.0 * PI && volume_sphere > 3.9,
+        "{volume_sphere}"
+    );
+
+    let icosphere = Mesh::icosphere(2.0, 2);
+    assert_closed_manifold(&icosphere);
+    assert_eq!(icosphere.points_of_face.len(), 20 * 16);
+    for position in icosphere.data_points_position.iter() {
+        assert!((position.length() - 2.0).abs() < 1e-5);
+    }
+    assert!(volume(&icosphere) > 0.0);
+
+    let torus = Mesh::torus(1.0, 0.25, 48, 24);
+    assert_watertight(&torus);
+    // A torus has a hole so the euler characteristic is zero.
+    let euler = torus.data_points_position.len() as i64 - torus.edges.len() as i64
+        + torus.points_of_face.len() as i64;
+    assert_eq!(euler, 0);
+    let volume_torus = volume(&torus);
+    let expected = 2.0 * PI * PI * 0.25 * 0.25;
+    assert!(
+        (volume_torus - expected).abs() < expected * 0.02,
+        "{volume_torus}"
+    );
+
+    let frustum = Mesh::cone(1.0, 0.5, 1.0, 4, true);
+    assert_closed_manifold(&frustum);
+    // Square frustum with a base area of 2 and a top area of 0.5.
+    assert!((volume(&frustum) - 7.0 / 6.0).abs() < 1e-5);
+}
+
+#[test]
+fn cone_ends_in_a_single_point() {
+    let cone = Mesh::cone(1.0, 0.0, 2.0, 8, true);
+    assert_closed_manifold(&cone);
+    assert_eq!(cone.data_points_position.len(), 9);
+    assert_eq!(cone.points_of_face.len(), 8 + 6);
+
+    let open = Mesh::cone(1.0, 1.0, 2.0, 8, false);
+    assert_eq!(open.points_of_face.len(), 16);
+    assert!(open.edges.values().any(|faces| faces[1].is_none()));
+}
+
+#[test]
+fn grid_faces_up() {
+    let grid = Mesh::grid(2.0, 4.0, 3, 2);
+    assert_eq!(grid.data_points_position.len(), 4 * 3);
+    assert_eq!(grid.points_of_face.len(), 3 * 2 * 2);
+    for face in grid.face_handles() {
+        assert!(grid.face_normal(face).abs_diff_eq(Vec3::Y, 1e-6));
+    }
+    let min = grid
+        .data_points_position
+        .iter()
+        .fold(Vec3::splat(f32::MAX), |min, position| min.min(*position));
+    assert_eq!(min, Vec3::new(-1.0, 0.0, -2.0));
+
+    let plane = Mesh::grid(1.0, 1.0, 1, 1);
+    assert_eq!(plane.points_of_face.len(), 2);
+}
+
+#[test]
+fn attributes_follow_new_elements() {
+    let mut mesh = unit_box();
+    mesh.add_attribute("weight", AttributeDomain::Point, 0.5_f32);
+    mesh.add_attribute("crease", AttributeDomain::Edge, false);
+    let point = PointHandle::new(3);
+    mesh.set_attribute("weight", Element::Point(point), 2.0_f32)
+        .unwrap();
+    assert_eq!(
+        mesh.attribute::<f32>("weight", Element::Point(point)),
+        Ok(2.0)
+    );
+
+    let new_point = mesh.add_point(Vec3::ONE);
+    assert_eq!(
+        mesh.attribute::<f32>("weight", Element::Point(new_point)),
+        Ok(0.5)
+    );
+    assert_eq!(mesh.attribute_values::<f32>("weight").unwrap().len(), 9);
+
+    let edge = all_edges(&mesh)[0];
+    mesh.set_attribute("crease", Element::Edge(edge), true)
+        .unwrap();
+    assert_eq!(
+        mesh.attribute::<bool>("crease", Element::Edge(edge)),
+        Ok(true)
+    );
+    assert_eq!(
+        mesh.attribute::<bool>("crease", Element::Edge(all_edges(&mesh)[1])),
+        Ok(false)
+    );
+
+    assert_eq!(
+        mesh.attribute::<i64>("weight", Element::Point(point)),
+        Err(AttributeError::WrongType)
+    );
+    assert_eq!(
+        mesh.attribute::<f32>("weight", Element::Face(FaceHandle::new(1))),
+        Err(AttributeError::WrongDomain)
+    );
+    assert_eq!(
+        mesh.attribute::<f32>("missing", Element::Point(point)),
+        Err(AttributeError::NotFound)
+    );
+
+    let metadata = mesh.attributes_metadata();
+    assert_eq!(metadata.len(), 2);
+    assert_eq!(metadata[0].0, AttributeDomain::Point);
+    assert_eq!(metadata[0].1.name, "weight");
+    assert_eq!(metadata[0].1.r#type, DataTypeKind::Float);
+
+    assert!(mesh.remove_attribute("weight"));
+    assert!(!mesh.remove_attribute("weight"));
+}
+
+#[test]
+fn buffers_split_points_with_differing_corners() {
+    let mut mesh = unit_box();
+    mesh.add_attribute("temperature", AttributeDomain::Point, 1.0_f32);
+    let buffers = mesh.get_index_mesh_buffers(false);
+    assert_eq!(buffers.positions.len(), 8);
+    assert!(matches!(
+        &buffers.attributes[0].values,
+        AttributeBufferValues::Float(values) if values.len() == 8
+    ));
+
+    // Every side gets its own id, so a corner of the box is split into three vertices.
+    mesh.add_attribute("side", AttributeDomain::Corner, 0_i64);
+    for face in mesh.face_handles() {
+        let normal = mesh.face_normal(face);
+        let side = normal.abs().max_position() as i64 * 2 + (normal.max_element() > 0.5) as i64;
+        for corner in 0..3 {
+            mesh.set_attribute("side", Element::Corner(face, corner), side)
+                .unwrap();
+        }
+    }
+    let buffers = mesh.get_index_mesh_buffers(false);
+    assert_eq!(buffers.positions.len(), 24);
+    assert_eq!(buffers.normals.len(), 24);
+    assert_eq!(buffers.indices.len(), 36);
+    assert_eq!(buffers.attributes[0].name, "temperature");
+    let AttributeBufferValues::Int(sides) = &buffers.attributes[1].values else {
+        unreachable!()
+    };
+    for triangle in buffers.indices.chunks(3) {
+        let side = sides[triangle[0] as usize];
+        assert!(triangle.iter().all(|index| sides[*index as usize] == side));
+    }
+}
+
+#[test]
+fn attributes_survive_join_mirror_and_transform() {
+    let mut mesh = open_half_box();
+    mesh.add_attribute("id", AttributeDomain::Corner, 0_i64);
+    let face = FaceHandle::new(1);
+    for corner in 0..3 {
+        mesh.set_attribute("id", Element::Corner(face, corner), corner as i64)
+            .unwrap();
+    }
+    let corner_of = |mesh: &Mesh, face: FaceHandle, point: PointHandle| {
+        let corner = mesh
+            .points_in_face(face)
+            .iter()
+            .position(|other| *other == point)
+            .unwrap();
+        mesh.attribute::<i64>("id", Element::Corner(face, corner))
+            .unwrap()
+    };
+
+    // The values stay on the same points when the winding order is flipped.
+    let mut flipped = mesh.clone();
+    flipped.transform(&Transform::from_scale(Vec3::new(1.0, -1.0, 1.0)));
+    for point in mesh.points_in_face(face) {
+        assert_eq!(
+            corner_of(&flipped, face, point),
+            corner_of(&mesh, face, point)
+        );
+    }
+
+    let mirrored = mesh.mirror(Vec3::X, 0.0, true);
+    let ids = mirrored.attribute_values::<i64>("id").unwrap();
+    assert_eq!(ids.len(), 20 * 3);
+    assert_eq!(ids.iter().filter(|id| **id == 2).count(), 2);
+
+    let joined = Mesh::join([&unit_box(), &mesh]);
+    let moved = FaceHandle::new(12 + 1);
+    assert_eq!(
+        joined.attribute::<i64>("id", Element::Corner(moved, 1)),
+        Ok(1)
+    );
+    assert_eq!(
+        joined.attribute::<i64>("id", Element::Corner(FaceHandle::new(1), 1)),
+        Ok(0)
+    );
+}
+
+/// The largest difference in U or V between the corners of any face.
+fn largest_uv_span(mesh: &Mesh) -> f32 {
+    mesh.face_handles()
+        .map(|face| {
+            let uvs = [0, 1, 2].map(|corner| mesh.uv(face, corner).unwrap());
+            let min = uvs[0].min(uvs[1]).min(uvs[2]);
+            let max = uvs[0].max(uvs[1]).max(uvs[2]);
+            (max - min).max_element()
+        })
+        .fold(0.0, f32::max)
+}
+
+#[test]
+fn planar_and_box_projections() {
+    let mut grid = Mesh::grid(2.0, 2.0, 2, 2);
+    grid.project_uvs(UvProjection::Planar, Vec3::Y, 0.5);
+    for face in grid.face_handles() {
+        for (corner, point) in grid.points_in_face(face).into_iter().enumerate() {
+            let position = grid.position(point);
+            let uv = grid.uv(face, corner).unwrap();
+            assert!(uv.abs_diff_eq(Vec2::new(position.x, -position.z) * 0.5, 1e-6));
+        }
+    }
+
+    let mut mesh = unit_box();
+    mesh.project_uvs(UvProjection::Box, Vec3::Y, 1.0);
+    assert!((largest_uv_span(&mesh) - 1.0).abs() < 1e-6);
+    // The sides are split apart except for the four points where two sides have the same UV.
+    let buffers = mesh.get_index_mesh_buffers(false);
+    assert_eq!(buffers.positions.len(), 20);
+    assert_eq!(buffers.uvs.len(), 20);
+    assert!(buffers.attributes.is_empty());
+}
+
+#[test]
+fn wrapping_projections_split_the_seam() {
+    let mut cylinder = Mesh::cone(1.0, 1.0, 2.0, 8, false);
+    cylinder.project_uvs(UvProjection::Cylindrical, Vec3::Y, 0.5);
+    // Faces crossing the seam don't stretch over the whole texture.
+    assert!((largest_uv_span(&cylinder) - 1.0).abs() < 1e-6);
+    let buffers = cylinder.get_index_mesh_buffers(false);
+    assert_eq!(buffers.positions.len(), 16 + 2);
+    let heights = buffers.uvs.iter().map(|uv| uv.y);
+    assert_eq!(heights.fold(0.0, f32::max), 1.0);
+
+    let mut sphere = Mesh::uv_sphere(1.0, 16, 8);
+    sphere.project_uvs(UvProjection::Spherical, Vec3::Y, 1.0);
+    assert!(largest_uv_span(&sphere) <= 1.0 / 8.0 + 1e-5);
+    let top = sphere.faces_in_point(PointHandle::new(1))[0];
+    let uv = [0, 1, 2].map(|corner| sphere.uv(top, corner).unwrap());
+    assert_eq!(uv[0].y, 1.0);
+    // The pole is in the middle of the two other corners.
+    assert!((uv[0].x - (uv[1].x + uv[2].x) * 0.5).abs() < 1e-6);
+}
+
+/// Adds box projected UVs and the height of every point as an attribute.
+fn with_attributes(mut mesh: Mesh) -> Mesh {
+    mesh.project_uvs(UvProjection::Box, Vec3::Y, 1.0);
+    mesh.add_attribute("height", AttributeDomain::Point, 0.0f32);
+    for point in mesh.point_handles() {
+        let height = mesh.position(point).y;
+        mesh.set_attribute("height", Element::Point(point), height)
+            .unwrap();
+    }
+    mesh
+}
+
+/// Checks that the attributes added by `with_attributes` still match the positions
+/// on all faces which lie on a side of a box.
+fn assert_box_attributes(mesh: &Mesh) {
+    for face in mesh.face_handles() {
+        let normal = mesh.face_normal(face);
+        let on_side = normal.abs().max_element() > 1.0 - 1e-5;
+        for (corner, point) in mesh.points_in_face(face).into_iter().enumerate() {
+            let position = mesh.position(point);
+            let uv = mesh.uv(face, corner).unwrap();
+            let height = mesh
+                .attribute::<f32>("height", Element::Point(point))
+                .unwrap();
+            if on_side {
+                let expected = box_uv(position, normal);
+                assert!(uv.abs_diff_eq(expected, 1e-5), "{uv} != {expected}");
+                assert!((height - position.y).abs() < 1e-5);
+            }
+        }
+    }
+}
+
+#[test]
+fn subdivision_interpolates_attributes() {
+    let subdivided = with_attributes(unit_box()).subdivide(false);
+    assert_box_attributes(&subdivided);
+}
+
+#[test]
+fn bevel_samples_attributes() {
+    let mesh = with_attributes(unit_box());
+    let beveled = mesh.bevel_edges(all_edges(&mesh), 0.1, 2);
+    assert_box_attributes(&beveled);
+}
+
+#[test]
+fn boolean_samples_attributes_of_both_meshes() {
+    let a = with_attributes(unit_box());
+    let b = with_attributes(moved_box(Vec3::new(0.5, 0.25, 0.3), Vec3::ONE));
+    let union = a.boolean(&b, BooleanMode::Union);
+    assert_box_attributes(&union);
+}
+
+#[test]
+fn tangents_follow_u_and_store_handedness() {
+    let mut grid = Mesh::grid(2.0, 2.0, 2, 2);
+    assert!(grid.get_index_mesh_buffers(true).tangents.is_empty());
+
+    grid.project_uvs(UvProjection::Planar, Vec3::Y, 1.0);
+    assert!(grid.get_index_mesh_buffers(false).tangents.is_empty());
+    let buffers = grid.get_index_mesh_buffers(true);
+    assert_eq!(buffers.tangents.len(), buffers.positions.len());
+    for tangent in &buffers.tangents {
+        assert!(tangent.truncate().abs_diff_eq(Vec3::X, 1e-6));
+        assert_eq!(tangent.w, 1.0);
+    }
+
+    // Mirroring U turns the tangent around and the bitangent stays, so the handedness flips.
+    let uvs = grid
+        .face_handles()
+        .flat_map(|face| (0..3).map(move |corner| (face, corner)))
+        .map(|(face, corner)| grid.uv(face, corner).unwrap() * Vec2::new(-1.0, 1.0))
+        .collect::<Vec<_>>();
+    grid.set_uvs(uvs);
+    for tangent in grid.get_index_mesh_buffers(true).tangents {
+        assert!(tangent.truncate().abs_diff_eq(Vec3::NEG_X, 1e-6));
+        assert_eq!(tangent.w, -1.0);
+    }
+
+    let mut cylinder = Mesh::cone(1.0, 1.0, 2.0, 8, true);
+    cylinder.project_uvs(UvProjection::Cylindrical, Vec3::Y, 0.5);
+    let buffers = cylinder.get_index_mesh_buffers(true);
+    for (tangent, normal) in buffers.tangents.iter().zip(&buffers.normals) {
+        assert!((tangent.truncate().length() - 1.0).abs() < 1e-5);
+        assert!(tangent.truncate().dot(*normal).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn auto_smooth_splits_normals_at_sharp_edges() {
+    let mut cylinder = Mesh::cone(1.0, 1.0, 2.0, 16, true);
+    // Without hard Edges every point has a single normal.
+    assert_eq!(cylinder.get_index_mesh_buffers(false).positions.len(), 32);
+
+    cylinder.auto_smooth(30f32.to_radians());
+    // Only the rims of the caps are sharper than the threshold.
+    assert_eq!(cylinder.data_hard_edge.len(), 32);
+
+    let buffers = cylinder.get_index_mesh_buffers(false);
+    assert_eq!(buffers.positions.len(), 64);
+    for (position, normal) in buffers.positions.iter().zip(&buffers.normals) {
+        let side = Vec3::new(position.x, 0.0, position.z).normalize();
+        // The sides are only smoothed with each other so their normals stay horizontal.
+        assert!(
+            (normal.y.abs() < 1e-5 && normal.dot(side) > 0.99)
+                || normal.abs_diff_eq(Vec3::Y, 1e-5)
+                || normal.abs_diff_eq(Vec3::NEG_Y, 1e-5),
+            "{normal} at {position}"
+        );
+    }
+}
+
+#[test]
+fn normal_weighting_modes() {
+    // The corner of a cube where one side is split into two triangles.
+    let mut mesh = Mesh::default();
+    let [corner, x, y, z, xy] = *mesh.add_points([
+        Vec3::ZERO,
+        Vec3::X,
+        Vec3::Y,
+        Vec3::Z,
+        Vec3::new(1.0, 1.0, 0.0),
+    ]) else {
+        unreachable!()
+    };
+    mesh.add_tri([corner, y, xy]);
+    mesh.add_tri([corner, xy, x]);
+    mesh.add_tri([corner, z, y]);
+    mesh.add_tri([corner, x, z]);
+
+    let normal = |mesh: &Mesh| mesh.get_index_mesh_buffers(false).normals[corner.idx()].abs();
+    let diagonal = Vec3::ONE.normalize();
+
+    mesh.set_normal_weighting(NormalWeighting::Uniform);
+    assert!(normal(&mesh).z > normal(&mesh).x);
+    mesh.set_normal_weighting(NormalWeighting::Area);
+    assert!(normal(&mesh).z > normal(&mesh).x);
+    // Splitting a face doesn't change how much it counts.
+    mesh.set_normal_weighting(NormalWeighting::Angle);
+    assert!(normal(&mesh).abs_diff_eq(diagonal, 1e-5));
+}
+
+#[test]
+fn validate_reports_broken_topology() {
+    for shape in [
+        Mesh::cone(1.0, 0.5, 2.0, 8, true),
+        Mesh::uv_sphere(1.0, 8, 4),
+        Mesh::icosphere(1.0, 1),
+        Mesh::torus(1.0, 0.25, 8, 6),
+        Mesh::grid(1.0, 1.0, 3, 2),
+    ] {
+        assert_eq!(shape.validate(), []);
+    }
+
+    let mut mesh = Mesh::default();
+    let points = mesh.add_points([
+        Vec3::ZERO,
+        Vec3::X,
+        Vec3::Y,
+        Vec3::new(1.0, 1.0, 0.0),
+        Vec3::ZERO,
+        Vec3::splat(5.0),
+        Vec3::X * 2.0,
+        Vec3::X * 3.0,
+        Vec3::new(-1.0, 0.5, 0.0),
+        Vec3::new(-1.0, 0.5, 1.0),
+    ]);
+    let point = |index: usize| points[index - 1];
+    mesh.add_tri([point(1), point(2), point(3)]);
+    // Walks the shared Edge in the same direction as the first face.
+    mesh.add_tri([point(2), point(3), point(4)]);
+    mesh.add_tri([point(2), point(7), point(8)]);
+    mesh.add_tri([point(3), point(1), point(9)]);
+    mesh.add_tri([point(1), point(3), point(10)]);
+
+    let issues = mesh.validate();
+    for issue in [
+        TopologyIssue::InconsistentWinding(FaceHandle::new(1), FaceHandle::new(2)),
+        TopologyIssue::DegenerateFace(FaceHandle::new(3)),
+        TopologyIssue::NonManifoldEdge(Edge::new((point(1), point(3)))),
+        TopologyIssue::DuplicatePoint(point(1), point(5)),
+        TopologyIssue::UnreferencedPoint(point(6)),
+    ] {
+        assert!(issues.contains(&issue), "{issue:?} missing in {issues:?}");
+    }
+}
+
+#[test]
+fn cleanup_welds_removes_degenerates_and_unifies_winding() {
+    let grid = Mesh::grid(2.0, 2.0, 2, 2);
+    // Every face gets its own points and one of them faces down.
+    let mut soup = Mesh::default();
+    for face in grid.face_handles() {
+        let positions = grid.points_in_face(face).map(|point| grid.position(point));
+        let mut points = [0, 1, 2].map(|corner| soup.add_point(positions[corner]));
+        if face.idx() == 3 {
+            points.swap(1, 2);
+        }
+        soup.add_tri(points);
+    }
+    let sliver = soup.add_points([Vec3::ZERO, Vec3::X * 0.5, Vec3::X]);
+    soup.add_tri([sliver[0], sliver[1], sliver[2]]);
+    assert!(!soup.validate().is_empty());
+
+    let cleaned = soup.cleanup(1e-4, true);
+    assert_eq!(cleaned.validate(), []);
+    assert_eq!(cleaned.point_handles().count(), 9);
+    assert_eq!(cleaned.face_handles().count(), 8);
+    for face in cleaned.face_handles() {
+        assert!(cleaned.face_normal(face).abs_diff_eq(Vec3::Y, 1e-6));
+    }
+
+    // Without unifying the flipped face stays as it is.
+    let kept = soup.cleanup(1e-4, false);
+    assert_eq!(kept.validate().len(), 1);
+}
@@ -0,0 +1,22 @@
+use super::Mesh;
+use crate::type_system::data_types::Transform;
+use crate::type_system::trait_types::Transformable;
+
+impl Transformable for Mesh {
+    /// Normals are derived from the positions so they follow any non uniform scale.
+    /// Mirroring transforms flip the winding order to keep the normals pointing outwards.
+    fn transform(&mut self, transform: &Transform) {
+        for position in self.data_points_position.iter_mut() {
+            *position = transform.transform_point3(*position);
+        }
+
+        if transform.matrix3.determinant() < 0.0 {
+            for points in self.points_of_face.iter_mut() {
+                points.swap(1, 2);
+            }
+            for face in self.face_handles() {
+                self.flip_corner_attributes(face);
+            }
+        }
+    }
+}
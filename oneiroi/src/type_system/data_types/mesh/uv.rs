@@ -0,0 +1,163 @@
+use std::f32::consts::{PI, TAU};
+
+use glam::{Vec2, Vec3};
+
+use super::{AttributeDomain, Element, FaceHandle, Mesh};
+use crate::type_system::trait_types::MeshMut0D;
+
+/// The corner attribute storing the texture coordinates in x and y.
+/// There is no Vec2 DataType so z is unused.
+pub(crate) const UV_ATTRIBUTE: &str = "uv";
+
+/// How positions are turned into texture coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UvProjection {
+    /// Straight along the axis.
+    Planar,
+    /// Along the object axis closest to the normal of each face.
+    Box,
+    /// Around the axis with U going once around it.
+    Cylindrical,
+    /// Around the axis with V going from pole to pole.
+    Spherical,
+}
+
+impl UvProjection {
+    /// The projection as stored in the integer property of the UvProjectV1 node.
+    /// Returns None for values which don't name a projection.
+    pub(crate) fn from_index(index: i64) -> Option<Self> {
+        match index {
+            0 => Some(UvProjection::Planar),
+            1 => Some(UvProjection::Box),
+            2 => Some(UvProjection::Cylindrical),
+            3 => Some(UvProjection::Spherical),
+            _ => None,
+        }
+    }
+}
+
+/// Projects the position onto the side of a box the normal points to.
+/// Every side is seen from the outside so textures are never mirrored.
+pub(crate) fn box_uv(position: Vec3, normal: Vec3) -> Vec2 {
+    let abs = normal.abs();
+    if abs.x >= abs.y && abs.x >= abs.z {
+        Vec2::new(-position.z * normal.x.signum(), position.y)
+    } else if abs.y >= abs.z {
+        Vec2::new(position.x, -position.z * normal.y.signum())
+    } else {
+        Vec2::new(position.x * normal.z.signum(), position.y)
+    }
+}
+
+/// Two directions perpendicular to the axis, the first one as close to X as possible.
+fn axis_basis(axis: Vec3) -> (Vec3, Vec3) {
+    let reference = if axis.x.abs() > 0.99 {
+        Vec3::Z
+    } else {
+        Vec3::X
+    };
+    let right = (reference - axis * axis.dot(reference)).normalize();
+    (right, axis.cross(right))
+}
+
+impl Mesh {
+    pub(crate) fn uv(&self, face: FaceHandle, corner: usize) -> Option<Vec2> {
+        self.attribute::<Vec3>(UV_ATTRIBUTE, Element::Corner(face, corner))
+            .ok()
+            .map(|uv| uv.truncate())
+    }
+
+    /// Replaces the UVs with one for every corner of every face in order.
+    pub(crate) fn set_uvs(&mut self, uvs: impl IntoIterator<Item = Vec2>) {
+        self.add_attribute(UV_ATTRIBUTE, AttributeDomain::Corner, Vec3::ZERO);
+        let faces = self.face_handles().collect::<Vec<_>>();
+        let corners = faces
+            .into_iter()
+            .flat_map(|face| (0..3).map(move |corner| Element::Corner(face, corner)));
+        for (corner, uv) in corners.zip(uvs) {
+            self.set_attribute(UV_ATTRIBUTE, corner, uv.extend(0.0))
+                .unwrap();
+        }
+    }
+
+    /// Generates the UVs of all faces with the projection.
+    /// Planar and box projections repeat the texture `scale` times per unit,
+    /// the cylindrical one only along the axis and the spherical one not at all.
+    pub(crate) fn project_uvs(&mut self, projection: UvProjection, axis: Vec3, scale: f32) {
+        let axis = axis.normalize_or(Vec3::Y);
+        let (right, up) = axis_basis(axis);
+
+        let mut uvs = Vec::with_capacity(self.points_of_face.len() * 3);
+        for face in self.face_handles() {
+            let positions = self.points_in_face(face).map(|point| self.position(point));
+            let face_uvs = match projection {
+                UvProjection::Planar => positions
+                    .map(|position| Vec2::new(position.dot(right), position.dot(up)) * scale),
+                UvProjection::Box => {
+                    let normal = self.face_normal(face);
+                    positions.map(|position| box_uv(position, normal) * scale)
+                }
+                UvProjection::Cylindrical => {
+                    let heights = positions.map(|position| position.dot(axis) * scale);
+                    wrap_around(positions, right, up, axis, heights)
+                }
+                UvProjection::Spherical => {
+                    let heights = positions.map(|position| {
+                        let cos = position.normalize_or_zero().dot(axis).clamp(-1.0, 1.0);
+                        1.0 - cos.acos() / PI
+                    });
+                    wrap_around(positions, right, up, axis, heights)
+                }
+            };
+            uvs.extend(face_uvs);
+        }
+        self.set_uvs(uvs);
+    }
+}
+
+/// UVs going once around the axis for the corners of one face.
+/// Faces crossing the seam continue past 1 instead of spanning the whole texture
+/// and points on the axis take the U of the rest of the face.
+fn wrap_around(
+    positions: [Vec3; 3],
+    right: Vec3,
+    up: Vec3,
+    axis: Vec3,
+    heights: [f32; 3],
+) -> [Vec2; 3] {
+    let on_axis = positions.map(|position| {
+        let radial = position - axis * position.dot(axis);
+        radial.length_squared() < 1e-12
+    });
+    let mut us =
+        positions.map(|position| position.dot(up).atan2(position.dot(right)).rem_euclid(TAU) / TAU);
+
+    let around = (0..3).filter(|corner| !on_axis[*corner]);
+    let min = around
+        .clone()
+        .map(|corner| us[corner])
+        .fold(f32::MAX, f32::min);
+    let max = around
+        .clone()
+        .map(|corner| us[corner])
+        .fold(f32::MIN, f32::max);
+    if max - min > 0.5 {
+        for corner in around.clone() {
+            if us[corner] < 0.5 {
+                us[corner] += 1.0;
+            }
+        }
+    }
+
+    let count = around.clone().count();
+    if count > 0 {
+        let average = around.map(|corner| us[corner]).sum::<f32>() / count as f32;
+        for corner in 0..3 {
+            if on_axis[corner] {
+                us[corner] = average;
+            }
+        }
+    }
+
+    [0, 1, 2].map(|corner| Vec2::new(us[corner], heights[corner]))
+}
@@ -0,0 +1,244 @@
+use std::collections::{HashMap, VecDeque};
+
+use glam::{I64Vec3, Vec3};
+
+use super::{Edge, FaceHandle, Mesh, PointHandle};
+use crate::type_system::trait_types::{MeshMut0D, MeshMut2D};
+
+/// Points closer than this are reported as duplicates.
+const DUPLICATE_DISTANCE: f32 = 1e-6;
+/// Faces whose area is this small compared to their longest Edge are degenerate.
+const DEGENERATE_RATIO: f32 = 1e-6;
+
+/// A problem with the topology or geometry of a Mesh found by [`Mesh::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopologyIssue {
+    /// An Edge shared by more than two faces.
+    NonManifoldEdge(Edge),
+    /// A face without an area because its points are the same or lie on a line.
+    DegenerateFace(FaceHandle),
+    /// The second point lies on top of the first one.
+    DuplicatePoint(PointHandle, PointHandle),
+    /// A point which is not used by any face.
+    UnreferencedPoint(PointHandle),
+    /// Two faces sharing an Edge which they walk along in the same direction,
+    /// so one of them faces the wrong way.
+    InconsistentWinding(FaceHandle, FaceHandle),
+}
+
+/// Whether the face walks along the Edge from its smaller to its larger point.
+fn walks_forward(points: [PointHandle; 3], edge: Edge) -> bool {
+    (0..3).any(|corner| points[corner] == edge.0 && points[(corner + 1) % 3] == edge.1)
+}
+
+impl Mesh {
+    /// Checks the Mesh for problems which can't be ruled out while building it.
+    /// Returns an empty list if none were found.
+    pub fn validate(&self) -> Vec<TopologyIssue> {
+        let mut issues = Vec::new();
+
+        let faces_of_edge = faces_of_edges(self.face_handles(), |face| self.edges_in_face(face));
+        let mut edges = faces_of_edge.keys().copied().collect::<Vec<_>>();
+        edges.sort();
+        for edge in edges {
+            let faces = &faces_of_edge[&edge];
+            if faces.len() > 2 {
+                issues.push(TopologyIssue::NonManifoldEdge(edge));
+            } else if let [first, second] = faces[..]
+                && walks_forward(self.points_in_face(first), edge)
+                    == walks_forward(self.points_in_face(second), edge)
+            {
+                issues.push(TopologyIssue::InconsistentWinding(first, second));
+            }
+        }
+
+        for face in self.face_handles() {
+            let positions = self.points_in_face(face).map(|point| self.position(point));
+            if is_degenerate(positions) {
+                issues.push(TopologyIssue::DegenerateFace(face));
+            }
+        }
+
+        for (point, welded) in self.point_handles().zip(self.weld_map(DUPLICATE_DISTANCE)) {
+            if welded != point {
+                issues.push(TopologyIssue::DuplicatePoint(welded, point));
+            }
+            if self.faces_in_point(point).is_empty() {
+                issues.push(TopologyIssue::UnreferencedPoint(point));
+            }
+        }
+
+        issues
+    }
+
+    /// Rebuilds the Mesh without the problems reported by [`Mesh::validate`].
+    /// Points closer than the distance are merged and faces without an area are removed
+    /// together with the points no face uses anymore.
+    /// When unifying the winding each connected part keeps the winding of its first face.
+    pub(crate) fn cleanup(&self, distance: f32, unify_winding: bool) -> Mesh {
+        let welded = self.weld_map(distance);
+
+        let faces = self
+            .face_handles()
+            .filter(|face| {
+                let points = self.points_in_face(*face).map(|point| welded[point.idx()]);
+                points[0] != points[1]
+                    && points[1] != points[2]
+                    && points[0] != points[2]
+                    && !is_degenerate(points.map(|point| self.position(point)))
+            })
+            .collect::<Vec<_>>();
+        let flipped = if unify_winding {
+            self.unified_winding(&faces, &welded)
+        } else {
+            HashMap::new()
+        };
+
+        let mut cleaned = Mesh {
+            material: self.material,
+            normal_weighting: self.normal_weighting,
+            ..Default::default()
+        };
+        let mut points = HashMap::new();
+        let mut new_faces = HashMap::new();
+        for face in faces {
+            let mut face_points = self.points_in_face(face).map(|point| {
+                let point = welded[point.idx()];
+                *points
+                    .entry(point)
+                    .or_insert_with(|| cleaned.add_point(self.position(point)))
+            });
+            if flipped.get(&face) == Some(&true) {
+                face_points.swap(1, 2);
+            }
+            let new_face = cleaned.add_tri(face_points);
+            cleaned.set_face_material(new_face, self.get_face_material_ref(face));
+            new_faces.insert(face, new_face);
+        }
+
+        for edge in self.data_hard_edge.iter() {
+            let (Some(first), Some(second)) = (
+                points.get(&welded[edge.0.idx()]),
+                points.get(&welded[edge.1.idx()]),
+            ) else {
+                continue;
+            };
+            if first != second {
+                let edge = Edge::new((*first, *second));
+                if cleaned.edges.contains_key(&edge) {
+                    cleaned.set_edge_hard(edge);
+                }
+            }
+        }
+        cleaned.copy_all_attributes(
+            self,
+            |point| points.get(&welded[point.idx()]).copied(),
+            |face| new_faces.get(&face).copied(),
+        );
+        for (face, flip) in flipped {
+            if flip {
+                cleaned.flip_corner_attributes(new_faces[&face]);
+            }
+        }
+
+        cleaned
+    }
+
+    /// Maps every point to the first point within the distance of it, which may be itself.
+    fn weld_map(&self, distance: f32) -> Vec<PointHandle> {
+        let cell_size = distance.max(f32::MIN_POSITIVE);
+        let cell = |position: Vec3| (position / cell_size).floor().as_i64vec3();
+
+        let mut cells: HashMap<_, Vec<PointHandle>> = HashMap::new();
+        let mut welded = Vec::with_capacity(self.faces_of_point.len());
+        for point in self.point_handles() {
+            let position = self.position(point);
+            let center = cell(position);
+            let mut neighbours = (-1..=1).flat_map(|x| {
+                (-1..=1).flat_map(move |y| (-1..=1).map(move |z| center + I64Vec3::new(x, y, z)))
+            });
+            let existing = neighbours.find_map(|key| {
+                cells
+                    .get(&key)?
+                    .iter()
+                    .copied()
+                    .find(|other| self.position(*other).distance(position) <= distance)
+            });
+            match existing {
+                Some(other) => welded.push(other),
+                None => {
+                    cells.entry(center).or_default().push(point);
+                    welded.push(point);
+                }
+            }
+        }
+        welded
+    }
+
+    /// Decides which of the faces to flip so neighbours walk their shared Edges in opposite directions.
+    /// The winding only spreads across Edges shared by exactly two faces.
+    fn unified_winding(
+        &self,
+        faces: &[FaceHandle],
+        welded: &[PointHandle],
+    ) -> HashMap<FaceHandle, bool> {
+        let welded_points =
+            |face: FaceHandle| self.points_in_face(face).map(|point| welded[point.idx()]);
+        let edges_of = |face: FaceHandle| {
+            let [a, b, c] = welded_points(face);
+            [Edge::new((a, b)), Edge::new((b, c)), Edge::new((a, c))]
+        };
+
+        let faces_of_edge = faces_of_edges(faces.iter().copied(), edges_of);
+
+        let mut flipped = HashMap::new();
+        for seed in faces {
+            if flipped.contains_key(seed) {
+                continue;
+            }
+            flipped.insert(*seed, false);
+            let mut queue = VecDeque::from([*seed]);
+            while let Some(face) = queue.pop_front() {
+                let flip = flipped[&face];
+                for edge in edges_of(face) {
+                    let [first, second] = faces_of_edge[&edge][..] else {
+                        continue;
+                    };
+                    let other = if first == face { second } else { first };
+                    if flipped.contains_key(&other) {
+                        continue;
+                    }
+                    let forward = walks_forward(welded_points(face), edge) != flip;
+                    let other_forward = walks_forward(welded_points(other), edge);
+                    flipped.insert(other, forward == other_forward);
+                    queue.push_back(other);
+                }
+            }
+        }
+        flipped
+    }
+}
+
+/// The faces using each Edge, counted from the faces themselves
+/// since an Edge only keeps track of two of them.
+fn faces_of_edges(
+    faces: impl IntoIterator<Item = FaceHandle>,
+    edges_of: impl Fn(FaceHandle) -> [Edge; 3],
+) -> HashMap<Edge, Vec<FaceHandle>> {
+    let mut faces_of_edge: HashMap<Edge, Vec<FaceHandle>> = HashMap::new();
+    for face in faces {
+        for edge in edges_of(face) {
+            faces_of_edge.entry(edge).or_default().push(face);
+        }
+    }
+    faces_of_edge
+}
+
+/// Whether the triangle has next to no area compared to its size.
+fn is_degenerate([a, b, c]: [Vec3; 3]) -> bool {
+    let longest = (b - a)
+        .length_squared()
+        .max((c - b).length_squared())
+        .max((a - c).length_squared());
+    (b - a).cross(c - a).length() <= longest * DEGENERATE_RATIO
+}
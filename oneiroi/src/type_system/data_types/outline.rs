@@ -7,7 +7,7 @@ use crate::type_system::{
 };
 
 //TODO maybe make this always hold Vec3s
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct Outline {
     // Points are inserted in counter clockwise order
     points: Vec<Vec2>,
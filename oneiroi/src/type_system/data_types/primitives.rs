@@ -4,11 +4,19 @@ use serde::{Deserialize, Serialize};
 
 use crate::type_system::{
     data_types::{ArributeMetadata, DataType, DataTypeKind},
+    trait_types::Transformable,
     variants::{TypeRef, OwnedDataType},
 };
 
 pub type Vec3 = MathVec3;
 
+/// A Vec3 is transformed as a point.
+impl Transformable for Vec3 {
+    fn transform(&mut self, transform: &Transform) {
+        *self = transform.transform_point3(*self);
+    }
+}
+
 impl DataType for Vec3 {
     fn get_type_ref(value: TypeRef) -> &Self {
         match value {
@@ -187,6 +195,12 @@ impl DataType for Int {
 }
 
 pub type Transform = Affine3A;
+
+impl Transformable for Transform {
+    fn transform(&mut self, transform: &Transform) {
+        *self = *transform * *self;
+    }
+}
 impl DataType for Transform {
     const DATA_TYPE_TYPE: DataTypeKind = DataTypeKind::Transform;
 
@@ -219,7 +233,7 @@ impl DataType for Transform {
     type ConfigurationOptions = ();
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Color(DynamicColor);
 
 impl Default for Color {
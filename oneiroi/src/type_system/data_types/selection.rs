@@ -1,12 +1,64 @@
+use std::sync::{Arc, Mutex, OnceLock};
+
+use chumsky::Parser;
 use serde::{Deserialize, Serialize};
 
-use crate::type_system::variants::{TypeRef, OwnedDataType};
+use crate::type_system::variants::{OwnedDataType, TypeRef};
+
+use super::{
+    ArributeMetadata, DataType, DataTypeKind,
+    mesh::{Edge, Mesh},
+};
+
+mod query;
+
+pub(crate) use query::{ElementKind, Elements};
 
-use super::{ArributeMetadata, DataType, DataTypeKind, mesh::FaceHandle};
+#[cfg(test)]
+mod tests;
+
+/// How many resolved Meshes are remembered by a Selection.
+const CACHE_SIZE: usize = 4;
+
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Invalid selection `{literal}`: {message}")]
+pub struct SelectionError {
+    literal: String,
+    message: String,
+}
 
+/// A query which picks faces, Edges or points of a Mesh e.g.
+/// - `all` or `*` selects everything.
+/// - `0..4, 7` selects by index, the end of a range is exclusive.
+/// - `facing(0, 1, 0)` selects faces pointing upwards, an optional fourth argument sets the angle.
+/// - `boundary` and `sharp(30)` select Edges.
+/// - `grow(q, 2)` and `shrink(q)` expand or reduce a query by adjacent elements.
+/// - `faces(q)`, `edges(q)` and `points(q)` convert a query into another kind.
+///
+/// Queries can be combined with `|` (union), `&` (intersection) and `-` (difference).
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct Selection {
     literal: String,
+
+    #[serde(skip)]
+    query: OnceLock<Result<query::Query, SelectionError>>,
+    // Clones share the cache since they share the same query.
+    #[serde(skip)]
+    cache: Arc<Mutex<Vec<CachedElements>>>,
+}
+
+// The query and the cache are derived from the literal.
+impl PartialEq for Selection {
+    fn eq(&self, other: &Self) -> bool {
+        self.literal == other.literal
+    }
+}
+
+#[derive(Debug)]
+struct CachedElements {
+    mesh: Mesh,
+    kind: ElementKind,
+    elements: Arc<Elements>,
 }
 
 impl DataType for Selection {
@@ -17,19 +69,29 @@ impl DataType for Selection {
     }
 
     fn get_type(value: OwnedDataType) -> Self {
-        todo!()
+        match value {
+            OwnedDataType::Selection(val) => *val,
+            _ => unreachable!(),
+        }
     }
 
     fn to_data_type_value(&self) -> OwnedDataType {
-        todo!()
+        OwnedDataType::Selection(Box::new(self.clone()))
     }
 
     fn get_type_ref(value: TypeRef) -> &Self {
-        todo!()
+        match value {
+            TypeRef::Selection(val) => val,
+            _ => unreachable!(),
+        }
     }
 
     fn to_data_type_ref(&self) -> TypeRef {
-        todo!()
+        TypeRef::Selection(self)
+    }
+
+    fn generate_script(&self) -> String {
+        self.literal.clone()
     }
 
     type ConfigurationOptions = ();
@@ -39,6 +101,7 @@ impl Selection {
     pub fn new(selection: &str) -> Self {
         Selection {
             literal: selection.into(),
+            ..Default::default()
         }
     }
 
@@ -46,10 +109,63 @@ impl Selection {
         &self.literal
     }
 
-    //TODO this should be a slice
-    pub fn try_get(&self) -> Result<Vec<FaceHandle>, ()> {
-        let parsed = self.literal.parse::<usize>();
-        println!("{parsed:?}");
-        Ok(vec![FaceHandle::new(parsed.unwrap())])
+    /// Checks the syntax of the query without a Mesh.
+    pub fn check(&self) -> Result<(), SelectionError> {
+        self.query().map(|_| ())
+    }
+
+    fn query(&self) -> Result<&query::Query, SelectionError> {
+        self.query
+            .get_or_init(|| {
+                query::parser()
+                    .parse(self.literal.as_str())
+                    .into_result()
+                    .map_err(|errors| SelectionError {
+                        literal: self.literal.clone(),
+                        message: errors
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    })
+            })
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+
+    /// Resolves the query against the Mesh and converts the result into the kind.
+    /// Results are cached as long as the Mesh stays unchanged.
+    pub(crate) fn resolve(
+        &self,
+        mesh: &Mesh,
+        kind: ElementKind,
+    ) -> Result<Arc<Elements>, SelectionError> {
+        let query = self.query()?;
+
+        let mut cache = self.cache.lock().unwrap();
+        if let Some(cached) = cache
+            .iter()
+            .find(|cached| cached.kind == kind && cached.mesh.is_same_mesh(mesh))
+        {
+            return Ok(cached.elements.clone());
+        }
+
+        let elements = Arc::new(query.evaluate(mesh, kind).convert(mesh, kind));
+        if cache.len() == CACHE_SIZE {
+            cache.remove(0);
+        }
+        cache.push(CachedElements {
+            mesh: mesh.clone(),
+            kind,
+            elements: elements.clone(),
+        });
+        Ok(elements)
+    }
+
+    pub(crate) fn edges(&self, mesh: &Mesh) -> Result<Vec<Edge>, SelectionError> {
+        match &*self.resolve(mesh, ElementKind::Edge)? {
+            Elements::Edges(edges) => Ok(edges.iter().copied().collect()),
+            _ => unreachable!(),
+        }
     }
 }
@@ -0,0 +1,415 @@
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+use chumsky::prelude::*;
+use glam::Vec3;
+
+use crate::type_system::data_types::mesh::{Edge, FaceHandle, Mesh, PointHandle};
+
+/// The kind of Mesh elements a Selection resolves to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ElementKind {
+    Face,
+    Edge,
+    Point,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Query {
+    /// Every element of the requested kind.
+    All,
+    /// Indices of the requested kind, Edges are numbered in the order of their points.
+    Indices(Vec<Range<usize>>),
+    /// Converts the inner Query into the kind, `faces` on its own selects all faces.
+    Kind(ElementKind, Box<Query>),
+    /// Faces whose normal is within the angle in degrees of the direction.
+    Facing(Vec3, f32),
+    /// Edges with only a single face.
+    Boundary,
+    /// Edges whose faces meet at an angle in degrees larger than the threshold.
+    Sharp(f32),
+    Grow(Box<Query>, usize),
+    Shrink(Box<Query>, usize),
+
+    Union(Box<Query>, Box<Query>),
+    Intersection(Box<Query>, Box<Query>),
+    Difference(Box<Query>, Box<Query>),
+}
+
+const DEFAULT_FACING_ANGLE: f32 = 45.0;
+const DEFAULT_SHARP_ANGLE: f32 = 30.0;
+
+pub(super) fn parser<'a>() -> impl Parser<'a, &'a str, Query, extra::Err<Rich<'a, char>>> {
+    // Rich errors only accept static labels so keywords are matched as identifiers.
+    let keyword = |name: &'static str| {
+        text::ascii::ident()
+            .filter(move |ident: &&str| *ident == name)
+            .padded()
+    };
+    let op = |c| just(c).padded();
+
+    let int = text::int(10).from_str::<usize>().unwrapped().padded();
+    let number = just('-')
+        .or_not()
+        .then(text::int(10))
+        .then(just('.').then(text::digits(10)).or_not())
+        .to_slice()
+        .from_str::<f32>()
+        .unwrapped()
+        .padded();
+
+    recursive(|query| {
+        let arguments = |count| {
+            number
+                .separated_by(op(','))
+                .at_least(count)
+                .at_most(count + 1)
+                .collect::<Vec<_>>()
+                .delimited_by(op('('), op(')'))
+        };
+
+        let range = int
+            .then(just("..").padded().ignore_then(int).or_not())
+            .map(|(start, end)| start..end.unwrap_or(start + 1));
+        let indices = range
+            .separated_by(op(','))
+            .at_least(1)
+            .collect::<Vec<_>>()
+            .map(Query::Indices);
+
+        let kind = choice((
+            keyword("faces").to(ElementKind::Face),
+            keyword("edges").to(ElementKind::Edge),
+            keyword("points").to(ElementKind::Point),
+        ))
+        .then(query.clone().delimited_by(op('('), op(')')).or_not())
+        .map(|(kind, query)| Query::Kind(kind, Box::new(query.unwrap_or(Query::All))));
+
+        let facing = keyword("facing")
+            .ignore_then(arguments(3))
+            .map(|arguments: Vec<f32>| {
+                Query::Facing(
+                    Vec3::new(arguments[0], arguments[1], arguments[2]),
+                    arguments.get(3).copied().unwrap_or(DEFAULT_FACING_ANGLE),
+                )
+            });
+
+        let sharp = keyword("sharp")
+            .ignore_then(number.delimited_by(op('('), op(')')).or_not())
+            .map(|angle| Query::Sharp(angle.unwrap_or(DEFAULT_SHARP_ANGLE)));
+
+        let adjacency = query
+            .clone()
+            .then(op(',').ignore_then(int).or_not())
+            .delimited_by(op('('), op(')'))
+            .map(|(query, steps)| (Box::new(query), steps.unwrap_or(1)));
+        let grow = keyword("grow")
+            .ignore_then(adjacency.clone())
+            .map(|(query, steps)| Query::Grow(query, steps));
+        let shrink = keyword("shrink")
+            .ignore_then(adjacency)
+            .map(|(query, steps)| Query::Shrink(query, steps));
+
+        let atom = choice((
+            query.clone().delimited_by(op('('), op(')')),
+            op('*').to(Query::All),
+            keyword("all").to(Query::All),
+            keyword("boundary").to(Query::Boundary),
+            kind,
+            facing,
+            sharp,
+            grow,
+            shrink,
+            indices,
+        ));
+
+        let intersection = atom
+            .clone()
+            .foldl(op('&').ignore_then(atom).repeated(), |lhs, rhs| {
+                Query::Intersection(Box::new(lhs), Box::new(rhs))
+            });
+
+        intersection.clone().foldl(
+            choice((
+                op('|').to(Query::Union as fn(_, _) -> _),
+                op('-').to(Query::Difference as fn(_, _) -> _),
+            ))
+            .then(intersection)
+            .repeated(),
+            |lhs, (op, rhs)| op(Box::new(lhs), Box::new(rhs)),
+        )
+    })
+    .padded()
+    .then_ignore(end())
+}
+
+/// The resolved elements of a Selection, sorted by their handles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Elements {
+    Faces(BTreeSet<FaceHandle>),
+    Edges(BTreeSet<Edge>),
+    Points(BTreeSet<PointHandle>),
+}
+
+impl Elements {
+    fn kind(&self) -> ElementKind {
+        match self {
+            Elements::Faces(_) => ElementKind::Face,
+            Elements::Edges(_) => ElementKind::Edge,
+            Elements::Points(_) => ElementKind::Point,
+        }
+    }
+
+    fn all(mesh: &Mesh, kind: ElementKind) -> Self {
+        match kind {
+            ElementKind::Face => Elements::Faces(mesh.face_handles().collect()),
+            ElementKind::Edge => Elements::Edges(mesh.edge_handles().into_iter().collect()),
+            ElementKind::Point => Elements::Points(mesh.point_handles().collect()),
+        }
+    }
+
+    fn indices(mesh: &Mesh, kind: ElementKind, ranges: &[Range<usize>]) -> Self {
+        match kind {
+            ElementKind::Face => Elements::Faces(
+                mesh.face_handles()
+                    .filter(|face| ranges.iter().any(|range| range.contains(&face.idx())))
+                    .collect(),
+            ),
+            ElementKind::Edge => {
+                let edges = mesh.edge_handles();
+                Elements::Edges(
+                    ranges
+                        .iter()
+                        .filter_map(|range| edges.get(range.start..range.end.min(edges.len())))
+                        .flatten()
+                        .copied()
+                        .collect(),
+                )
+            }
+            ElementKind::Point => Elements::Points(
+                mesh.point_handles()
+                    .filter(|point| ranges.iter().any(|range| range.contains(&point.idx())))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Faces and Edges convert into all of their points.
+    /// The other way around a face or Edge is only selected when all of its points are.
+    pub(crate) fn convert(self, mesh: &Mesh, kind: ElementKind) -> Self {
+        match (self, kind) {
+            (Elements::Faces(faces), ElementKind::Edge) => Elements::Edges(
+                faces
+                    .into_iter()
+                    .flat_map(|face| mesh.edges_in_face(face))
+                    .collect(),
+            ),
+            (Elements::Faces(faces), ElementKind::Point) => Elements::Points(
+                faces
+                    .into_iter()
+                    .flat_map(|face| mesh.points_in_face(face))
+                    .collect(),
+            ),
+            (Elements::Edges(edges), ElementKind::Face) => Elements::Faces(
+                mesh.face_handles()
+                    .filter(|face| {
+                        mesh.edges_in_face(*face)
+                            .iter()
+                            .all(|edge| edges.contains(edge))
+                    })
+                    .collect(),
+            ),
+            (Elements::Edges(edges), ElementKind::Point) => {
+                Elements::Points(edges.into_iter().flat_map(|edge| edge.points()).collect())
+            }
+            (Elements::Points(points), ElementKind::Face) => Elements::Faces(
+                mesh.face_handles()
+                    .filter(|face| {
+                        mesh.points_in_face(*face)
+                            .iter()
+                            .all(|point| points.contains(point))
+                    })
+                    .collect(),
+            ),
+            (Elements::Points(points), ElementKind::Edge) => Elements::Edges(
+                mesh.edge_handles()
+                    .into_iter()
+                    .filter(|edge| edge.points().iter().all(|point| points.contains(point)))
+                    .collect(),
+            ),
+            (elements, _) => elements,
+        }
+    }
+
+    /// Adds every element which shares an Edge with a face, a point with an Edge
+    /// or an Edge with a point.
+    fn grow(self, mesh: &Mesh) -> Self {
+        match self {
+            Elements::Faces(faces) => Elements::Faces(
+                faces
+                    .iter()
+                    .flat_map(|face| mesh.edges_in_face(*face))
+                    .flat_map(|edge| mesh.faces_in_edge(edge))
+                    .chain(faces.iter().copied())
+                    .collect(),
+            ),
+            Elements::Edges(edges) => Elements::Edges(
+                edges
+                    .iter()
+                    .flat_map(|edge| edge.points())
+                    .flat_map(|point| mesh.edges_in_point(point))
+                    .chain(edges.iter().copied())
+                    .collect(),
+            ),
+            Elements::Points(points) => Elements::Points(
+                points
+                    .iter()
+                    .flat_map(|point| mesh.edges_in_point(*point))
+                    .flat_map(|edge| edge.points())
+                    .chain(points.iter().copied())
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Removes every element which is next to an unselected element.
+    /// The boundary of the Mesh itself doesn't shrink the selection.
+    fn shrink(self, mesh: &Mesh) -> Self {
+        match self {
+            Elements::Faces(faces) => Elements::Faces(
+                faces
+                    .iter()
+                    .copied()
+                    .filter(|face| {
+                        mesh.edges_in_face(*face)
+                            .into_iter()
+                            .flat_map(|edge| mesh.faces_in_edge(edge))
+                            .all(|neighbour| faces.contains(&neighbour))
+                    })
+                    .collect(),
+            ),
+            Elements::Edges(edges) => Elements::Edges(
+                edges
+                    .iter()
+                    .copied()
+                    .filter(|edge| {
+                        edge.points()
+                            .into_iter()
+                            .flat_map(|point| mesh.edges_in_point(point))
+                            .all(|neighbour| edges.contains(&neighbour))
+                    })
+                    .collect(),
+            ),
+            Elements::Points(points) => Elements::Points(
+                points
+                    .iter()
+                    .copied()
+                    .filter(|point| {
+                        mesh.edges_in_point(*point)
+                            .all(|edge| edge.points().iter().all(|point| points.contains(point)))
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// The other elements are converted into the kind of these first.
+    fn combine(self, other: Self, mesh: &Mesh, operation: Operation) -> Self {
+        fn apply<T: Ord + Copy>(
+            a: BTreeSet<T>,
+            b: BTreeSet<T>,
+            operation: Operation,
+        ) -> BTreeSet<T> {
+            match operation {
+                Operation::Union => a.union(&b).copied().collect(),
+                Operation::Intersection => a.intersection(&b).copied().collect(),
+                Operation::Difference => a.difference(&b).copied().collect(),
+            }
+        }
+
+        let kind = self.kind();
+        match (self, other.convert(mesh, kind)) {
+            (Elements::Faces(a), Elements::Faces(b)) => Elements::Faces(apply(a, b, operation)),
+            (Elements::Edges(a), Elements::Edges(b)) => Elements::Edges(apply(a, b, operation)),
+            (Elements::Points(a), Elements::Points(b)) => Elements::Points(apply(a, b, operation)),
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Operation {
+    Union,
+    Intersection,
+    Difference,
+}
+
+impl Query {
+    /// Elements without an inherent kind like `all` or indices resolve to the expected kind.
+    /// The result is not converted, so it can be of another kind.
+    pub(super) fn evaluate(&self, mesh: &Mesh, expected: ElementKind) -> Elements {
+        match self {
+            Query::All => Elements::all(mesh, expected),
+            Query::Indices(ranges) => Elements::indices(mesh, expected, ranges),
+            Query::Kind(kind, query) => query.evaluate(mesh, *kind).convert(mesh, *kind),
+            Query::Facing(direction, angle) => {
+                let direction = direction.normalize_or_zero();
+                let min_cos = angle.to_radians().cos();
+                Elements::Faces(
+                    mesh.face_handles()
+                        .filter(|face| mesh.face_normal(*face).dot(direction) >= min_cos)
+                        .collect(),
+                )
+            }
+            Query::Boundary => Elements::Edges(
+                mesh.edge_handles()
+                    .into_iter()
+                    .filter(|edge| mesh.faces_in_edge(*edge).count() == 1)
+                    .collect(),
+            ),
+            Query::Sharp(angle) => {
+                let max_cos = angle.to_radians().cos();
+                Elements::Edges(
+                    mesh.edge_handles()
+                        .into_iter()
+                        .filter(|edge| {
+                            let mut faces = mesh.faces_in_edge(*edge);
+                            match (faces.next(), faces.next()) {
+                                (Some(a), Some(b)) => {
+                                    mesh.face_normal(a).dot(mesh.face_normal(b)) < max_cos
+                                }
+                                _ => false,
+                            }
+                        })
+                        .collect(),
+                )
+            }
+            Query::Grow(query, steps) => (0..*steps)
+                .fold(query.evaluate(mesh, expected), |elements, _| {
+                    elements.grow(mesh)
+                }),
+            Query::Shrink(query, steps) => (0..*steps)
+                .fold(query.evaluate(mesh, expected), |elements, _| {
+                    elements.shrink(mesh)
+                }),
+            Query::Union(a, b) => Self::combine(a, b, mesh, expected, Operation::Union),
+            Query::Intersection(a, b) => {
+                Self::combine(a, b, mesh, expected, Operation::Intersection)
+            }
+            Query::Difference(a, b) => Self::combine(a, b, mesh, expected, Operation::Difference),
+        }
+    }
+
+    /// The right hand side is evaluated in the kind of the left hand side.
+    fn combine(
+        a: &Query,
+        b: &Query,
+        mesh: &Mesh,
+        expected: ElementKind,
+        operation: Operation,
+    ) -> Elements {
+        let a = a.evaluate(mesh, expected);
+        let b = b.evaluate(mesh, a.kind());
+        a.combine(b, mesh, operation)
+    }
+}
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use glam::Vec3;
+
+use super::{ElementKind, Selection};
+use crate::type_system::data_types::mesh::PointHandle;
+use crate::type_system::data_types::mesh::tests::unit_box;
+use crate::type_system::trait_types::MeshMut0D;
+
+#[test]
+fn syntax_errors_are_reported() {
+    for valid in [
+        "all",
+        "*",
+        "0..4, 7",
+        "facing(0, 1, 0)",
+        "facing(0, -1.5, 0, 10)",
+        "sharp | boundary",
+        "grow(faces(3), 2) - shrink(all)",
+        "points(edges(sharp) & 0..4)",
+    ] {
+        assert!(Selection::new(valid).check().is_ok(), "{valid}");
+    }
+    for invalid in ["", "faces(", "facing(0, 1)", "0..", "sharpest", "all all"] {
+        assert!(Selection::new(invalid).check().is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn queries_resolve_against_mesh() {
+    let mesh = unit_box();
+    let count = |literal: &str, kind| {
+        let elements = Selection::new(literal).resolve(&mesh, kind).unwrap();
+        match &*elements {
+            super::Elements::Faces(faces) => faces.len(),
+            super::Elements::Edges(edges) => edges.len(),
+            super::Elements::Points(points) => points.len(),
+        }
+    };
+
+    assert_eq!(count("all", ElementKind::Face), 12);
+    assert_eq!(count("all", ElementKind::Edge), 18);
+    assert_eq!(count("0..3, 5", ElementKind::Face), 4);
+    assert_eq!(count("0..100", ElementKind::Point), 8);
+    assert_eq!(count("facing(0, 1, 0)", ElementKind::Face), 2);
+    assert_eq!(count("facing(0, 1, 0)", ElementKind::Point), 4);
+    assert_eq!(count("facing(0, 1, 0)", ElementKind::Edge), 5);
+    assert_eq!(count("all - facing(0, 1, 0)", ElementKind::Face), 10);
+    // The sides of the box are split by their flat diagonals.
+    assert_eq!(count("sharp", ElementKind::Edge), 12);
+    assert_eq!(count("edges - sharp", ElementKind::Edge), 6);
+    assert_eq!(count("boundary", ElementKind::Edge), 0);
+    // Faces are only selected when all of their edges are.
+    assert_eq!(count("faces(sharp)", ElementKind::Face), 0);
+    assert_eq!(count("grow(facing(0, 1, 0))", ElementKind::Face), 6);
+    assert_eq!(count("grow(facing(0, 1, 0), 2)", ElementKind::Face), 10);
+    assert_eq!(count("shrink(all - facing(0, 1, 0))", ElementKind::Face), 6);
+    assert_eq!(
+        count(
+            "points(facing(0, 1, 0)) & facing(1, 0, 0)",
+            ElementKind::Point
+        ),
+        2
+    );
+}
+
+#[test]
+fn results_are_cached_per_mesh() {
+    let mesh = unit_box();
+    let selection = Selection::new("facing(0, 1, 0)");
+    let first = selection.resolve(&mesh, ElementKind::Face).unwrap();
+    let second = selection.resolve(&mesh.clone(), ElementKind::Face).unwrap();
+    assert!(Arc::ptr_eq(&first, &second));
+
+    let mut moved = mesh.clone();
+    moved.set_position(PointHandle::new(1), Vec3::new(0.5, 2.0, 0.5));
+    let third = selection.resolve(&moved, ElementKind::Face).unwrap();
+    assert!(!Arc::ptr_eq(&first, &third));
+    assert!(Arc::ptr_eq(
+        &first,
+        &selection.resolve(&mesh, ElementKind::Face).unwrap()
+    ));
+}
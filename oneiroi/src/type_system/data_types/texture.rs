@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::type_system::data_types::Color;
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct Texture {
     albedo: Color,
 }
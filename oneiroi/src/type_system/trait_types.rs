@@ -1,8 +1,10 @@
 mod mesh;
 mod sequential_sample;
+mod transformable;
 
 pub use mesh::{MeshMut0D, MeshMut1D, MeshMut2D};
 pub use sequential_sample::SequentialSample;
+pub use transformable::Transformable;
 
 use crate::type_system::variants::TypeRef;
 
@@ -146,6 +146,4 @@ pub trait MeshMut2D: MeshMut1D {
         //SAFETY: Everything got initialized above.
         unsafe { points.assume_init() }
     }
-
-    fn bevel_edges(&mut self) {}
 }
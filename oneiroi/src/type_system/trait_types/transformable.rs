@@ -0,0 +1,7 @@
+use crate::type_system::data_types::Transform;
+
+/// Geometry which can be moved, rotated and scaled as a whole.
+pub trait Transformable {
+    /// Applies the Transform on top of the current placement.
+    fn transform(&mut self, transform: &Transform);
+}
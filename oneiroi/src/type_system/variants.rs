@@ -9,11 +9,11 @@ use crate::{
             Collection, Collider, Color, CubicBezier, Curve, DataType, DataTypeKind, Instance,
             Material, Mesh, Outline, Selection, Texture,
         },
-        trait_types::{MeshMut0D, SequentialSample, TraitType, TraitTypeKind},
+        trait_types::{MeshMut0D, SequentialSample, TraitType, TraitTypeKind, Transformable},
     },
 };
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum OwnedDataType {
     //Primitives
     Vec3(Vec3),
@@ -79,6 +79,20 @@ impl OwnedDataType {
         value.to_data_type_value()
     }
 
+    /// The value as geometry which can be transformed if it is one.
+    pub(crate) fn as_transformable_mut(&mut self) -> Option<&mut dyn Transformable> {
+        match self {
+            OwnedDataType::Vec3(vec3) => Some(vec3),
+            OwnedDataType::Transform(transform) => Some(&mut **transform),
+            OwnedDataType::Mesh(mesh) => Some(&mut **mesh),
+            OwnedDataType::Curve(curve) => Some(&mut **curve),
+            OwnedDataType::CubicBezier(cubic_bezier) => Some(&mut **cubic_bezier),
+            OwnedDataType::Collection(collection) => Some(&mut **collection),
+            OwnedDataType::Instance(instance) => Some(&mut **instance),
+            _ => None,
+        }
+    }
+
     pub fn get_data_type(&self) -> DataTypeKind {
         match self {
             OwnedDataType::Vec3(_) => DataTypeKind::Vec3,